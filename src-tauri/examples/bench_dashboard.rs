@@ -0,0 +1,234 @@
+// Benchmark muat dashboard di bawah request bersamaan lewat modul db aplikasi:
+// semua request lewat koneksi tulis (db::tulis, cara lama) dibandingkan pool
+// koneksi baca WAL (db::baca, dipakai handler sekarang).
+//
+// Jalankan dari folder src-tauri:
+//   cargo run --release --example bench_dashboard
+//   cargo run --release --example bench_dashboard -- 16   (jumlah klien)
+//
+// Database fixture dibuat di folder sementara lewat migrations::init_db, jadi
+// skema dan query sama persis dengan aplikasi. Setiap klien mengulang query yang
+// dipanggil halaman Dashboard (semua motor, penyewa, transaksi, bukti pelunasan,
+// pengeluaran + 7 pengaturan), sementara satu thread lain terus menyimpan
+// transaksi baru seperti pemakaian normal.
+
+use aplikasipembukuan_lib::{db, migrations, paths};
+use rusqlite::{Connection, Result};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const JUMLAH_KLIEN_DEFAULT: usize = 8;
+const PUTARAN_PER_KLIEN: usize = 10;
+
+const KEY_PENGATURAN: [&str; 7] = [
+    "saldo_awal_kas",
+    "saldo_awal_bank",
+    "saldo_awal_ewallet",
+    "diskon_persen",
+    "diskon_tanggal_mulai",
+    "diskon_tanggal_berakhir",
+    "diskon_aktif",
+];
+
+// Isi fixture lewat koneksi tulis aplikasi, di atas skema hasil migrasi
+fn buat_data() -> Result<()> {
+    migrations::init_db()?;
+    db::with_connection_mut(|conn| {
+        let tx = conn.transaction()?;
+        for i in 0..60 {
+            tx.execute(
+                "INSERT INTO motor (nama, plat, tipe_motor, tahun, harga_harian, foto, status)
+                 VALUES (?1, ?2, 'Matic', '2022', 75000, '', 'tersedia')",
+                (format!("Motor {}", i), format!("B {} XYZ", 1000 + i)),
+            )?;
+        }
+        for i in 0..3_000 {
+            tx.execute(
+                "INSERT INTO penyewa (nama, no_hp, no_ktp, alamat)
+                 VALUES (?1, '081234567890', ?2, 'Jl. Merdeka No. 1')",
+                (format!("Penyewa {}", i), format!("{:016}", i)),
+            )?;
+        }
+        for i in 0..60_000 {
+            tx.execute(
+                "INSERT INTO transaksi (motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana,
+                     tanggal_kembali_aktual, hari_terlambat, total_bayar, status, denda)
+                 VALUES (?1, ?2, '2026-01-01', '2026-01-03', '2026-01-03', 0, 150000, 'selesai', 0)",
+                (i % 60 + 1, i % 3_000 + 1),
+            )?;
+        }
+        for i in 0..40_000 {
+            tx.execute(
+                "INSERT INTO bukti_pelunasan (transaksi_id, tanggal_bayar, jumlah_bayar, metode_bayar,
+                     foto_bukti)
+                 VALUES (?1, '2026-01-03', 150000, 'tunai', '')",
+                [i + 1],
+            )?;
+        }
+        for _ in 0..5_000 {
+            tx.execute(
+                "INSERT INTO pengeluaran_rental (tanggal, jenis, nominal, keterangan, sumber_dana)
+                 VALUES ('2026-01-05', 'servis', 50000, 'Ganti oli', 'Kas')",
+                [],
+            )?;
+        }
+        for key in KEY_PENGATURAN {
+            tx.execute(
+                "INSERT OR IGNORE INTO pengaturan (key, value) VALUES (?1, '0')",
+                [key],
+            )?;
+        }
+        tx.commit()
+    })
+}
+
+// Query yang sama dengan command yang dipanggil halaman Dashboard
+fn muat_dashboard(conn: &Connection) -> Result<usize> {
+    let jumlah = db::get_all_motor(conn)?.len()
+        + db::get_all_penyewa(conn)?.len()
+        + db::get_all_transaksi(conn)?.len()
+        + db::get_all_bukti_pelunasan(conn)?.len()
+        + db::get_all_pengeluaran_rental(conn)?.len();
+    for key in KEY_PENGATURAN {
+        db::get_pengaturan_value(conn, key)?;
+    }
+    Ok(jumlah)
+}
+
+fn simpan_transaksi(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT INTO transaksi (motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana,
+             hari_terlambat, total_bayar, status, denda)
+         VALUES (1, 1, '2026-02-01', '2026-02-02', 0, 75000, 'disewa', 0)",
+        [],
+    )?;
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    // Semua request antre di koneksi tulis
+    KoneksiTulis,
+    // Pool koneksi baca (query_only)
+    PoolBaca,
+}
+
+impl Mode {
+    fn nama(self) -> &'static str {
+        match self {
+            Mode::KoneksiTulis => "db::tulis (satu koneksi)",
+            Mode::PoolBaca => "db::baca (pool baca WAL)",
+        }
+    }
+
+    // Dipanggil seperti command Tauri: future db::baca/db::tulis ditunggu di runtime async
+    fn muat(self) -> Result<usize> {
+        match self {
+            Mode::KoneksiTulis => tauri::async_runtime::block_on(db::tulis(muat_dashboard)),
+            Mode::PoolBaca => tauri::async_runtime::block_on(db::baca(muat_dashboard)),
+        }
+    }
+}
+
+struct Hasil {
+    total: Duration,
+    latensi: Vec<Duration>,
+    jumlah_tulis: usize,
+}
+
+fn jalankan(mode: Mode, jumlah_klien: usize) -> Hasil {
+    let selesai = Arc::new(AtomicBool::new(false));
+    let jumlah_tulis = Arc::new(AtomicUsize::new(0));
+
+    let penulis = {
+        let selesai = selesai.clone();
+        let jumlah_tulis = jumlah_tulis.clone();
+        thread::spawn(move || {
+            while !selesai.load(Ordering::Relaxed) {
+                db::with_connection(simpan_transaksi).expect("gagal menyimpan transaksi");
+                jumlah_tulis.fetch_add(1, Ordering::Relaxed);
+                thread::sleep(Duration::from_millis(5));
+            }
+        })
+    };
+
+    let mulai = Instant::now();
+    let klien: Vec<_> = (0..jumlah_klien)
+        .map(|_| {
+            thread::spawn(move || {
+                (0..PUTARAN_PER_KLIEN)
+                    .map(|_| {
+                        let t = Instant::now();
+                        mode.muat().expect("gagal memuat dashboard");
+                        t.elapsed()
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    let mut latensi: Vec<Duration> = klien
+        .into_iter()
+        .flat_map(|k| k.join().expect("thread klien panik"))
+        .collect();
+    let total = mulai.elapsed();
+
+    selesai.store(true, Ordering::Relaxed);
+    penulis.join().expect("thread penulis panik");
+
+    latensi.sort();
+    Hasil {
+        total,
+        latensi,
+        jumlah_tulis: jumlah_tulis.load(Ordering::Relaxed),
+    }
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+fn cetak(nama: &str, hasil: &Hasil) {
+    let n = hasil.latensi.len();
+    let rata = hasil.latensi.iter().sum::<Duration>() / n as u32;
+    let p95 = hasil.latensi[(n * 95 / 100).min(n - 1)];
+    println!(
+        "{:<26} total {:>8.1} ms | {:>6.1} dashboard/s | rata-rata {:>7.1} ms | p95 {:>7.1} ms | maks {:>7.1} ms | {} tulis",
+        nama,
+        ms(hasil.total),
+        n as f64 / hasil.total.as_secs_f64(),
+        ms(rata),
+        ms(p95),
+        ms(hasil.latensi[n - 1]),
+        hasil.jumlah_tulis,
+    );
+}
+
+fn main() -> Result<()> {
+    let jumlah_klien = std::env::args()
+        .nth(1)
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(JUMLAH_KLIEN_DEFAULT);
+
+    // Folder data dibaca sekali saat modul db pertama dipakai
+    let folder = std::env::temp_dir().join(format!("bench_dashboard-{}", std::process::id()));
+    std::fs::remove_dir_all(&folder).ok();
+    std::env::set_var(paths::ENV_DATA_DIR, &folder);
+
+    println!("📦 Menyiapkan data di {:?}...", folder);
+    buat_data()?;
+    println!(
+        "⏱️ {} klien x {} muat dashboard, 1 penulis latar\n",
+        jumlah_klien, PUTARAN_PER_KLIEN
+    );
+
+    for mode in [Mode::KoneksiTulis, Mode::PoolBaca] {
+        // Pemanasan supaya cache halaman kedua mode sama-sama terisi
+        mode.muat()?;
+        cetak(mode.nama(), &jalankan(mode, jumlah_klien));
+    }
+
+    std::fs::remove_dir_all(&folder).ok();
+    Ok(())
+}
@@ -0,0 +1,1197 @@
+use crate::models::{BackupHistory, InfoRestore};
+use chrono::{Datelike, Duration, Local, NaiveDateTime};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use zip::write::{SimpleFileOptions, StreamWriter};
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+// Isi arsip backup (.zip):
+//   manifest.json      -> versi aplikasi, versi skema, checksum semua file
+//   database.sqlite    -> snapshot database lewat SQLite online backup API
+//   lampiran/...       -> foto & dokumen (lampiran terpadu)
+//   motor/..., dst     -> folder foto versi lama yang masih ada di folder data
+//   uploads/...        -> bukti pelunasan versi lama
+const NAMA_MANIFEST: &str = "manifest.json";
+const NAMA_DATABASE: &str = "database.sqlite";
+const FOLDER_LAMPIRAN: &str = "lampiran";
+
+const MAGIC_SQLITE: &[u8] = b"SQLite format 3\0";
+const MAGIC_ZIP: &[u8] = b"PK\x03\x04";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManifestBackup {
+    pub app_version: String,
+    pub schema_version: i32,
+    pub dibuat: String,
+    // Folder data asal, untuk mengarahkan ulang path foto saat restore di lokasi lain
+    pub data_dir: String,
+    pub file: Vec<FileBackup>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileBackup {
+    pub path: String,
+    pub ukuran: u64,
+    pub sha256: String,
+}
+
+// Folder foto yang ikut diarsipkan: (nama di dalam arsip, folder di disk)
+fn folder_foto() -> Vec<(String, PathBuf)> {
+    let mut folder = vec![(FOLDER_LAMPIRAN.to_string(), crate::lampiran::folder())];
+    for nama in crate::paths::FOLDER_FOTO_LAMA
+        .into_iter()
+        .chain([crate::paths::FOLDER_UPLOADS_LAMA])
+    {
+        folder.push((nama.to_string(), crate::paths::data_dir().join(nama)));
+    }
+    folder
+}
+
+fn file_sementara(path: &Path) -> PathBuf {
+    let nama = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "backup".to_string());
+    path.with_file_name(format!(".{}.tmp", nama))
+}
+
+// Salin isi file ke writer sambil menghitung sha256
+fn salin_dengan_hash(src: &mut impl Read, dest: &mut impl Write) -> std::io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    let mut ukuran = 0u64;
+    loop {
+        let n = src.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        dest.write_all(&buf[..n])?;
+        ukuran += n as u64;
+    }
+    Ok((ukuran, hex::encode(hasher.finalize())))
+}
+
+fn tambah_file<W: Write>(
+    zip: &mut ZipWriter<StreamWriter<W>>,
+    src: &mut impl Read,
+    nama: &str,
+    kompres: bool,
+) -> Result<FileBackup, String> {
+    // Foto sudah terkompresi, cukup disimpan apa adanya
+    let metode = if kompres {
+        CompressionMethod::Deflated
+    } else {
+        CompressionMethod::Stored
+    };
+    let options = SimpleFileOptions::default()
+        .compression_method(metode)
+        .large_file(true);
+    zip.start_file(nama, options)
+        .map_err(|e| format!("Gagal menulis arsip: {}", e))?;
+
+    let (ukuran, sha256) =
+        salin_dengan_hash(src, zip).map_err(|e| format!("Gagal menulis {}: {}", nama, e))?;
+
+    Ok(FileBackup {
+        path: nama.to_string(),
+        ukuran,
+        sha256,
+    })
+}
+
+// Tulis arsip secara berurutan (tanpa seek) supaya bisa langsung masuk ke writer enkripsi
+fn tulis_arsip<W: Write>(writer: W, database: &[u8]) -> Result<(ManifestBackup, W), String> {
+    let mut zip = ZipWriter::new_stream(writer);
+
+    let mut isi_database = database;
+    let mut daftar = vec![tambah_file(
+        &mut zip,
+        &mut isi_database,
+        NAMA_DATABASE,
+        true,
+    )?];
+
+    for (nama_folder, folder) in folder_foto() {
+        if !folder.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&folder).map_err(|e| format!("Gagal membaca folder: {}", e))? {
+            let entry = entry.map_err(|e| format!("Gagal membaca folder: {}", e))?;
+            if !entry.path().is_file() {
+                continue;
+            }
+            let nama = format!("{}/{}", nama_folder, entry.file_name().to_string_lossy());
+            let mut file = File::open(entry.path())
+                .map_err(|e| format!("Gagal membaca {}: {}", entry.path().display(), e))?;
+            daftar.push(tambah_file(&mut zip, &mut file, &nama, false)?);
+        }
+    }
+
+    let manifest = ManifestBackup {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: crate::migrations::SCHEMA_VERSION,
+        dibuat: Local::now().to_rfc3339(),
+        data_dir: crate::paths::data_dir().to_string_lossy().to_string(),
+        file: daftar,
+    };
+    let json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    zip.start_file(NAMA_MANIFEST, SimpleFileOptions::default())
+        .map_err(|e| format!("Gagal menulis arsip: {}", e))?;
+    zip.write_all(&json)
+        .map_err(|e| format!("Gagal menulis arsip: {}", e))?;
+
+    let writer = zip
+        .finish()
+        .map_err(|e| format!("Gagal menyelesaikan arsip: {}", e))?;
+    Ok((manifest, writer.into_inner()))
+}
+
+fn tulis_ke_file(
+    path: &Path,
+    sandi: Option<&str>,
+    database: &[u8],
+) -> Result<ManifestBackup, String> {
+    let gagal = |e: std::io::Error| format!("Gagal menyelesaikan arsip: {}", e);
+    let file = BufWriter::new(
+        File::create(path).map_err(|e| format!("Gagal membuat file backup: {}", e))?,
+    );
+    let (manifest, mut file) = match sandi {
+        Some(sandi) => {
+            let penulis = crate::enkripsi::PenulisTerenkripsi::new(file, sandi)?;
+            let (manifest, penulis) = tulis_arsip(penulis, database)?;
+            (manifest, penulis.selesai().map_err(gagal)?)
+        }
+        None => tulis_arsip(file, database)?,
+    };
+    file.flush().map_err(gagal)?;
+    file.get_ref().sync_all().map_err(gagal)?;
+    Ok(manifest)
+}
+
+// Buat arsip backup lengkap. Database disalin ke memori lewat online backup API sehingga
+// aman walaupun koneksi utama sedang terbuka. Arsip (terenkripsi jika kata sandi diisi)
+// ditulis langsung ke file sementara di folder tujuan lalu di-rename, jadi isi database
+// tidak pernah tersimpan polos di disk selama backup.
+pub fn buat_arsip(dest: &Path, sandi: Option<&str>) -> Result<ManifestBackup, String> {
+    if let Some(sandi) = sandi {
+        crate::enkripsi::validasi_sandi(sandi)?;
+    }
+    // Isi database terenkripsi tidak boleh keluar sebagai arsip polos: tanpa kata sandi
+    // backup, arsip dienkripsi dengan kata sandi database (apa adanya, tanpa cek panjang)
+    let kunci_db = crate::db::kunci();
+    let sandi = sandi.or(kunci_db.as_deref());
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Gagal membuat folder: {}", e))?;
+    }
+
+    let database = crate::db::with_connection(crate::db::salin_ke_memori)
+        .map_err(|e| format!("Gagal snapshot database: {}", e))?;
+
+    let sementara = file_sementara(dest);
+    let manifest = match tulis_ke_file(&sementara, sandi, &database) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            fs::remove_file(&sementara).ok();
+            return Err(e);
+        }
+    };
+
+    fs::rename(&sementara, dest).map_err(|e| format!("Gagal menyimpan file backup: {}", e))?;
+    Ok(manifest)
+}
+
+fn buka_arsip<R: Read + Seek>(sumber: R) -> Result<ZipArchive<R>, String> {
+    ZipArchive::new(sumber).map_err(|e| format!("Arsip backup rusak: {}", e))
+}
+
+fn baca_manifest<R: Read + Seek>(arsip: &mut ZipArchive<R>) -> Result<ManifestBackup, String> {
+    let entry = arsip
+        .by_name(NAMA_MANIFEST)
+        .map_err(|_| "Arsip tidak memiliki manifest.json".to_string())?;
+    serde_json::from_reader(entry).map_err(|e| format!("Manifest backup tidak valid: {}", e))
+}
+
+// Ekstrak foto di manifest ke folder tujuan sambil mencocokkan checksum. Database
+// dikembalikan sebagai isi di memori, tidak ditulis ke disk.
+fn ekstrak_arsip<R: Read + Seek>(
+    arsip: &mut ZipArchive<R>,
+    dest: &Path,
+) -> Result<(ManifestBackup, Vec<u8>), String> {
+    let manifest = baca_manifest(arsip)?;
+    if manifest.schema_version > crate::migrations::SCHEMA_VERSION {
+        return Err(format!(
+            "Backup dibuat oleh versi aplikasi yang lebih baru ({}), perbarui aplikasi terlebih dahulu",
+            manifest.app_version
+        ));
+    }
+    if !manifest.file.iter().any(|f| f.path == NAMA_DATABASE) {
+        return Err("Arsip tidak berisi database".to_string());
+    }
+
+    let mut database = Vec::new();
+    for info in &manifest.file {
+        let mut entry = arsip
+            .by_name(&info.path)
+            .map_err(|_| format!("File {} tidak ada di arsip", info.path))?;
+        let gagal = |e: std::io::Error| format!("Gagal mengekstrak {}: {}", info.path, e);
+
+        let (ukuran, sha256) = if info.path == NAMA_DATABASE {
+            salin_dengan_hash(&mut entry, &mut database).map_err(gagal)?
+        } else {
+            // Tolak path yang keluar dari folder tujuan (../, path absolut)
+            let relatif = entry
+                .enclosed_name()
+                .ok_or_else(|| format!("Path tidak valid di arsip: {}", info.path))?;
+            let tujuan = dest.join(relatif);
+            if let Some(parent) = tujuan.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Gagal membuat folder: {}", e))?;
+            }
+            let mut out = BufWriter::new(File::create(&tujuan).map_err(gagal)?);
+            let hasil = salin_dengan_hash(&mut entry, &mut out).map_err(gagal)?;
+            out.flush().map_err(gagal)?;
+            hasil
+        };
+
+        if ukuran != info.ukuran || sha256 != info.sha256 {
+            return Err(format!(
+                "Checksum {} tidak cocok, arsip backup rusak",
+                info.path
+            ));
+        }
+    }
+    Ok((manifest, database))
+}
+
+// Salin isi folder (tanpa subfolder), kembalikan jumlah file yang disalin
+pub fn salin_folder(src: &Path, dest: &Path) -> Result<usize, String> {
+    if !src.is_dir() {
+        return Ok(0);
+    }
+    fs::create_dir_all(dest).map_err(|e| format!("Gagal membuat folder: {}", e))?;
+
+    let mut jumlah = 0;
+    for entry in fs::read_dir(src).map_err(|e| format!("Gagal membaca folder: {}", e))? {
+        let entry = entry.map_err(|e| format!("Gagal membaca folder: {}", e))?;
+        if entry.path().is_file() {
+            fs::copy(entry.path(), dest.join(entry.file_name()))
+                .map_err(|e| format!("Gagal menyalin {:?}: {}", entry.file_name(), e))?;
+            jumlah += 1;
+        }
+    }
+    Ok(jumlah)
+}
+
+// Tabel yang pasti ada sejak versi pertama; tanpa ini file bukan database aplikasi ini
+const TABEL_WAJIB: [&str; 3] = ["motor", "penyewa", "transaksi"];
+
+// Folder kerja restore dan snapshot untuk "batalkan restore terakhir"
+const FOLDER_PEMULIHAN: &str = "pemulihan";
+const FOLDER_KERJA: &str = "proses";
+const NAMA_SNAPSHOT: &str = "sebelum_restore.zip";
+const NAMA_INFO_RESTORE: &str = "restore_terakhir.json";
+
+fn folder_pemulihan() -> PathBuf {
+    crate::paths::data_dir().join(FOLDER_PEMULIHAN)
+}
+
+enum JenisBackup {
+    Sqlite,
+    Zip,
+    Terenkripsi,
+}
+
+fn jenis_backup(src: &Path) -> Result<JenisBackup, String> {
+    let mut header = [0u8; 16];
+    let n = File::open(src)
+        .and_then(|mut f| f.read(&mut header))
+        .map_err(|e| format!("Gagal membaca file: {}", e))?;
+
+    if header[..n].starts_with(crate::enkripsi::MAGIC) {
+        Ok(JenisBackup::Terenkripsi)
+    } else if header[..n].starts_with(MAGIC_SQLITE) {
+        Ok(JenisBackup::Sqlite)
+    } else if header[..n].starts_with(MAGIC_ZIP) {
+        Ok(JenisBackup::Zip)
+    } else {
+        Err("File yang dipilih bukan file backup yang valid".to_string())
+    }
+}
+
+// Periksa salinan database hasil restore (di memori) lalu naikkan skemanya ke versi
+// saat ini. Semua perubahan dilakukan pada salinan, database utama belum disentuh.
+fn siapkan_database(conn: &Connection) -> Result<(), String> {
+    let integritas: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| format!("Database backup tidak bisa dibaca: {}", e))?;
+    if integritas != "ok" {
+        return Err(format!("Database backup rusak: {}", integritas));
+    }
+
+    let versi: i32 = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| format!("Gagal membaca versi database: {}", e))?;
+    if versi > crate::migrations::SCHEMA_VERSION {
+        return Err(
+            "Backup dibuat oleh versi aplikasi yang lebih baru, perbarui aplikasi terlebih dahulu"
+                .to_string(),
+        );
+    }
+
+    for tabel in TABEL_WAJIB {
+        let ada: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                [tabel],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if ada == 0 {
+            return Err(format!(
+                "File bukan database aplikasi ini (tabel {} tidak ada)",
+                tabel
+            ));
+        }
+    }
+
+    crate::migrations::migrate(conn)
+        .map_err(|e| format!("Gagal memperbarui skema database backup: {}", e))
+}
+
+// Ganti isi database utama dengan database yang sudah disiapkan, dalam satu transaksi
+// di bawah mutex DB sehingga perintah lain tidak pernah melihat database setengah jadi
+fn ganti_database(sumber: &Connection) -> Result<(), String> {
+    crate::db::ganti_isi_dari(sumber).map_err(|e| format!("Gagal memulihkan database: {}", e))
+}
+
+// Folder pendamping backup format lama (.sqlite): <nama_backup>_lampiran/
+fn folder_lampiran_lama(db_file: &Path) -> PathBuf {
+    let stem = db_file
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "backup".to_string());
+    db_file.with_file_name(format!("{}_lampiran", stem))
+}
+
+fn proses_pulihkan(
+    src: &Path,
+    sandi: Option<&str>,
+    kerja: &Path,
+    buat_snapshot: bool,
+) -> Result<(), String> {
+    let gagal_baca = |e: std::io::Error| format!("Gagal membaca file: {}", e);
+    let (manifest, database) = match jenis_backup(src)? {
+        JenisBackup::Sqlite => (None, fs::read(src).map_err(gagal_baca)?),
+        JenisBackup::Zip => {
+            let file = BufReader::new(File::open(src).map_err(gagal_baca)?);
+            let (manifest, database) = ekstrak_arsip(&mut buka_arsip(file)?, kerja)?;
+            (Some(manifest), database)
+        }
+        // Backup terenkripsi selalu berisi arsip .zip, dibaca sambil didekripsi
+        JenisBackup::Terenkripsi => {
+            let sandi = sandi.ok_or_else(|| {
+                "Backup ini terenkripsi, masukkan kata sandi untuk memulihkannya".to_string()
+            })?;
+            let file = File::open(src).map_err(gagal_baca)?;
+            let pembaca = crate::enkripsi::PembacaTerenkripsi::new(file, sandi)?;
+            let (manifest, database) = ekstrak_arsip(&mut buka_arsip(pembaca)?, kerja)?;
+            (Some(manifest), database)
+        }
+    };
+
+    // Database backup dibuka di memori supaya file asli tidak ikut termigrasi dan isi
+    // backup terenkripsi tidak pernah tersimpan polos di disk
+    let kandidat = crate::db::buka_dari_memori(&database)
+        .map_err(|e| format!("Database backup tidak bisa dibaca: {}", e))?;
+    drop(database);
+    siapkan_database(&kandidat)?;
+
+    if buat_snapshot {
+        // Snapshot ikut dienkripsi dengan kata sandi database jika database terenkripsi
+        buat_arsip(&folder_pemulihan().join(NAMA_SNAPSHOT), None)
+            .map_err(|e| format!("Gagal membuat snapshot sebelum restore: {}", e))?;
+    }
+    ganti_database(&kandidat)?;
+
+    match manifest {
+        Some(manifest) => {
+            for (nama_folder, folder) in folder_foto() {
+                salin_folder(&kerja.join(&nama_folder), &folder)?;
+            }
+            crate::db::with_connection(|conn| {
+                let data_dir = crate::paths::data_dir();
+                crate::lampiran::ganti_folder(conn, Path::new(&manifest.data_dir), data_dir)?;
+                crate::lampiran::perbaiki_path(conn, &crate::lampiran::folder())
+            })
+        }
+        None => {
+            let backup_lampiran = folder_lampiran_lama(src);
+            if !backup_lampiran.is_dir() {
+                return Ok(());
+            }
+            let lampiran = crate::lampiran::folder();
+            salin_folder(&backup_lampiran, &lampiran)?;
+            crate::db::with_connection(|conn| crate::lampiran::perbaiki_path(conn, &lampiran))
+        }
+    }
+    .map_err(|e| format!("Gagal memperbarui path lampiran: {}", e))
+}
+
+fn pulihkan_dari(src: &Path, sandi: Option<&str>, buat_snapshot: bool) -> Result<(), String> {
+    let kerja = folder_pemulihan().join(FOLDER_KERJA);
+    fs::remove_dir_all(&kerja).ok();
+    fs::create_dir_all(&kerja).map_err(|e| format!("Gagal membuat folder: {}", e))?;
+
+    let hasil = proses_pulihkan(src, sandi, &kerja, buat_snapshot);
+    fs::remove_dir_all(&kerja).ok();
+    hasil
+}
+
+// Pulihkan dari arsip .zip (boleh terenkripsi) atau file .sqlite biasa (backup versi lama).
+// Kondisi sebelum restore disimpan sebagai snapshot untuk batalkan_restore_terakhir.
+pub fn pulihkan(src: &Path, sandi: Option<&str>) -> Result<(), String> {
+    pulihkan_dari(src, sandi, true)?;
+
+    let info = InfoRestore {
+        waktu: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        sumber: src.to_string_lossy().to_string(),
+        snapshot: folder_pemulihan()
+            .join(NAMA_SNAPSHOT)
+            .to_string_lossy()
+            .to_string(),
+    };
+    let json = serde_json::to_vec_pretty(&info).map_err(|e| e.to_string())?;
+    fs::write(folder_pemulihan().join(NAMA_INFO_RESTORE), json)
+        .map_err(|e| format!("Gagal menyimpan info restore: {}", e))
+}
+
+// Hapus sisa backup/restore/enkripsi yang terhenti (aplikasi ditutup paksa, listrik
+// padam): file sementara di folder data dan folder kerja restore. Versi lama juga
+// menaruh snapshot database dan arsip polos di sini. Dipanggil sekali saat start.
+pub fn bersihkan_file_sementara() {
+    let data_dir = crate::paths::data_dir();
+    if let Ok(entries) = fs::read_dir(data_dir) {
+        for entry in entries.flatten() {
+            let nama = entry.file_name().to_string_lossy().to_string();
+            let sementara = (nama.starts_with('.') && nama.ends_with(".tmp"))
+                || [crate::db::AKHIRAN_ENKRIPSI, crate::db::AKHIRAN_PULIHKAN]
+                    .iter()
+                    .any(|akhiran| nama.ends_with(&format!(".{}", akhiran)));
+            if sementara && entry.path().is_file() {
+                match fs::remove_file(entry.path()) {
+                    Ok(()) => println!("🗑️ File sementara dihapus: {}", nama),
+                    Err(e) => eprintln!("❌ Error hapus file sementara {}: {}", nama, e),
+                }
+            }
+        }
+    }
+    fs::remove_dir_all(folder_pemulihan().join(FOLDER_KERJA)).ok();
+}
+
+pub fn info_restore_terakhir() -> Option<InfoRestore> {
+    let json = fs::read(folder_pemulihan().join(NAMA_INFO_RESTORE)).ok()?;
+    let info: InfoRestore = serde_json::from_slice(&json).ok()?;
+    Path::new(&info.snapshot).is_file().then_some(info)
+}
+
+// Kembalikan database dan lampiran ke kondisi sebelum restore terakhir.
+// Snapshot dihapus setelah dipakai sehingga batal hanya bisa sekali.
+pub fn batalkan_restore_terakhir() -> Result<InfoRestore, String> {
+    let info = info_restore_terakhir()
+        .ok_or_else(|| "Tidak ada restore yang bisa dibatalkan".to_string())?;
+
+    let kunci = crate::db::kunci();
+    pulihkan_dari(Path::new(&info.snapshot), kunci.as_deref(), false)?;
+
+    fs::remove_file(&info.snapshot).ok();
+    fs::remove_file(folder_pemulihan().join(NAMA_INFO_RESTORE)).ok();
+    Ok(info)
+}
+
+// Pengaturan backup otomatis (tabel pengaturan)
+const KEY_FOLDER: &str = "backup_folder";
+const KEY_HARIAN: &str = "backup_harian";
+const KEY_SAAT_KELUAR: &str = "backup_saat_keluar";
+const KEY_SIMPAN_HARIAN: &str = "backup_simpan_harian";
+const KEY_SIMPAN_MINGGUAN: &str = "backup_simpan_mingguan";
+const KEY_SIMPAN_BULANAN: &str = "backup_simpan_bulanan";
+// Kata sandi backup otomatis disimpan di file terpisah di folder data (hanya bisa dibaca
+// pemilik), bukan di tabel pengaturan: isi database ikut ke backup yang tidak dienkripsi.
+// File ini tidak ikut diarsipkan.
+const FILE_SANDI: &str = ".backup_sandi";
+// Key pengaturan dengan awalan ini tidak bisa dibaca atau diubah lewat get/set_pengaturan.
+// Versi lama menyimpan kata sandi backup otomatis di key "backup_sandi".
+const AWALAN_KEY_RAHASIA: &str = "backup_sandi";
+
+const SIMPAN_HARIAN_DEFAULT: usize = 7;
+const SIMPAN_MINGGUAN_DEFAULT: usize = 4;
+const SIMPAN_BULANAN_DEFAULT: usize = 12;
+
+// Hanya file dengan awalan ini yang disentuh rotasi, backup manual di folder yang sama aman
+const AWALAN_OTOMATIS: &str = "backup_otomatis_";
+const FORMAT_WAKTU_FILE: &str = "%Y%m%d_%H%M%S";
+const EKSTENSI_ARSIP: &str = "zip";
+const EKSTENSI_TERENKRIPSI: &str = "enc";
+
+// Jeda sebelum backup harian yang gagal dicoba lagi
+const JEDA_COBA_ULANG_MENIT: i64 = 120;
+
+pub const PEMICU_HARIAN: &str = "harian";
+pub const PEMICU_KELUAR: &str = "keluar";
+pub const PEMICU_MANUAL: &str = "manual";
+
+pub struct JadwalBackup {
+    pub folder: Option<PathBuf>,
+    pub harian: bool,
+    pub saat_keluar: bool,
+    pub simpan_harian: usize,
+    pub simpan_mingguan: usize,
+    pub simpan_bulanan: usize,
+    pub sandi: Option<String>,
+}
+
+pub fn baca_jadwal(conn: &Connection) -> rusqlite::Result<JadwalBackup> {
+    let aktif = |key: &str| -> rusqlite::Result<bool> {
+        Ok(matches!(
+            crate::db::get_pengaturan_value(conn, key)?
+                .as_deref()
+                .map(str::trim),
+            Some("1") | Some("true")
+        ))
+    };
+    let jumlah = |key: &str, default: usize| -> rusqlite::Result<usize> {
+        Ok(crate::db::get_pengaturan_value(conn, key)?
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(default))
+    };
+
+    Ok(JadwalBackup {
+        folder: crate::db::get_pengaturan_value(conn, KEY_FOLDER)?
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from),
+        harian: aktif(KEY_HARIAN)?,
+        saat_keluar: aktif(KEY_SAAT_KELUAR)?,
+        simpan_harian: jumlah(KEY_SIMPAN_HARIAN, SIMPAN_HARIAN_DEFAULT)?,
+        simpan_mingguan: jumlah(KEY_SIMPAN_MINGGUAN, SIMPAN_MINGGUAN_DEFAULT)?,
+        simpan_bulanan: jumlah(KEY_SIMPAN_BULANAN, SIMPAN_BULANAN_DEFAULT)?,
+        sandi: baca_sandi(&path_sandi()),
+    })
+}
+
+pub fn key_rahasia(key: &str) -> bool {
+    key.starts_with(AWALAN_KEY_RAHASIA)
+}
+
+fn path_sandi() -> PathBuf {
+    crate::paths::data_dir().join(FILE_SANDI)
+}
+
+fn baca_sandi(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().filter(|s| !s.is_empty())
+}
+
+// Tulis lewat file sementara lalu rename. Di Unix file dibuat dengan mode 0600; di
+// Windows folder data berada di profil user sehingga hanya user itu yang bisa membaca.
+fn tulis_sandi(path: &Path, sandi: &str) -> Result<(), String> {
+    let sementara = file_sementara(path);
+    let mut opsi = fs::OpenOptions::new();
+    opsi.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut opsi, 0o600);
+    opsi.open(&sementara)
+        .and_then(|mut file| {
+            file.write_all(sandi.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&sementara, path))
+        .map_err(|e| {
+            fs::remove_file(&sementara).ok();
+            format!("Gagal menyimpan kata sandi backup otomatis: {}", e)
+        })
+}
+
+fn atur_sandi_di(path: &Path, sandi: Option<&str>) -> Result<(), String> {
+    match sandi.filter(|s| !s.is_empty()) {
+        Some(sandi) => {
+            crate::enkripsi::validasi_sandi(sandi)?;
+            tulis_sandi(path, sandi)
+        }
+        None => match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Gagal menghapus kata sandi backup otomatis: {}", e))
+            }
+            _ => Ok(()),
+        },
+    }
+}
+
+// Atur atau hapus (None) kata sandi backup otomatis
+pub fn atur_sandi_terjadwal(sandi: Option<&str>) -> Result<(), String> {
+    atur_sandi_di(&path_sandi(), sandi)
+}
+
+// Pindahkan kata sandi yang disimpan versi lama di tabel pengaturan ke file, lalu hapus
+// dari database. Jika file sudah ada (mis. restore backup lama) kata sandi sekarang dipakai.
+fn pindahkan_sandi_lama_ke(conn: &Connection, path: &Path) -> rusqlite::Result<()> {
+    let Some(lama) = crate::db::get_pengaturan_value(conn, AWALAN_KEY_RAHASIA)? else {
+        return Ok(());
+    };
+    if !lama.is_empty() && !path.exists() {
+        if let Err(e) = tulis_sandi(path, &lama) {
+            eprintln!("❌ Error pindahkan kata sandi backup: {}", e);
+            return Ok(());
+        }
+    }
+    conn.execute(
+        "DELETE FROM pengaturan WHERE key = ?1",
+        [AWALAN_KEY_RAHASIA],
+    )?;
+    Ok(())
+}
+
+pub fn pindahkan_sandi_lama(conn: &Connection) -> rusqlite::Result<()> {
+    pindahkan_sandi_lama_ke(conn, &path_sandi())
+}
+
+fn map_backup_history(row: &rusqlite::Row) -> rusqlite::Result<BackupHistory> {
+    Ok(BackupHistory {
+        backup_id: row.get(0)?,
+        waktu: row.get(1)?,
+        pemicu: row.get(2)?,
+        path: row.get(3)?,
+        ukuran: row.get(4)?,
+        jumlah_file: row.get(5)?,
+        status: row.get(6)?,
+        pesan: row.get(7)?,
+    })
+}
+
+const BACKUP_HISTORY_COLUMNS: &str =
+    "backup_id, waktu, pemicu, path, ukuran, jumlah_file, status, pesan";
+
+pub fn get_backup_history(conn: &Connection, limit: i64) -> rusqlite::Result<Vec<BackupHistory>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {BACKUP_HISTORY_COLUMNS} FROM backup_history ORDER BY waktu DESC, backup_id DESC LIMIT ?1"
+    ))?;
+    let rows = stmt.query_map([limit], map_backup_history)?;
+    rows.collect()
+}
+
+// Simpan hasil backup (sukses maupun gagal) ke riwayat
+pub fn catat_riwayat(
+    pemicu: &str,
+    dest: &Path,
+    hasil: &Result<ManifestBackup, String>,
+) -> rusqlite::Result<BackupHistory> {
+    let (ukuran, jumlah_file, status, pesan) = match hasil {
+        Ok(manifest) => (
+            fs::metadata(dest).map(|m| m.len() as i64).unwrap_or(0),
+            manifest.file.len() as i64,
+            "sukses",
+            None,
+        ),
+        Err(e) => (0, 0, "gagal", Some(e.clone())),
+    };
+
+    crate::db::with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO backup_history (waktu, pemicu, path, ukuran, jumlah_file, status, pesan)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            (
+                Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                pemicu,
+                dest.to_string_lossy().to_string(),
+                ukuran,
+                jumlah_file,
+                status,
+                pesan,
+            ),
+        )?;
+        conn.query_row(
+            &format!("SELECT {BACKUP_HISTORY_COLUMNS} FROM backup_history WHERE backup_id = ?1"),
+            [conn.last_insert_rowid()],
+            map_backup_history,
+        )
+    })
+}
+
+// Backup harian perlu dijalankan jika aktif dan hari ini belum ada backup otomatis yang sukses
+pub fn perlu_backup_harian() -> rusqlite::Result<bool> {
+    crate::db::with_connection(|conn| perlu_backup_pada(conn, Local::now().naive_local()))
+}
+
+fn perlu_backup_pada(conn: &Connection, sekarang: NaiveDateTime) -> rusqlite::Result<bool> {
+    let jadwal = baca_jadwal(conn)?;
+    if !jadwal.harian || jadwal.folder.is_none() {
+        return Ok(false);
+    }
+    let sekarang_str = sekarang.format("%Y-%m-%d %H:%M:%S").to_string();
+    let sudah: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM backup_history
+         WHERE status = 'sukses' AND pemicu IN (?1, ?2) AND date(waktu) = date(?3)",
+        (PEMICU_HARIAN, PEMICU_KELUAR, &sekarang_str),
+        |row| row.get(0),
+    )?;
+    if sudah > 0 {
+        return Ok(false);
+    }
+
+    // Folder tidak tersedia (flashdisk dicabut, drive jaringan putus): jangan mencoba
+    // dan menambah riwayat gagal di setiap pemeriksaan, tunggu JEDA_COBA_ULANG_MENIT dulu
+    let gagal_terakhir: Option<String> = conn.query_row(
+        "SELECT MAX(waktu) FROM backup_history WHERE status = 'gagal' AND pemicu = ?1",
+        [PEMICU_HARIAN],
+        |row| row.get(0),
+    )?;
+    let masih_jeda = gagal_terakhir
+        .and_then(|w| NaiveDateTime::parse_from_str(&w, "%Y-%m-%d %H:%M:%S").ok())
+        .is_some_and(|w| sekarang - w < Duration::minutes(JEDA_COBA_ULANG_MENIT));
+    Ok(!masih_jeda)
+}
+
+// Backup ke folder yang diatur lalu rotasi file lama.
+// None jika folder backup belum diatur.
+pub fn backup_terjadwal(pemicu: &str) -> Result<Option<BackupHistory>, String> {
+    let jadwal = crate::db::with_connection(baca_jadwal)
+        .map_err(|e| format!("Gagal membaca pengaturan backup: {}", e))?;
+    let Some(folder) = jadwal.folder.as_ref() else {
+        return Ok(None);
+    };
+
+    // Sama seperti buat_arsip: database terenkripsi memakai kata sandi database
+    let terenkripsi = jadwal.sandi.is_some() || crate::db::kunci().is_some();
+    let nama = format!(
+        "{}{}.{}",
+        AWALAN_OTOMATIS,
+        Local::now().format(FORMAT_WAKTU_FILE),
+        if terenkripsi {
+            EKSTENSI_TERENKRIPSI
+        } else {
+            EKSTENSI_ARSIP
+        }
+    );
+    let dest = folder.join(nama);
+    let hasil = buat_arsip(&dest, jadwal.sandi.as_deref());
+    let riwayat = catat_riwayat(pemicu, &dest, &hasil)
+        .map_err(|e| format!("Gagal mencatat riwayat backup: {}", e))?;
+    hasil?;
+
+    match rotasi(folder, &jadwal) {
+        Ok(n) if n > 0 => println!("🗑️ {} backup lama dihapus dari {:?}", n, folder),
+        Ok(_) => {}
+        Err(e) => eprintln!("❌ Error rotasi backup: {}", e),
+    }
+    Ok(Some(riwayat))
+}
+
+// Skema kakek-ayah-anak: simpan backup terbaru per hari untuk N hari terakhir,
+// per minggu untuk N minggu terakhir dan per bulan untuk N bulan terakhir.
+// Kembalikan jumlah file yang dihapus.
+pub fn rotasi(folder: &Path, jadwal: &JadwalBackup) -> Result<usize, String> {
+    let mut daftar: Vec<(NaiveDateTime, PathBuf)> = Vec::new();
+    for entry in fs::read_dir(folder).map_err(|e| format!("Gagal membaca folder: {}", e))? {
+        let entry = entry.map_err(|e| format!("Gagal membaca folder: {}", e))?;
+        let nama = entry.file_name().to_string_lossy().to_string();
+        let waktu = nama
+            .strip_prefix(AWALAN_OTOMATIS)
+            .and_then(|s| s.rsplit_once('.'))
+            .filter(|(_, ext)| [EKSTENSI_ARSIP, EKSTENSI_TERENKRIPSI].contains(ext))
+            .map(|(s, _)| s)
+            .and_then(|s| NaiveDateTime::parse_from_str(s, FORMAT_WAKTU_FILE).ok());
+        if let Some(waktu) = waktu {
+            daftar.push((waktu, entry.path()));
+        }
+    }
+    // Terbaru dulu, supaya yang disimpan untuk tiap periode adalah yang paling akhir
+    daftar.sort_by_key(|(waktu, _)| std::cmp::Reverse(*waktu));
+
+    let mut disimpan: HashSet<PathBuf> = HashSet::new();
+    // Backup terbaru selalu disimpan
+    if let Some((_, path)) = daftar.first() {
+        disimpan.insert(path.clone());
+    }
+
+    let mut simpan_per_periode = |kunci: &dyn Fn(&NaiveDateTime) -> (i32, u32), jumlah: usize| {
+        let mut periode: Vec<(i32, u32)> = Vec::new();
+        for (waktu, path) in &daftar {
+            let k = kunci(waktu);
+            if periode.contains(&k) {
+                continue;
+            }
+            if periode.len() >= jumlah {
+                break;
+            }
+            periode.push(k);
+            disimpan.insert(path.clone());
+        }
+    };
+    simpan_per_periode(&|w| (w.year(), w.ordinal()), jadwal.simpan_harian);
+    simpan_per_periode(
+        &|w| (w.iso_week().year(), w.iso_week().week()),
+        jadwal.simpan_mingguan,
+    );
+    simpan_per_periode(&|w| (w.year(), w.month()), jadwal.simpan_bulanan);
+
+    let mut dihapus = 0;
+    for (_, path) in &daftar {
+        if disimpan.contains(path) {
+            continue;
+        }
+        fs::remove_file(path).map_err(|e| format!("Gagal menghapus {}: {}", path.display(), e))?;
+        dihapus += 1;
+    }
+    Ok(dihapus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waktu(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn jadwal(harian: usize, mingguan: usize, bulanan: usize) -> JadwalBackup {
+        JadwalBackup {
+            folder: None,
+            harian: true,
+            saat_keluar: false,
+            simpan_harian: harian,
+            simpan_mingguan: mingguan,
+            simpan_bulanan: bulanan,
+            sandi: None,
+        }
+    }
+
+    // Isi database global diganti dengan motor bernama daftar ini
+    fn atur_motor(daftar: &[&str]) {
+        crate::migrations::init_db().unwrap();
+        crate::db::with_connection(|conn| {
+            conn.execute("DELETE FROM motor", [])?;
+            for nama in daftar {
+                conn.execute(
+                    "INSERT INTO motor (nama, foto, plat, tipe_motor, tahun, harga_harian, status)
+                     VALUES (?1, '', 'H 1 AB', 'Matic', '2022', 75000, 'tersedia')",
+                    [nama],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    fn nama_motor(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare("SELECT nama FROM motor ORDER BY nama")
+            .unwrap();
+        let rows = stmt.query_map([], |row| row.get(0)).unwrap();
+        rows.collect::<rusqlite::Result<_>>().unwrap()
+    }
+
+    fn motor_sekarang() -> Vec<String> {
+        crate::db::with_connection(|conn| Ok(nama_motor(conn))).unwrap()
+    }
+
+    // Arsip dengan manifest versi skema tertentu, seperti buatan versi aplikasi lain
+    fn arsip_uji(path: &Path, database: &[u8], schema_version: i32) {
+        let mut zip = ZipWriter::new_stream(File::create(path).unwrap());
+        let mut isi = database;
+        let file = vec![tambah_file(&mut zip, &mut isi, NAMA_DATABASE, true).unwrap()];
+        let manifest = ManifestBackup {
+            app_version: "0.0.0-uji".to_string(),
+            schema_version,
+            dibuat: Local::now().to_rfc3339(),
+            data_dir: crate::paths::data_dir().to_string_lossy().to_string(),
+            file,
+        };
+        zip.start_file(NAMA_MANIFEST, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&serde_json::to_vec(&manifest).unwrap())
+            .unwrap();
+        zip.finish().unwrap();
+    }
+
+    fn folder_arsip() -> PathBuf {
+        let folder = crate::paths::data_dir().join("arsip_uji");
+        fs::create_dir_all(&folder).unwrap();
+        folder
+    }
+
+    #[test]
+    fn restore_dari_versi_lebih_baru_ditolak() {
+        let _giliran = crate::paths::folder_data_uji();
+        atur_motor(&["Beat"]);
+        let folder = folder_arsip();
+        let database = crate::db::with_connection(crate::db::salin_ke_memori).unwrap();
+        let sumber_terakhir = || info_restore_terakhir().map(|info| info.sumber);
+        let sebelum = sumber_terakhir();
+
+        // Versi skema di manifest lebih baru
+        let arsip = folder.join("baru.zip");
+        arsip_uji(&arsip, &database, crate::migrations::SCHEMA_VERSION + 1);
+        let e = pulihkan(&arsip, None).unwrap_err();
+        assert!(e.contains("lebih baru"), "{}", e);
+
+        // Backup .sqlite versi lama tanpa manifest: user_version database yang dicek
+        let conn = crate::db::buka_dari_memori(&database).unwrap();
+        conn.pragma_update(None, "user_version", crate::migrations::SCHEMA_VERSION + 1)
+            .unwrap();
+        let sqlite = folder.join("baru.sqlite");
+        fs::write(&sqlite, crate::db::salin_ke_memori(&conn).unwrap()).unwrap();
+        let e = pulihkan(&sqlite, None).unwrap_err();
+        assert!(e.contains("lebih baru"), "{}", e);
+
+        // Database utama tidak tersentuh dan snapshot restore sebelumnya tidak tertimpa
+        assert_eq!(motor_sekarang(), vec!["Beat"]);
+        assert_eq!(sumber_terakhir(), sebelum);
+    }
+
+    #[test]
+    fn restore_dari_versi_lama_dimigrasi() {
+        let _giliran = crate::paths::folder_data_uji();
+        atur_motor(&["Beat"]);
+
+        // Skema versi pertama: belum ada odometer, nomor dokumen, dst.
+        let lama = Connection::open_in_memory().unwrap();
+        lama.execute_batch(
+            "CREATE TABLE motor (motor_id INTEGER PRIMARY KEY AUTOINCREMENT, nama TEXT NOT NULL,
+                 foto TEXT NOT NULL, plat TEXT NOT NULL, tipe_motor TEXT NOT NULL,
+                 tahun TEXT NOT NULL, harga_harian INTEGER NOT NULL, status TEXT NOT NULL);
+             CREATE TABLE penyewa (penyewa_id INTEGER PRIMARY KEY AUTOINCREMENT, nama TEXT NOT NULL,
+                 no_hp TEXT NOT NULL, no_ktp TEXT NOT NULL, alamat TEXT NOT NULL);
+             CREATE TABLE transaksi (transaksi_id INTEGER PRIMARY KEY AUTOINCREMENT, motor_id INTEGER,
+                 penyewa_id INTEGER, tanggal_sewa TEXT, tanggal_kembali_rencana TEXT,
+                 tanggal_kembali_aktual TEXT, hari_terlambat INTEGER, total_bayar INTEGER,
+                 status TEXT, denda INTEGER DEFAULT 0);
+             INSERT INTO motor (nama, foto, plat, tipe_motor, tahun, harga_harian, status)
+                 VALUES ('Supra Lama', '', 'H 2 CD', 'Bebek', '2010', 50000, 'tersedia');
+             INSERT INTO penyewa (nama, no_hp, no_ktp, alamat) VALUES ('Budi', '08123', '3374', 'Semarang');
+             INSERT INTO transaksi (motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana,
+                 total_bayar, status) VALUES (1, 1, '2025-03-01', '2025-03-02', 50000, 'selesai');",
+        )
+        .unwrap();
+        let arsip = folder_arsip().join("lama.zip");
+        arsip_uji(&arsip, &crate::db::salin_ke_memori(&lama).unwrap(), 0);
+
+        pulihkan(&arsip, None).unwrap();
+
+        assert_eq!(motor_sekarang(), vec!["Supra Lama"]);
+        crate::db::with_connection(|conn| {
+            let versi: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+            assert_eq!(versi, crate::migrations::SCHEMA_VERSION);
+            let (odometer, nomor): (i64, Option<String>) = conn.query_row(
+                "SELECT m.odometer, t.nomor FROM transaksi t JOIN motor m USING (motor_id)",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+            assert_eq!(odometer, 0);
+            assert!(nomor.is_some_and(|n| !n.is_empty()));
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn snapshot_dibuat_sebelum_database_diganti() {
+        let _giliran = crate::paths::folder_data_uji();
+        atur_motor(&["Vario", "Scoopy"]);
+        let arsip = folder_arsip().join("baru.enc");
+        atur_motor(&["NMax"]);
+        buat_arsip(&arsip, Some("rahasia123")).unwrap();
+        atur_motor(&["Vario", "Scoopy"]);
+
+        assert!(pulihkan(&arsip, Some("sandi-salah1")).is_err());
+        assert_eq!(motor_sekarang(), vec!["Scoopy", "Vario"]);
+
+        pulihkan(&arsip, Some("rahasia123")).unwrap();
+        assert_eq!(motor_sekarang(), vec!["NMax"]);
+
+        // Snapshot berisi database sebelum restore
+        let info = info_restore_terakhir().unwrap();
+        assert_eq!(info.sumber, arsip.to_string_lossy());
+        let file = File::open(&info.snapshot).unwrap();
+        let (_, database) = ekstrak_arsip(
+            &mut buka_arsip(BufReader::new(file)).unwrap(),
+            &folder_arsip().join("ekstrak"),
+        )
+        .unwrap();
+        let snapshot = crate::db::buka_dari_memori(&database).unwrap();
+        assert_eq!(nama_motor(&snapshot), vec!["Scoopy", "Vario"]);
+        // Folder kerja restore tidak tertinggal
+        assert!(!folder_pemulihan().join(FOLDER_KERJA).exists());
+    }
+
+    #[test]
+    fn batalkan_restore_mengembalikan_data_sebelumnya() {
+        let _giliran = crate::paths::folder_data_uji();
+        atur_motor(&["Lama"]);
+        let lampiran = crate::lampiran::folder();
+        fs::create_dir_all(&lampiran).unwrap();
+        fs::write(lampiran.join("foto_lama.jpg"), b"foto").unwrap();
+        let arsip = folder_arsip().join("baru.zip");
+        atur_motor(&["Baru 1", "Baru 2"]);
+        buat_arsip(&arsip, None).unwrap();
+        atur_motor(&["Lama"]);
+
+        pulihkan(&arsip, None).unwrap();
+        assert_eq!(motor_sekarang(), vec!["Baru 1", "Baru 2"]);
+
+        let info = batalkan_restore_terakhir().unwrap();
+        assert_eq!(motor_sekarang(), vec!["Lama"]);
+        assert!(lampiran.join("foto_lama.jpg").is_file());
+        assert!(!Path::new(&info.snapshot).exists());
+        // Snapshot hanya bisa dipakai sekali
+        assert!(info_restore_terakhir().is_none());
+        assert!(batalkan_restore_terakhir().is_err());
+    }
+
+    #[test]
+    fn file_sementara_dibersihkan_saat_start() {
+        let _giliran = crate::paths::folder_data_uji();
+        let data_dir = crate::paths::data_dir();
+        let kerja = folder_pemulihan().join(FOLDER_KERJA);
+        fs::create_dir_all(&kerja).unwrap();
+        fs::write(kerja.join(NAMA_DATABASE), b"polos").unwrap();
+        let sisa = [
+            ".database.sqlite.tmp",
+            ".arsip.zip.tmp",
+            "rental_motor.sqlite.pulihkan",
+            "rental_motor.sqlite.enkripsi",
+        ];
+        for nama in sisa {
+            fs::write(data_dir.join(nama), b"sisa").unwrap();
+        }
+        fs::write(data_dir.join("catatan.tmp"), b"bukan milik aplikasi").unwrap();
+
+        bersihkan_file_sementara();
+
+        for nama in sisa {
+            assert!(!data_dir.join(nama).exists(), "{}", nama);
+        }
+        assert!(!kerja.exists());
+        assert!(data_dir.join("catatan.tmp").exists());
+    }
+
+    #[test]
+    fn sandi_terjadwal_disimpan_di_luar_database() {
+        let folder = std::env::temp_dir().join(format!("sandi-{}", std::process::id()));
+        fs::remove_dir_all(&folder).ok();
+        fs::create_dir_all(&folder).unwrap();
+        let path = folder.join(FILE_SANDI);
+
+        assert!(atur_sandi_di(&path, Some("pendek")).is_err());
+        atur_sandi_di(&path, Some("rahasia123")).unwrap();
+        assert_eq!(baca_sandi(&path).as_deref(), Some("rahasia123"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        atur_sandi_di(&path, None).unwrap();
+        assert_eq!(baca_sandi(&path), None);
+        atur_sandi_di(&path, None).unwrap();
+
+        // Kata sandi versi lama dipindahkan dari tabel pengaturan
+        let conn = Connection::open_in_memory().unwrap();
+        crate::migrations::migrate(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO pengaturan (key, value) VALUES ('backup_sandi', 'sandilama123')",
+            [],
+        )
+        .unwrap();
+        pindahkan_sandi_lama_ke(&conn, &path).unwrap();
+        assert_eq!(baca_sandi(&path).as_deref(), Some("sandilama123"));
+        assert_eq!(
+            crate::db::get_pengaturan_value(&conn, "backup_sandi").unwrap(),
+            None
+        );
+        assert!(key_rahasia("backup_sandi"));
+        assert!(!key_rahasia("backup_folder"));
+
+        fs::remove_dir_all(&folder).ok();
+    }
+
+    #[test]
+    fn rotasi_kakek_ayah_anak() {
+        let folder = std::env::temp_dir().join(format!("rotasi-{}", std::process::id()));
+        fs::remove_dir_all(&folder).ok();
+        fs::create_dir_all(&folder).unwrap();
+
+        let semua = [
+            "20260110_080000", // bulan lalu
+            "20260128_080000", // terakhir di bulan Januari
+            "20260202_080000", // minggu 6
+            "20260209_080000", // minggu 7
+            "20260210_080000",
+            "20260211_070000.enc",
+            "20260211_200000", // hari terakhir, dua kali backup
+        ];
+        for w in semua {
+            let nama = if w.contains('.') {
+                format!("{}{}", AWALAN_OTOMATIS, w)
+            } else {
+                format!("{}{}.zip", AWALAN_OTOMATIS, w)
+            };
+            fs::write(folder.join(nama), b"").unwrap();
+        }
+        fs::write(folder.join("backup_manual.zip"), b"").unwrap();
+
+        // 2 hari terakhir, 2 minggu terakhir, 2 bulan terakhir
+        let dihapus = rotasi(&folder, &jadwal(2, 2, 2)).unwrap();
+
+        let mut sisa: Vec<String> = fs::read_dir(&folder)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        sisa.sort();
+        assert_eq!(
+            sisa,
+            vec![
+                "backup_manual.zip".to_string(),
+                format!("{}20260128_080000.zip", AWALAN_OTOMATIS),
+                format!("{}20260202_080000.zip", AWALAN_OTOMATIS),
+                format!("{}20260210_080000.zip", AWALAN_OTOMATIS),
+                format!("{}20260211_200000.zip", AWALAN_OTOMATIS),
+            ]
+        );
+        assert_eq!(dihapus, 3);
+
+        fs::remove_dir_all(&folder).ok();
+    }
+
+    #[test]
+    fn backup_harian_gagal_tidak_diulang_setiap_pemeriksaan() {
+        // baca_jadwal membaca file kata sandi di folder data
+        let _giliran = crate::paths::folder_data_uji();
+        let conn = Connection::open_in_memory().unwrap();
+        crate::migrations::migrate(&conn).unwrap();
+        conn.execute_batch(&format!(
+            "INSERT OR REPLACE INTO pengaturan (key, value) VALUES ('{KEY_HARIAN}', '1'), ('{KEY_FOLDER}', 'E:/backup');"
+        ))
+        .unwrap();
+        let catat = |waktu: &str, status: &str| {
+            conn.execute(
+                "INSERT INTO backup_history (waktu, pemicu, path, status) VALUES (?1, ?2, '', ?3)",
+                (waktu, PEMICU_HARIAN, status),
+            )
+            .unwrap();
+        };
+
+        assert!(perlu_backup_pada(&conn, waktu("2026-10-19 08:00:00")).unwrap());
+
+        catat("2026-10-19 08:00:00", "gagal");
+        assert!(!perlu_backup_pada(&conn, waktu("2026-10-19 08:15:00")).unwrap());
+        assert!(perlu_backup_pada(&conn, waktu("2026-10-19 10:00:00")).unwrap());
+
+        catat("2026-10-19 10:00:00", "sukses");
+        assert!(!perlu_backup_pada(&conn, waktu("2026-10-19 23:00:00")).unwrap());
+        assert!(perlu_backup_pada(&conn, waktu("2026-10-20 00:15:00")).unwrap());
+    }
+}
@@ -53,70 +53,62 @@ pub async fn get_db_path_string() -> Result<String, String> {
     Ok(db_path.to_string_lossy().to_string())
 }
 
+// Backup lengkap (database + foto) dalam satu arsip .zip,
+// dienkripsi jika kata sandi diisi atau database terenkripsi
 #[tauri::command]
-pub async fn backup_database(dest_path: String) -> Result<String, String> {
-    let src = crate::db::get_db_path();
-
-    if !src.exists() {
-        return Err("Database file not found".to_string());
-    }
+pub async fn backup_database(
+    dest_path: String,
+    kata_sandi: Option<String>,
+) -> Result<String, String> {
+    println!("💾 Backup database ke {}", dest_path);
 
     let dest = PathBuf::from(&dest_path);
-
-    // Ensure destination directory exists
-    if let Some(parent) = dest.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| format!("Gagal membuat folder: {}", e))?;
-    }
-
-    // Perform WAL checkpoint before backup to ensure all data is in main db file
-    crate::db::with_connection(|conn| {
-        conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);").ok();
-        Ok(())
+    let manifest = crate::db::blocking({
+        let dest = dest.clone();
+        move || {
+            let sandi = kata_sandi.as_deref().filter(|s| !s.is_empty());
+            let hasil = crate::backup::buat_arsip(&dest, sandi);
+            if let Err(e) =
+                crate::backup::catat_riwayat(crate::backup::PEMICU_MANUAL, &dest, &hasil)
+            {
+                eprintln!("❌ Error catat riwayat backup: {}", e);
+            }
+            hasil
+        }
     })
-    .map_err(|e| format!("Gagal checkpoint database: {}", e))?;
-
-    // Copy the main database file
-    std::fs::copy(&src, &dest).map_err(|e| format!("Gagal menyalin database: {}", e))?;
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error backup_database: {}", e);
+        e
+    })?;
+    println!("✅ Backup selesai: {} file", manifest.file.len());
 
     Ok(dest.to_string_lossy().to_string())
 }
 
+// Terima arsip .zip (terenkripsi atau tidak) maupun file .sqlite dari versi sebelumnya
 #[tauri::command]
-pub async fn import_database(src_path: String) -> Result<(), String> {
-    let src = PathBuf::from(&src_path);
+pub async fn import_database(src_path: String, kata_sandi: Option<String>) -> Result<(), String> {
+    println!("📥 Import database dari {}", src_path);
 
+    let src = PathBuf::from(&src_path);
     if !src.exists() {
-        return Err("File database tidak ditemukan".to_string());
+        return Err("File backup tidak ditemukan".to_string());
     }
 
-    // Validate it's a valid SQLite file by checking the magic header
-    let header = std::fs::read(&src).map_err(|e| format!("Gagal membaca file: {}", e))?;
-
-    if header.len() < 16 || &header[0..16] != b"SQLite format 3\0" {
-        return Err("File yang dipilih bukan file database SQLite yang valid".to_string());
-    }
-
-    let dest = crate::db::get_db_path();
-
-    // Close the WAL and SHM first
-    crate::db::with_connection(|conn| {
-        conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);").ok();
-        Ok(())
+    crate::db::blocking(move || {
+        let sandi = kata_sandi.as_deref().filter(|s| !s.is_empty());
+        crate::backup::pulihkan(&src, sandi)
     })
-    .map_err(|e| format!("Gagal checkpoint: {}", e))?;
-
-    // Copy the imported file to the database location
-    std::fs::copy(&src, &dest).map_err(|e| format!("Gagal mengimpor database: {}", e))?;
-
-    // Remove WAL and SHM files if they exist (they belong to the old DB)
-    let wal_path = dest.with_extension("sqlite-wal");
-    let shm_path = dest.with_extension("sqlite-shm");
-    if wal_path.exists() {
-        std::fs::remove_file(&wal_path).ok();
-    }
-    if shm_path.exists() {
-        std::fs::remove_file(&shm_path).ok();
-    }
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error import_database: {}", e);
+        e
+    })
+}
 
-    Ok(())
+// Dipakai UI untuk menanyakan kata sandi sebelum import
+#[tauri::command]
+pub async fn cek_backup_terenkripsi(path: String) -> Result<bool, String> {
+    Ok(crate::enkripsi::terenkripsi(&PathBuf::from(path)))
 }
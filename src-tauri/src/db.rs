@@ -1,28 +1,79 @@
-use once_cell::sync::Lazy;
-use parking_lot::Mutex;
-use rusqlite::{Connection, Result};
-use std::fs;
-use std::path::PathBuf;
+use parking_lot::{const_mutex, const_rwlock, Mutex, RwLock, RwLockWriteGuard};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::backup::Backup;
+use rusqlite::{ffi, Connection, DatabaseName, OptionalExtension, Result};
+use std::ffi::CString;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 // Fungsi untuk mendapatkan path database
 pub fn get_db_path() -> PathBuf {
-    let mut path = PathBuf::from(std::env::var("APPDATA").expect("APPDATA not found"));
+    crate::paths::db_path()
+}
+
+// Koneksi tulis global dengan Mutex (SQLite hanya mengizinkan satu penulis).
+// Dibuka saat pertama dipakai; untuk database terenkripsi baru bisa dibuka
+// setelah kata sandi dimasukkan.
+static DB: Mutex<Option<Connection>> = const_mutex(None);
+
+// Pool koneksi baca. Dengan WAL pembaca tidak saling menunggu dan tidak
+// menunggu penulis, jadi query laporan/dashboard bisa berjalan bersamaan.
+static POOL_BACA: Mutex<Option<r2d2::Pool<SqliteConnectionManager>>> = const_mutex(None);
+
+const UKURAN_POOL_BACA: u32 = 4;
 
-    path.push("AplikasiPembukuan");
-    fs::create_dir_all(&path).ok();
+// Setiap pemakaian koneksi baca memegang gerbang ini (kunci baca). Penggantian
+// file atau kata sandi database memegang kunci tulisnya, jadi menunggu semua
+// koneksi yang sedang dipinjam dikembalikan. Urutan kunci: GERBANG_BACA, DB, POOL_BACA.
+static GERBANG_BACA: RwLock<()> = const_rwlock(());
 
-    path.push("rental_motor.sqlite");
+// Kata sandi SQLCipher yang sedang dipakai (None = database tidak terenkripsi / masih terkunci)
+static KUNCI: Mutex<Option<String>> = const_mutex(None);
 
-    println!("DB PATH => {:?}", path);
+const MAGIC_SQLITE: &[u8] = b"SQLite format 3\0";
 
-    path
+// Akhiran file sementara aktifkan_enkripsi dan ganti_isi_dari. Isinya terenkripsi;
+// sisa dari proses yang terhenti dihapus backup::bersihkan_file_sementara.
+pub const AKHIRAN_ENKRIPSI: &str = "sqlite.enkripsi";
+pub const AKHIRAN_PULIHKAN: &str = "sqlite.pulihkan";
+
+// Enkripsi database hanya tersedia jika dibangun dengan feature "sqlcipher"
+pub const ENKRIPSI_DIDUKUNG: bool = cfg!(feature = "sqlcipher");
+
+// File SQLCipher tidak punya header SQLite biasa (seluruh isi file terenkripsi)
+pub fn db_terenkripsi() -> bool {
+    let mut header = [0u8; 16];
+    match std::fs::File::open(get_db_path()) {
+        Ok(mut file) => match std::io::Read::read_exact(&mut file, &mut header) {
+            Ok(()) => header != MAGIC_SQLITE,
+            Err(_) => false,
+        },
+        Err(_) => false,
+    }
 }
 
-// Global database connection dengan Mutex
-static DB: Lazy<Mutex<Connection>> = Lazy::new(|| {
-    let db_path = get_db_path();
+// Kata sandi database yang sedang aktif, None jika database tidak terenkripsi
+pub fn kunci() -> Option<String> {
+    KUNCI.lock().clone()
+}
+
+pub fn terkunci() -> bool {
+    KUNCI.lock().is_none() && db_terenkripsi()
+}
+
+fn pasang_kunci(conn: &Connection, kunci: Option<&str>) -> Result<()> {
+    if let Some(kunci) = kunci {
+        conn.pragma_update(None, "key", kunci)?;
+        // Kata sandi baru diperiksa saat halaman pertama dibaca
+        conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |_| Ok(()))
+            .map_err(|_| validation_error("Kata sandi database salah"))?;
+    }
+    Ok(())
+}
 
-    let conn = Connection::open(&db_path).expect("Failed to open database");
+fn buka(path: &Path, kunci: Option<&str>) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    pasang_kunci(&conn, kunci)?;
 
     // Set PRAGMA untuk menghindari lock
     conn.execute_batch(
@@ -31,52 +82,383 @@ static DB: Lazy<Mutex<Connection>> = Lazy::new(|| {
          PRAGMA busy_timeout = 30000;
          PRAGMA temp_store = MEMORY;
          PRAGMA cache_size = -64000;",
-    )
-    .expect("Failed to set PRAGMA");
+    )?;
+    Ok(conn)
+}
 
-    Mutex::new(conn)
-});
+fn buka_utama() -> Result<Connection> {
+    let kunci = KUNCI.lock().clone();
+    if kunci.is_none() && db_terenkripsi() {
+        return Err(validation_error(if ENKRIPSI_DIDUKUNG {
+            "Database terkunci, masukkan kata sandi database terlebih dahulu"
+        } else {
+            "Database terenkripsi tetapi aplikasi ini dibangun tanpa dukungan enkripsi"
+        }));
+    }
+    buka(&get_db_path(), kunci.as_deref())
+}
 
 // Helper function untuk akses database
 pub fn with_connection<F, R>(f: F) -> Result<R>
 where
     F: FnOnce(&Connection) -> Result<R>,
 {
-    let conn = DB.lock();
+    with_connection_mut(|conn| f(conn))
+}
+
+// Untuk operasi yang butuh &mut Connection (mis. restore lewat backup API)
+pub fn with_connection_mut<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&mut Connection) -> Result<R>,
+{
+    let mut guard = DB.lock();
+    let conn = match guard.take() {
+        Some(conn) => conn,
+        None => buka_utama()?,
+    };
+    f(guard.insert(conn))
+}
+
+fn pool_baca() -> Result<r2d2::Pool<SqliteConnectionManager>> {
+    if let Some(pool) = POOL_BACA.lock().as_ref() {
+        return Ok(pool.clone());
+    }
+
+    // Pastikan koneksi tulis sudah terbuka dulu: file database dibuat, mode WAL
+    // aktif dan kata sandi sudah dicek. Urutan kunci selalu DB lalu POOL_BACA.
+    with_connection(|_| Ok(()))?;
+
+    let manager = SqliteConnectionManager::file(get_db_path()).with_init(|conn| {
+        let kunci = KUNCI.lock().clone();
+        pasang_kunci(conn, kunci.as_deref())?;
+        conn.execute_batch(
+            "PRAGMA busy_timeout = 30000;
+             PRAGMA temp_store = MEMORY;
+             PRAGMA cache_size = -16000;
+             PRAGMA query_only = 1;",
+        )
+    });
+    let pool = r2d2::Pool::builder()
+        .max_size(UKURAN_POOL_BACA)
+        .min_idle(Some(1))
+        .build(manager)
+        .map_err(|e| validation_error(format!("Gagal membuka koneksi baca: {}", e)))?;
+
+    Ok(POOL_BACA.lock().get_or_insert(pool).clone())
+}
+
+// Tutup semua koneksi baca, dipanggil saat file atau kata sandi database berganti.
+// Menunggu koneksi yang sedang dipinjam selesai dipakai; selama guard dipegang
+// tidak ada koneksi baca baru yang dibuka.
+fn tutup_pool_baca() -> RwLockWriteGuard<'static, ()> {
+    let gerbang = GERBANG_BACA.write();
+    POOL_BACA.lock().take();
+    gerbang
+}
+
+// Helper untuk query yang hanya membaca. Koneksinya PRAGMA query_only,
+// jadi tulis tidak sengaja akan gagal alih-alih melewati koneksi tulis.
+pub fn with_reader<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&Connection) -> Result<R>,
+{
+    let _gerbang = GERBANG_BACA.read();
+    let conn = pool_baca()?
+        .get()
+        .map_err(|e| validation_error(format!("Gagal mengambil koneksi baca: {}", e)))?;
     f(&conn)
 }
 
-// Backward compatibility - jika ada kode lain yang masih pakai get_connection
-pub fn get_connection() -> Result<Connection> {
-    // Untuk migration saja, buat connection baru
-    let db_path = get_db_path();
-    let conn = Connection::open(&db_path)?;
+// Jalankan pekerjaan blocking (database, file, backup) di thread terpisah
+// supaya command async tidak menahan thread runtime Tauri
+pub async fn blocking<F, R>(f: F) -> std::result::Result<R, String>
+where
+    F: FnOnce() -> std::result::Result<R, String> + Send + 'static,
+    R: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| format!("Tugas latar gagal: {}", e))?
+}
 
-    conn.execute_batch(
-        "PRAGMA journal_mode = WAL;
-         PRAGMA synchronous = NORMAL;
-         PRAGMA busy_timeout = 30000;",
+// Versi async with_reader untuk command Tauri
+pub async fn baca<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&Connection) -> Result<R> + Send + 'static,
+    R: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(move || with_reader(f))
+        .await
+        .map_err(|e| validation_error(format!("Tugas latar gagal: {}", e)))?
+}
+
+// Versi async with_connection untuk command Tauri yang menulis
+pub async fn tulis<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&Connection) -> Result<R> + Send + 'static,
+    R: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(move || with_connection(f))
+        .await
+        .map_err(|e| validation_error(format!("Tugas latar gagal: {}", e)))?
+}
+
+// Buka database terenkripsi dengan kata sandi. Kata sandi disimpan di memori
+// selama aplikasi berjalan dan tidak pernah ditulis ke disk.
+pub fn buka_kunci(kata_sandi: &str) -> Result<()> {
+    if !db_terenkripsi() {
+        return Err(validation_error("Database tidak terenkripsi"));
+    }
+    if !ENKRIPSI_DIDUKUNG {
+        return buka_utama().map(|_| ());
+    }
+
+    let _gerbang = tutup_pool_baca();
+    let mut guard = DB.lock();
+    let conn = buka(&get_db_path(), Some(kata_sandi))?;
+    *KUNCI.lock() = Some(kata_sandi.to_string());
+    *guard = Some(conn);
+    Ok(())
+}
+
+fn salin_user_version(conn: &Connection, tujuan: &str) -> Result<()> {
+    let versi: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    conn.pragma_update(Some(DatabaseName::Attached(tujuan)), "user_version", versi)
+}
+
+// Ekspor isi database conn ke file lain dengan kata sandi berbeda
+// (kunci "" = file SQLite biasa). Backup API SQLCipher menolak sumber dan tujuan
+// yang kuncinya berbeda, jadi konversi harus lewat sqlcipher_export.
+fn ekspor_sqlcipher(conn: &Connection, dest: &Path, kunci: &str) -> Result<()> {
+    std::fs::remove_file(dest).ok();
+    conn.execute(
+        "ATTACH DATABASE ?1 AS ekspor KEY ?2",
+        (dest.to_string_lossy().to_string(), kunci),
     )?;
+    let hasil = conn
+        .query_row("SELECT sqlcipher_export('ekspor')", [], |_| Ok(()))
+        .and_then(|_| salin_user_version(conn, "ekspor"));
+    conn.execute_batch("DETACH DATABASE ekspor")?;
+    hasil
+}
+
+// Ubah database plaintext menjadi database SQLCipher. Hasil ekspor diperiksa dulu
+// sebelum menggantikan file lama, lalu koneksi dibuka ulang dengan kata sandi.
+pub fn aktifkan_enkripsi(kata_sandi: &str) -> Result<()> {
+    if !ENKRIPSI_DIDUKUNG {
+        return Err(validation_error(
+            "Aplikasi ini dibangun tanpa dukungan enkripsi database",
+        ));
+    }
+    if db_terenkripsi() {
+        return Err(validation_error("Database sudah terenkripsi"));
+    }
+
+    let db_path = get_db_path();
+    let sementara = db_path.with_extension(AKHIRAN_ENKRIPSI);
+
+    // Koneksi baca ditutup dulu (menunggu yang sedang dipakai) dan baru dibuka lagi
+    // setelah file diganti, supaya tidak ada yang masih memegang file lama
+    let _gerbang = tutup_pool_baca();
+    let mut guard = DB.lock();
+    let conn = match guard.take() {
+        Some(conn) => conn,
+        None => buka_utama()?,
+    };
+    if let Err(e) = ekspor_sqlcipher(&conn, &sementara, kata_sandi) {
+        *guard = Some(conn);
+        std::fs::remove_file(&sementara).ok();
+        return Err(e);
+    }
+
+    let cek = Connection::open(&sementara).and_then(|cek| {
+        pasang_kunci(&cek, Some(kata_sandi))?;
+        let integritas: String = cek.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+        if integritas != "ok" {
+            return Err(validation_error(format!(
+                "Hasil enkripsi tidak valid: {}",
+                integritas
+            )));
+        }
+        Ok(())
+    });
+    if let Err(e) = cek {
+        *guard = Some(conn);
+        std::fs::remove_file(&sementara).ok();
+        return Err(e);
+    }
+
+    // Tutup koneksi tulis lama (WAL ikut di-checkpoint), lalu ganti file database
+    drop(conn);
+    for akhiran in ["sqlite-wal", "sqlite-shm"] {
+        std::fs::remove_file(db_path.with_extension(akhiran)).ok();
+    }
+    std::fs::rename(&sementara, &db_path)
+        .map_err(|e| validation_error(format!("Gagal mengganti file database: {}", e)))?;
+
+    *KUNCI.lock() = Some(kata_sandi.to_string());
+    *guard = Some(buka(&db_path, Some(kata_sandi))?);
+    Ok(())
+}
+
+// Image database schema (main/nama attach) sebagai byte lewat sqlite3_serialize
+fn serialize(conn: &Connection, schema: &str) -> Result<Vec<u8>> {
+    let schema = CString::new(schema).map_err(|e| validation_error(e.to_string()))?;
+    let mut ukuran: i64 = 0;
+    // SAFETY: handle koneksi valid selama conn dipinjam; buffer hasil sqlite3_serialize
+    // sepanjang ukuran byte disalin lalu dibebaskan dengan sqlite3_free
+    unsafe {
+        let ptr = ffi::sqlite3_serialize(conn.handle(), schema.as_ptr(), &mut ukuran, 0);
+        if ptr.is_null() {
+            return Err(validation_error("Gagal menyalin database ke memori"));
+        }
+        let isi = std::slice::from_raw_parts(ptr, ukuran as usize).to_vec();
+        ffi::sqlite3_free(ptr.cast());
+        Ok(isi)
+    }
+}
+
+// Salin isi database ke memori sebagai image SQLite biasa (untuk backup), tanpa file
+// sementara di disk. Database terenkripsi diekspor tanpa kunci ke database memori
+// karena backup::buat_arsip selalu mengenkripsi arsipnya.
+pub fn salin_ke_memori(conn: &Connection) -> Result<Vec<u8>> {
+    if KUNCI.lock().is_some() {
+        conn.execute_batch("ATTACH DATABASE ':memory:' AS ekspor KEY ''")?;
+        let hasil = conn
+            .query_row("SELECT sqlcipher_export('ekspor')", [], |_| Ok(()))
+            .and_then(|_| salin_user_version(conn, "ekspor"))
+            .and_then(|_| serialize(conn, "ekspor"));
+        conn.execute_batch("DETACH DATABASE ekspor")?;
+        return hasil;
+    }
+    let mut memori = Connection::open_in_memory()?;
+    Backup::new(conn, &mut memori)?.run_to_completion(1000, Duration::ZERO, None)?;
+    serialize(&memori, "main")
+}
 
+// Buka image database (isi arsip backup) sebagai database memori, supaya isi backup
+// tidak pernah ditulis polos ke disk
+pub fn buka_dari_memori(isi: &[u8]) -> Result<Connection> {
+    let conn = Connection::open_in_memory()?;
+    let ukuran = isi.len() as i64;
+    // SAFETY: buffer dialokasikan sqlite3_malloc64 sepanjang isi (minimal 1 byte) dan
+    // diserahkan ke SQLite (FREEONCLOSE: dibebaskan SQLite, juga jika gagal)
+    let rc = unsafe {
+        let ptr = ffi::sqlite3_malloc64(isi.len().max(1) as u64).cast::<u8>();
+        if ptr.is_null() {
+            return Err(validation_error("Memori tidak cukup untuk membuka database"));
+        }
+        let buffer = std::slice::from_raw_parts_mut(ptr, isi.len());
+        buffer.copy_from_slice(isi);
+        // Database memori tidak punya file WAL: tandai sebagai mode rollback journal
+        // (byte 18-19 header), jika tidak SQLite menolak membacanya
+        if buffer.len() >= 20 && buffer[18] == 2 {
+            buffer[18] = 1;
+            buffer[19] = 1;
+        }
+        ffi::sqlite3_deserialize(
+            conn.handle(),
+            c"main".as_ptr(),
+            ptr,
+            ukuran,
+            ukuran,
+            (ffi::SQLITE_DESERIALIZE_FREEONCLOSE | ffi::SQLITE_DESERIALIZE_RESIZEABLE) as _,
+        )
+    };
+    if rc != ffi::SQLITE_OK {
+        return Err(rusqlite::Error::SqliteFailure(ffi::Error::new(rc), None));
+    }
     Ok(conn)
 }
 
+// Ganti isi database utama dengan isi koneksi sumber (untuk restore). Backup API
+// menulis semua halaman dalam satu transaksi di koneksi utama, jadi jika gagal
+// database lama tetap utuh. Untuk database terenkripsi, sumber diekspor dulu ke
+// file sementara dengan kunci yang sama (isinya ikut terenkripsi).
+pub fn ganti_isi_dari(sumber: &Connection) -> Result<()> {
+    let kunci = KUNCI.lock().clone();
+    let sementara = get_db_path().with_extension(AKHIRAN_PULIHKAN);
+
+    // Backup API tidak bisa mengubah ukuran halaman database WAL, jadi selama penggantian
+    // database utama memakai rollback journal. Itu butuh akses eksklusif: koneksi baca
+    // ditutup dulu dan baru dibuka lagi setelah selesai.
+    let _gerbang = tutup_pool_baca();
+    let hasil = with_connection_mut(|conn| {
+        let terenkripsi;
+        let sumber = match &kunci {
+            Some(kunci) => {
+                ekspor_sqlcipher(sumber, &sementara, kunci)?;
+                terenkripsi = Connection::open(&sementara)?;
+                pasang_kunci(&terenkripsi, Some(kunci))?;
+                &terenkripsi
+            }
+            None => sumber,
+        };
+        conn.execute_batch("PRAGMA journal_mode = DELETE")?;
+        let hasil = Backup::new(sumber, conn)
+            .and_then(|backup| backup.run_to_completion(1000, Duration::ZERO, None));
+        conn.execute_batch("PRAGMA journal_mode = WAL")?;
+        hasil
+    });
+    std::fs::remove_file(&sementara).ok();
+    hasil
+}
+
+// Jalankan f di dalam SAVEPOINT: semua perubahan di-rollback jika f gagal.
+// Berbeda dengan BEGIN, SAVEPOINT boleh bersarang di dalam transaksi lain.
+pub fn atomic<R>(conn: &Connection, f: impl FnOnce(&Connection) -> Result<R>) -> Result<R> {
+    conn.execute_batch("SAVEPOINT atomic")?;
+    match f(conn) {
+        Ok(result) => {
+            conn.execute_batch("RELEASE atomic")?;
+            Ok(result)
+        }
+        Err(e) => {
+            conn.execute_batch("ROLLBACK TO atomic; RELEASE atomic").ok();
+            Err(e)
+        }
+    }
+}
+
+// Error validasi bisnis yang dikembalikan lewat rusqlite::Result,
+// pesannya tampil apa adanya di frontend
+pub fn validation_error(msg: impl Into<String>) -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure(msg.into().into())
+}
+
+// Ambil nilai pengaturan berdasarkan key, None jika belum pernah diatur
+pub fn get_pengaturan_value(conn: &Connection, key: &str) -> Result<Option<String>> {
+    match conn.query_row("SELECT value FROM pengaturan WHERE key = ?1", [key], |row| {
+        row.get::<_, String>(0)
+    }) {
+        Ok(val) => Ok(Some(val)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+const MOTOR_COLUMNS: &str =
+    "motor_id, nama, plat, tipe_motor, tahun, harga_harian, foto, status, odometer";
+
+fn map_motor(conn: &Connection, row: &rusqlite::Row) -> Result<crate::models::Motor> {
+    let foto: String = row.get(6)?;
+    Ok(crate::models::Motor {
+        motor_id: row.get(0)?,
+        nama: row.get(1)?,
+        plat: row.get(2)?,
+        tipe_motor: row.get(3)?,
+        tahun: row.get(4)?,
+        harga_harian: row.get(5)?,
+        foto_thumbnail: crate::lampiran::thumbnail(conn, &foto)?,
+        foto,
+        status: row.get(7)?,
+        odometer: row.get(8)?,
+    })
+}
+
 pub fn get_all_motor(conn: &Connection) -> Result<Vec<crate::models::Motor>> {
-    let mut stmt = conn.prepare(
-        "SELECT motor_id, nama, plat, tipe_motor, tahun, harga_harian, foto, status FROM motor",
-    )?;
-    let motor_iter = stmt.query_map([], |row| {
-        Ok(crate::models::Motor {
-            motor_id: row.get(0)?,
-            nama: row.get(1)?,
-            plat: row.get(2)?,
-            tipe_motor: row.get(3)?,
-            tahun: row.get(4)?,
-            harga_harian: row.get(5)?,
-            foto: row.get(6)?,
-            status: row.get(7)?,
-        })
-    })?;
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM motor", MOTOR_COLUMNS))?;
+    let motor_iter = stmt.query_map([], |row| map_motor(conn, row))?;
 
     let mut result = Vec::new();
     for m in motor_iter {
@@ -87,36 +469,40 @@ pub fn get_all_motor(conn: &Connection) -> Result<Vec<crate::models::Motor>> {
 
 pub fn create_motor(conn: &Connection, data: crate::models::Motor) -> Result<()> {
     conn.execute(
-        "INSERT INTO motor (nama, plat, tipe_motor, tahun, harga_harian, foto, status) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-        (data.nama, data.plat, data.tipe_motor, data.tahun, data.harga_harian, data.foto, data.status),
+        "INSERT INTO motor (nama, plat, tipe_motor, tahun, harga_harian, foto, status, odometer) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, COALESCE(?8, 0))",
+        (data.nama, data.plat, data.tipe_motor, data.tahun, data.harga_harian, data.foto, data.status, data.odometer),
     )?;
     Ok(())
 }
 
 pub fn get_motor_by_id(conn: &Connection, id: i32) -> Result<crate::models::Motor> {
-    let mut stmt = conn.prepare("SELECT motor_id, nama, plat, tipe_motor, tahun, harga_harian, foto, status FROM motor WHERE motor_id = ?1")?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM motor WHERE motor_id = ?1",
+        MOTOR_COLUMNS
+    ))?;
     let mut rows = stmt.query([id])?;
 
     if let Some(row) = rows.next()? {
-        Ok(crate::models::Motor {
-            motor_id: row.get(0)?,
-            nama: row.get(1)?,
-            plat: row.get(2)?,
-            tipe_motor: row.get(3)?,
-            tahun: row.get(4)?,
-            harga_harian: row.get(5)?,
-            foto: row.get(6)?,
-            status: row.get(7)?,
-        })
+        map_motor(conn, row)
     } else {
         Err(rusqlite::Error::QueryReturnedNoRows)
     }
 }
 
 pub fn update_motor(conn: &Connection, id: i32, data: crate::models::Motor) -> Result<()> {
+    // odometer dibiarkan jika tidak dikirim dari form
+    conn.execute(
+        "UPDATE motor SET nama = ?1, plat = ?2, tipe_motor = ?3, tahun = ?4, harga_harian = ?5, foto = ?6, status = ?7, odometer = COALESCE(?8, odometer) WHERE motor_id = ?9",
+        (data.nama, data.plat, data.tipe_motor, data.tahun, data.harga_harian, data.foto, data.status, data.odometer, id),
+    )?;
+    Ok(())
+}
+
+// Odometer motor hanya bisa naik
+pub fn update_odometer_motor(conn: &Connection, motor_id: i32, km: i64) -> Result<()> {
     conn.execute(
-        "UPDATE motor SET nama = ?1, plat = ?2, tipe_motor = ?3, tahun = ?4, harga_harian = ?5, foto = ?6, status = ?7 WHERE motor_id = ?8",
-        (data.nama, data.plat, data.tipe_motor, data.tahun, data.harga_harian, data.foto, data.status, id),
+        "UPDATE motor SET odometer = MAX(COALESCE(odometer, 0), ?1) WHERE motor_id = ?2",
+        (km, motor_id),
     )?;
     Ok(())
 }
@@ -181,28 +567,61 @@ pub fn update_penyewa(conn: &Connection, id: i32, data: crate::models::Penyewa)
 }
 
 pub fn delete_penyewa(conn: &Connection, id: i32) -> Result<()> {
-    conn.execute("DELETE FROM penyewa WHERE penyewa_id = ?1", (id,))?;
-    Ok(())
+    atomic(conn, |conn| {
+        conn.execute("DELETE FROM dokumen_penyewa WHERE penyewa_id = ?1", (id,))?;
+        conn.execute("DELETE FROM penyewa WHERE penyewa_id = ?1", (id,))?;
+        Ok(())
+    })
+}
+
+const TRANSAKSI_COLUMNS: &str = "transaksi_id, motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana, tanggal_kembali_aktual, hari_terlambat, total_bayar, status, denda, foto_bukti, diskon, nomor, km_awal, km_akhir, biaya_km_lebih, bbm_awal, bbm_akhir, biaya_bbm";
+
+fn map_transaksi(conn: &Connection, row: &rusqlite::Row) -> Result<crate::models::Transaksi> {
+    let foto_bukti: Option<String> = row.get(10)?;
+    Ok(crate::models::Transaksi {
+        transaksi_id: row.get(0)?,
+        motor_id: row.get(1)?,
+        penyewa_id: row.get(2)?,
+        tanggal_sewa: row.get(3)?,
+        tanggal_kembali_rencana: row.get(4)?,
+        tanggal_kembali_aktual: row.get(5)?,
+        hari_terlambat: row.get(6)?,
+        total_bayar: row.get(7)?,
+        status: row.get(8)?,
+        denda: row.get(9)?,
+        foto_bukti_thumbnail: crate::lampiran::thumbnail_opsional(conn, &foto_bukti)?,
+        foto_bukti,
+        diskon: row.get(11)?,
+        nomor: row.get(12)?,
+        km_awal: row.get(13)?,
+        km_akhir: row.get(14)?,
+        biaya_km_lebih: row.get(15)?,
+        bbm_awal: row.get(16)?,
+        bbm_akhir: row.get(17)?,
+        biaya_bbm: row.get(18)?,
+    })
 }
 
 pub fn get_all_transaksi(conn: &Connection) -> Result<Vec<crate::models::Transaksi>> {
-    let mut stmt = conn.prepare("SELECT transaksi_id, motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana, tanggal_kembali_aktual, hari_terlambat, total_bayar, status, denda, foto_bukti, diskon FROM transaksi")?;
-    let transaksi_iter = stmt.query_map([], |row| {
-        Ok(crate::models::Transaksi {
-            transaksi_id: row.get(0)?,
-            motor_id: row.get(1)?,
-            penyewa_id: row.get(2)?,
-            tanggal_sewa: row.get(3)?,
-            tanggal_kembali_rencana: row.get(4)?,
-            tanggal_kembali_aktual: row.get(5)?,
-            hari_terlambat: row.get(6)?,
-            total_bayar: row.get(7)?,
-            status: row.get(8)?,
-            denda: row.get(9)?,
-            foto_bukti: row.get(10)?,
-            diskon: row.get(11)?,
-        })
-    })?;
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM transaksi", TRANSAKSI_COLUMNS))?;
+    let transaksi_iter = stmt.query_map([], |row| map_transaksi(conn, row))?;
+
+    let mut result = Vec::new();
+    for t in transaksi_iter {
+        result.push(t?);
+    }
+    Ok(result)
+}
+
+pub fn get_transaksi_by_penyewa(
+    conn: &Connection,
+    penyewa_id: i32,
+) -> Result<Vec<crate::models::Transaksi>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transaksi WHERE penyewa_id = ?1 ORDER BY tanggal_sewa DESC, transaksi_id DESC",
+        TRANSAKSI_COLUMNS
+    ))?;
+    let transaksi_iter = stmt.query_map([penyewa_id], |row| map_transaksi(conn, row))?;
 
     let mut result = Vec::new();
     for t in transaksi_iter {
@@ -211,48 +630,216 @@ pub fn get_all_transaksi(conn: &Connection) -> Result<Vec<crate::models::Transak
     Ok(result)
 }
 
-pub fn create_transaksi(conn: &Connection, data: crate::models::Transaksi) -> Result<()> {
+pub fn create_transaksi(conn: &Connection, mut data: crate::models::Transaksi) -> Result<()> {
     let motor_id = data.motor_id;
 
-    conn.execute(
-        "INSERT INTO transaksi (motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana, tanggal_kembali_aktual, hari_terlambat, total_bayar, status, denda, foto_bukti, diskon) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-        (data.motor_id, data.penyewa_id, data.tanggal_sewa, data.tanggal_kembali_rencana, data.tanggal_kembali_aktual, data.hari_terlambat, data.total_bayar, data.status, data.denda, data.foto_bukti, data.diskon),
-    )?;
+    validasi_motor_bisa_disewa(conn, motor_id)?;
+    validasi_penyewa_bisa_menyewa(conn, data.penyewa_id)?;
 
-    // Update motor status to dipinjam
-    conn.execute(
-        "UPDATE motor SET status = 'dipinjam' WHERE motor_id = ?1",
-        (motor_id,),
-    )?;
+    // km_awal default dari odometer motor saat ini
+    if data.km_awal.is_none() {
+        data.km_awal = get_motor_by_id(conn, motor_id)?.odometer;
+    }
+    validasi_km_awal_odometer(conn, motor_id, data.km_awal)?;
+    validasi_km(&data)?;
+    validasi_bbm(&data)?;
+    data.biaya_km_lebih = hitung_biaya_km_lebih(conn, &data)?;
+    data.biaya_bbm = hitung_biaya_bbm(conn, &data)?;
+
+    // Nomor dokumen dan INSERT dalam satu transaksi supaya nomor tidak bolong
+    atomic(conn, |conn| {
+        let nomor = crate::penomoran::nomor_berikutnya(conn, "transaksi", &data.tanggal_sewa)?;
+
+        conn.execute(
+            "INSERT INTO transaksi (motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana, tanggal_kembali_aktual, hari_terlambat, total_bayar, status, denda, foto_bukti, diskon, nomor, km_awal, km_akhir, biaya_km_lebih, bbm_awal, bbm_akhir, biaya_bbm) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+            rusqlite::params![data.motor_id, data.penyewa_id, data.tanggal_sewa, data.tanggal_kembali_rencana, data.tanggal_kembali_aktual, data.hari_terlambat, data.total_bayar, data.status, data.denda, data.foto_bukti, data.diskon, nomor, data.km_awal, data.km_akhir, data.biaya_km_lebih, data.bbm_awal, data.bbm_akhir, data.biaya_bbm],
+        )?;
+
+        // Update motor status to dipinjam
+        conn.execute(
+            "UPDATE motor SET status = 'dipinjam' WHERE motor_id = ?1",
+            (motor_id,),
+        )?;
+
+        Ok(())
+    })
+}
+
+// Odometer motor tidak pernah turun, jadi km awal sewa tidak boleh di bawahnya
+fn validasi_km_awal_odometer(conn: &Connection, motor_id: i32, km_awal: Option<i64>) -> Result<()> {
+    let (Some(awal), Some(odometer)) = (km_awal, get_motor_by_id(conn, motor_id)?.odometer) else {
+        return Ok(());
+    };
+    if awal < odometer {
+        return Err(validation_error(format!(
+            "Km awal ({}) lebih kecil dari odometer motor saat ini ({})",
+            awal, odometer
+        )));
+    }
+    Ok(())
+}
+
+fn validasi_km(data: &crate::models::Transaksi) -> Result<()> {
+    if let Some(awal) = data.km_awal {
+        if awal < 0 {
+            return Err(validation_error("Km awal tidak boleh negatif"));
+        }
+    }
+    if let (Some(awal), Some(akhir)) = (data.km_awal, data.km_akhir) {
+        if akhir < awal {
+            return Err(validation_error(format!(
+                "Km kembali ({}) tidak boleh lebih kecil dari km awal ({})",
+                akhir, awal
+            )));
+        }
+    }
+    Ok(())
+}
+
+// Level BBM dicatat dalam persen tangki (0-100)
+fn validasi_bbm(data: &crate::models::Transaksi) -> Result<()> {
+    for (label, level) in [("awal", data.bbm_awal), ("kembali", data.bbm_akhir)] {
+        if let Some(level) = level {
+            if !(0..=100).contains(&level) {
+                return Err(validation_error(format!(
+                    "Level BBM {} harus antara 0 dan 100 persen",
+                    label
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Biaya isi ulang jika BBM kembali lebih sedikit dari saat diambil:
+// selisih persen x biaya_bbm_penuh / 100, ditambah biaya_layanan_bbm (opsional).
+fn hitung_biaya_bbm(conn: &Connection, data: &crate::models::Transaksi) -> Result<Option<i64>> {
+    let (Some(awal), Some(akhir)) = (data.bbm_awal, data.bbm_akhir) else {
+        return Ok(None);
+    };
+
+    let selisih = awal - akhir;
+    if selisih <= 0 {
+        return Ok(Some(0));
+    }
+
+    let angka = |key: &str| -> Result<i64> {
+        Ok(get_pengaturan_value(conn, key)?
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0))
+    };
+    let biaya_penuh = angka("biaya_bbm_penuh")?;
+    let biaya_layanan = angka("biaya_layanan_bbm")?;
+
+    if biaya_penuh <= 0 {
+        return Ok(Some(0));
+    }
+
+    // Dibulatkan ke atas supaya kekurangan sedikit pun tetap tertagih
+    let biaya_isi = (selisih * biaya_penuh + 99) / 100;
+    Ok(Some(biaya_isi + biaya_layanan))
+}
+
+// Jumlah hari sewa (minimal 1), sampai tanggal kembali aktual jika sudah ada
+fn hari_sewa(data: &crate::models::Transaksi) -> i64 {
+    let parse = |s: &str| {
+        s.get(0..10)
+            .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+    };
+    let sampai = data
+        .tanggal_kembali_aktual
+        .as_deref()
+        .filter(|s| !s.is_empty())
+        .unwrap_or(&data.tanggal_kembali_rencana);
+
+    match (parse(&data.tanggal_sewa), parse(sampai)) {
+        (Some(mulai), Some(selesai)) => (selesai - mulai).num_days().max(1),
+        _ => 1,
+    }
+}
+
+// Biaya kelebihan km: km di atas jatah (batas_km_per_hari x hari sewa) dikali tarif_km_lebih.
+// Tidak dikenakan jika salah satu pengaturan kosong/0.
+fn hitung_biaya_km_lebih(conn: &Connection, data: &crate::models::Transaksi) -> Result<Option<i64>> {
+    let (Some(awal), Some(akhir)) = (data.km_awal, data.km_akhir) else {
+        return Ok(None);
+    };
+
+    let angka = |key: &str| -> Result<i64> {
+        Ok(get_pengaturan_value(conn, key)?
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0))
+    };
+    let batas_per_hari = angka("batas_km_per_hari")?;
+    let tarif = angka("tarif_km_lebih")?;
+
+    if batas_per_hari <= 0 || tarif <= 0 {
+        return Ok(Some(0));
+    }
+
+    let lebih = (akhir - awal) - batas_per_hari * hari_sewa(data);
+    Ok(Some(lebih.max(0) * tarif))
+}
+
+// Status motor yang sedang di bengkel dan tidak boleh disewakan
+pub const STATUS_MOTOR_TIDAK_BISA_DISEWA: [&str; 2] = ["servis", "perbaikan"];
+
+// Motor berstatus servis/perbaikan tidak boleh disewakan. Jika pengaturan
+// tolak_sewa_dokumen_kadaluarsa aktif, motor dengan STNK/pajak kadaluarsa juga ditolak.
+pub fn validasi_motor_bisa_disewa(conn: &Connection, motor_id: i32) -> Result<()> {
+    let motor = get_motor_by_id(conn, motor_id)?;
+
+    if STATUS_MOTOR_TIDAK_BISA_DISEWA.contains(&motor.status.as_str()) {
+        return Err(validation_error(format!(
+            "Motor {} ({}) sedang {} dan tidak bisa disewakan",
+            motor.nama, motor.plat, motor.status
+        )));
+    }
+
+    let tolak_dokumen_kadaluarsa = matches!(
+        get_pengaturan_value(conn, "tolak_sewa_dokumen_kadaluarsa")?.as_deref(),
+        Some("1") | Some("true")
+    );
+    if tolak_dokumen_kadaluarsa {
+        // hari = -1: hanya yang tanggal kadaluarsanya sebelum hari ini
+        let kadaluarsa: Vec<String> = get_dokumen_motor_akan_kadaluarsa(conn, -1, Some(motor_id))?
+            .into_iter()
+            .filter(|d| JENIS_DOKUMEN_WAJIB_SEWA.contains(&d.jenis.as_str()))
+            .map(|d| format!("{} ({})", d.jenis, d.tanggal_kadaluarsa))
+            .collect();
+
+        if !kadaluarsa.is_empty() {
+            return Err(validation_error(format!(
+                "Motor {} ({}) tidak bisa disewakan, dokumen kadaluarsa: {}",
+                motor.nama,
+                motor.plat,
+                kadaluarsa.join(", ")
+            )));
+        }
+    }
 
     Ok(())
 }
 
 pub fn get_transaksi_by_id(conn: &Connection, id: i32) -> Result<crate::models::Transaksi> {
-    let mut stmt = conn.prepare("SELECT transaksi_id, motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana, tanggal_kembali_aktual, hari_terlambat, total_bayar, status, denda, foto_bukti, diskon FROM transaksi WHERE transaksi_id = ?1")?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transaksi WHERE transaksi_id = ?1",
+        TRANSAKSI_COLUMNS
+    ))?;
     let mut rows = stmt.query([id])?;
 
     if let Some(row) = rows.next()? {
-        Ok(crate::models::Transaksi {
-            transaksi_id: row.get(0)?,
-            motor_id: row.get(1)?,
-            penyewa_id: row.get(2)?,
-            tanggal_sewa: row.get(3)?,
-            tanggal_kembali_rencana: row.get(4)?,
-            tanggal_kembali_aktual: row.get(5)?,
-            hari_terlambat: row.get(6)?,
-            total_bayar: row.get(7)?,
-            status: row.get(8)?,
-            denda: row.get(9)?,
-            foto_bukti: row.get(10)?,
-            diskon: row.get(11)?,
-        })
+        map_transaksi(conn, row)
     } else {
         Err(rusqlite::Error::QueryReturnedNoRows)
     }
 }
 
-pub fn update_transaksi(conn: &Connection, id: i32, data: crate::models::Transaksi) -> Result<()> {
+pub fn update_transaksi(
+    conn: &Connection,
+    id: i32,
+    mut data: crate::models::Transaksi,
+) -> Result<()> {
     // Get old motor_id before updating
     let old_transaksi = get_transaksi_by_id(conn, id)?;
     let old_motor_id = old_transaksi.motor_id;
@@ -261,7 +848,44 @@ pub fn update_transaksi(conn: &Connection, id: i32, data: crate::models::Transak
     let new_motor_id = data.motor_id;
     let is_kembali = data.status == "kembali";
 
-    conn.execute("UPDATE transaksi SET motor_id = ?1, penyewa_id = ?2, tanggal_sewa = ?3, tanggal_kembali_rencana = ?4, tanggal_kembali_aktual = ?5, hari_terlambat = ?6, total_bayar = ?7, status = ?8, denda = ?9, foto_bukti = ?10, diskon = ?11 WHERE transaksi_id = ?12", (data.motor_id, data.penyewa_id, data.tanggal_sewa, data.tanggal_kembali_rencana, data.tanggal_kembali_aktual, data.hari_terlambat, data.total_bayar, data.status, data.denda, data.foto_bukti, data.diskon, id))?;
+    if old_motor_id != new_motor_id {
+        validasi_motor_bisa_disewa(conn, new_motor_id)?;
+    }
+    if old_transaksi.penyewa_id != data.penyewa_id {
+        validasi_penyewa_bisa_menyewa(conn, data.penyewa_id)?;
+    }
+
+    // Form lama yang belum mengirim km/BBM tidak menghapus data yang sudah tercatat
+    if data.km_awal.is_none() {
+        data.km_awal = old_transaksi.km_awal;
+    }
+    if data.km_akhir.is_none() {
+        data.km_akhir = old_transaksi.km_akhir;
+    }
+    if data.bbm_awal.is_none() {
+        data.bbm_awal = old_transaksi.bbm_awal;
+    }
+    if data.bbm_akhir.is_none() {
+        data.bbm_akhir = old_transaksi.bbm_akhir;
+    }
+    // Setelah motor kembali odometer sudah dinaikkan oleh transaksi ini sendiri,
+    // jadi km awal hanya dicek terhadap odometer selama km kembali belum tercatat
+    let km_awal_berubah = data.km_awal != old_transaksi.km_awal;
+    if old_motor_id != new_motor_id || (km_awal_berubah && old_transaksi.km_akhir.is_none()) {
+        validasi_km_awal_odometer(conn, new_motor_id, data.km_awal)?;
+    }
+    validasi_km(&data)?;
+    validasi_bbm(&data)?;
+    data.biaya_km_lebih = hitung_biaya_km_lebih(conn, &data)?;
+    data.biaya_bbm = hitung_biaya_bbm(conn, &data)?;
+    let km_akhir = data.km_akhir;
+
+    conn.execute("UPDATE transaksi SET motor_id = ?1, penyewa_id = ?2, tanggal_sewa = ?3, tanggal_kembali_rencana = ?4, tanggal_kembali_aktual = ?5, hari_terlambat = ?6, total_bayar = ?7, status = ?8, denda = ?9, foto_bukti = ?10, diskon = ?11, km_awal = ?12, km_akhir = ?13, biaya_km_lebih = ?14, bbm_awal = ?15, bbm_akhir = ?16, biaya_bbm = ?17 WHERE transaksi_id = ?18", rusqlite::params![data.motor_id, data.penyewa_id, data.tanggal_sewa, data.tanggal_kembali_rencana, data.tanggal_kembali_aktual, data.hari_terlambat, data.total_bayar, data.status, data.denda, data.foto_bukti, data.diskon, data.km_awal, data.km_akhir, data.biaya_km_lebih, data.bbm_awal, data.bbm_akhir, data.biaya_bbm, id])?;
+
+    // Km saat motor kembali menjadi odometer motor yang baru
+    if let Some(km) = km_akhir {
+        update_odometer_motor(conn, new_motor_id, km)?;
+    }
 
     // If motor changed, reset old motor status
     if old_motor_id != new_motor_id {
@@ -295,18 +919,43 @@ pub fn delete_transaksi(conn: &Connection, id: i32) -> Result<()> {
     Ok(())
 }
 
+fn map_bukti_pelunasan(conn: &Connection, row: &rusqlite::Row) -> Result<crate::models::BuktiPelunasan> {
+    let foto_bukti: String = row.get(5)?;
+    Ok(crate::models::BuktiPelunasan {
+        bukti_id: row.get(0)?,
+        transaksi_id: row.get(1)?,
+        tanggal_bayar: row.get(2)?,
+        jumlah_bayar: row.get(3)?,
+        metode_bayar: row.get(4)?,
+        foto_bukti_thumbnail: crate::lampiran::thumbnail(conn, &foto_bukti)?,
+        foto_bukti,
+        nomor: row.get(6)?,
+    })
+}
+
 pub fn get_all_bukti_pelunasan(conn: &Connection) -> Result<Vec<crate::models::BuktiPelunasan>> {
-    let mut stmt = conn.prepare("SELECT bukti_id, transaksi_id, tanggal_bayar, jumlah_bayar, metode_bayar, foto_bukti FROM bukti_pelunasan")?;
-    let bukti_iter = stmt.query_map([], |row| {
-        Ok(crate::models::BuktiPelunasan {
-            bukti_id: row.get(0)?,
-            transaksi_id: row.get(1)?,
-            tanggal_bayar: row.get(2)?,
-            jumlah_bayar: row.get(3)?,
-            metode_bayar: row.get(4)?,
-            foto_bukti: row.get(5)?,
-        })
-    })?;
+    let mut stmt = conn.prepare("SELECT bukti_id, transaksi_id, tanggal_bayar, jumlah_bayar, metode_bayar, foto_bukti, nomor FROM bukti_pelunasan")?;
+    let bukti_iter = stmt.query_map([], |row| map_bukti_pelunasan(conn, row))?;
+
+    let mut result = Vec::new();
+    for b in bukti_iter {
+        result.push(b?);
+    }
+    Ok(result)
+}
+
+pub fn get_bukti_pelunasan_by_penyewa(
+    conn: &Connection,
+    penyewa_id: i32,
+) -> Result<Vec<crate::models::BuktiPelunasan>> {
+    let mut stmt = conn.prepare(
+        "SELECT b.bukti_id, b.transaksi_id, b.tanggal_bayar, b.jumlah_bayar, b.metode_bayar, b.foto_bukti, b.nomor
+         FROM bukti_pelunasan b
+         JOIN transaksi t ON t.transaksi_id = b.transaksi_id
+         WHERE t.penyewa_id = ?1
+         ORDER BY b.tanggal_bayar DESC, b.bukti_id DESC",
+    )?;
+    let bukti_iter = stmt.query_map([penyewa_id], |row| map_bukti_pelunasan(conn, row))?;
 
     let mut result = Vec::new();
     for b in bukti_iter {
@@ -319,26 +968,22 @@ pub fn create_bukti_pelunasan(
     conn: &Connection,
     data: crate::models::BuktiPelunasan,
 ) -> Result<()> {
-    conn.execute("INSERT INTO bukti_pelunasan (transaksi_id, tanggal_bayar, jumlah_bayar, metode_bayar, foto_bukti) VALUES (?1, ?2, ?3, ?4, ?5)", (data.transaksi_id, data.tanggal_bayar, data.jumlah_bayar, data.metode_bayar, data.foto_bukti))?;
-    Ok(())
+    atomic(conn, |conn| {
+        let nomor = crate::penomoran::nomor_berikutnya(conn, "bukti_pelunasan", &data.tanggal_bayar)?;
+        conn.execute("INSERT INTO bukti_pelunasan (transaksi_id, tanggal_bayar, jumlah_bayar, metode_bayar, foto_bukti, nomor) VALUES (?1, ?2, ?3, ?4, ?5, ?6)", (data.transaksi_id, data.tanggal_bayar, data.jumlah_bayar, data.metode_bayar, data.foto_bukti, nomor))?;
+        Ok(())
+    })
 }
 
 pub fn get_bukti_pelunasan_by_id(
     conn: &Connection,
     id: i32,
 ) -> Result<crate::models::BuktiPelunasan> {
-    let mut stmt = conn.prepare("SELECT bukti_id, transaksi_id, tanggal_bayar, jumlah_bayar, metode_bayar, foto_bukti FROM bukti_pelunasan WHERE bukti_id = ?1")?;
+    let mut stmt = conn.prepare("SELECT bukti_id, transaksi_id, tanggal_bayar, jumlah_bayar, metode_bayar, foto_bukti, nomor FROM bukti_pelunasan WHERE bukti_id = ?1")?;
     let mut rows = stmt.query([id])?;
 
     if let Some(row) = rows.next()? {
-        Ok(crate::models::BuktiPelunasan {
-            bukti_id: row.get(0)?,
-            transaksi_id: row.get(1)?,
-            tanggal_bayar: row.get(2)?,
-            jumlah_bayar: row.get(3)?,
-            metode_bayar: row.get(4)?,
-            foto_bukti: row.get(5)?,
-        })
+        map_bukti_pelunasan(conn, row)
     } else {
         Err(rusqlite::Error::QueryReturnedNoRows)
     }
@@ -358,47 +1003,11 @@ pub fn delete_bukti_pelunasan(conn: &Connection, id: i32) -> Result<()> {
     Ok(())
 }
 
-use base64::prelude::*;
-use uuid::Uuid;
-
-pub fn save_bukti_pelunasan_image(base64_data: String) -> Result<String, String> {
-    let mut path = get_db_path();
-    path.pop(); // Remove rental_motor.sqlite
-    path.push("uploads");
-
-    if !path.exists() {
-        fs::create_dir_all(&path).map_err(|e| e.to_string())?;
-    }
-
-    let filename = format!("{}.jpg", Uuid::new_v4());
-    path.push(filename);
-
-    // Handle data URI scheme if present (e.g. "data:image/jpeg;base64,...")
-    let b64_string = if let Some(index) = base64_data.find(',') {
-        &base64_data[index + 1..]
-    } else {
-        &base64_data
-    };
-
-    // Fix: Remove any whitespace (newlines) that might be in the base64 string
-    let clean_b64: String = b64_string.chars().filter(|c| !c.is_whitespace()).collect();
-
-    let decoded = BASE64_STANDARD
-        .decode(clean_b64)
-        .map_err(|e| e.to_string())?;
-    fs::write(&path, decoded).map_err(|e| e.to_string())?;
-
-    // Return connection asset protocol URL for frontend display
-    // Windows path needs to be converted for convertFileSrc in frontend,
-    // but here we just return absolute path and let frontend handle conversion
-    Ok(path.to_string_lossy().to_string())
-}
-
 pub fn get_all_pengeluaran_rental(
     conn: &Connection,
 ) -> Result<Vec<crate::models::PengeluaranRental>> {
     let mut stmt = conn.prepare(
-        "SELECT pengeluaran_id, tanggal, jenis, nominal, keterangan, sumber_dana FROM pengeluaran_rental",
+        "SELECT pengeluaran_id, tanggal, jenis, nominal, keterangan, sumber_dana, nomor, motor_id FROM pengeluaran_rental",
     )?;
     let pengeluaran_iter = stmt.query_map([], |row| {
         Ok(crate::models::PengeluaranRental {
@@ -408,6 +1017,8 @@ pub fn get_all_pengeluaran_rental(
             nominal: row.get(3)?,
             keterangan: row.get(4)?,
             sumber_dana: row.get(5)?,
+            nomor: row.get(6)?,
+            motor_id: row.get(7)?,
         })
     })?;
 
@@ -422,15 +1033,27 @@ pub fn create_pengeluaran_rental(
     conn: &Connection,
     data: crate::models::PengeluaranRental,
 ) -> Result<()> {
-    conn.execute("INSERT INTO pengeluaran_rental (tanggal, jenis, nominal, keterangan, sumber_dana) VALUES (?1, ?2, ?3, ?4, ?5)", (data.tanggal, data.jenis, data.nominal, data.keterangan, data.sumber_dana.unwrap_or("Kas".to_string())))?;
+    insert_pengeluaran_rental(conn, data)?;
     Ok(())
 }
 
+// Sama seperti create_pengeluaran_rental tapi mengembalikan pengeluaran_id baru
+pub fn insert_pengeluaran_rental(
+    conn: &Connection,
+    data: crate::models::PengeluaranRental,
+) -> Result<i64> {
+    atomic(conn, |conn| {
+        let nomor = crate::penomoran::nomor_berikutnya(conn, "pengeluaran_rental", &data.tanggal)?;
+        conn.execute("INSERT INTO pengeluaran_rental (tanggal, jenis, nominal, keterangan, sumber_dana, nomor, motor_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)", (data.tanggal, data.jenis, data.nominal, data.keterangan, data.sumber_dana.unwrap_or("Kas".to_string()), nomor, data.motor_id))?;
+        Ok(conn.last_insert_rowid())
+    })
+}
+
 pub fn get_pengeluaran_rental_by_id(
     conn: &Connection,
     id: i32,
 ) -> Result<crate::models::PengeluaranRental> {
-    let mut stmt = conn.prepare("SELECT pengeluaran_id, tanggal, jenis, nominal, keterangan, sumber_dana FROM pengeluaran_rental WHERE pengeluaran_id = ?1")?;
+    let mut stmt = conn.prepare("SELECT pengeluaran_id, tanggal, jenis, nominal, keterangan, sumber_dana, nomor, motor_id FROM pengeluaran_rental WHERE pengeluaran_id = ?1")?;
     let mut rows = stmt.query([id])?;
 
     if let Some(row) = rows.next()? {
@@ -441,6 +1064,8 @@ pub fn get_pengeluaran_rental_by_id(
             nominal: row.get(3)?,
             keterangan: row.get(4)?,
             sumber_dana: row.get(5)?,
+            nomor: row.get(6)?,
+            motor_id: row.get(7)?,
         })
     } else {
         Err(rusqlite::Error::QueryReturnedNoRows)
@@ -452,7 +1077,7 @@ pub fn update_pengeluaran_rental(
     id: i32,
     data: crate::models::PengeluaranRental,
 ) -> Result<()> {
-    conn.execute("UPDATE pengeluaran_rental SET tanggal = ?1, jenis = ?2, nominal = ?3, keterangan = ?4, sumber_dana = ?5 WHERE pengeluaran_id = ?6", (data.tanggal, data.jenis, data.nominal, data.keterangan, data.sumber_dana.unwrap_or("Kas".to_string()), id))?;
+    conn.execute("UPDATE pengeluaran_rental SET tanggal = ?1, jenis = ?2, nominal = ?3, keterangan = ?4, sumber_dana = ?5, motor_id = ?6 WHERE pengeluaran_id = ?7", (data.tanggal, data.jenis, data.nominal, data.keterangan, data.sumber_dana.unwrap_or("Kas".to_string()), data.motor_id, id))?;
     Ok(())
 }
 
@@ -463,3 +1088,1118 @@ pub fn delete_pengeluaran_rental(conn: &Connection, id: i32) -> Result<()> {
     )?;
     Ok(())
 }
+
+// Tandai transaksi yang lewat tanggal_kembali_rencana dan belum kembali sebagai
+// 'terlambat', sekaligus hitung ulang hari_terlambat dan denda berjalan.
+pub fn tandai_transaksi_terlambat(
+    conn: &Connection,
+    hari_ini: chrono::NaiveDate,
+) -> Result<Vec<crate::models::TransaksiTerlambat>> {
+    let denda_per_hari: i64 = get_pengaturan_value(conn, "denda_per_hari")?
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0);
+
+    let mut stmt = conn.prepare(
+        "SELECT t.transaksi_id, t.nomor, COALESCE(p.nama, '-'), COALESCE(p.no_hp, ''),
+                COALESCE(m.nama, '-'), COALESCE(m.plat, '-'), t.tanggal_kembali_rencana, t.status
+         FROM transaksi t
+         LEFT JOIN penyewa p ON p.penyewa_id = t.penyewa_id
+         LEFT JOIN motor m ON m.motor_id = t.motor_id
+         WHERE t.status IN ('dipinjam', 'terlambat')
+           AND (t.tanggal_kembali_aktual IS NULL OR t.tanggal_kembali_aktual = '')",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            crate::models::TransaksiTerlambat {
+                transaksi_id: row.get(0)?,
+                nomor: row.get(1)?,
+                penyewa: row.get(2)?,
+                no_hp: row.get(3)?,
+                motor: row.get(4)?,
+                plat: row.get(5)?,
+                tanggal_kembali_rencana: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+                hari_terlambat: 0,
+                denda: 0,
+                baru_terlambat: false,
+            },
+            row.get::<_, Option<String>>(7)?.unwrap_or_default(),
+        ))
+    })?;
+
+    let mut result = Vec::new();
+    for r in rows {
+        let (mut t, status) = r?;
+
+        let Some(rencana) = t
+            .tanggal_kembali_rencana
+            .get(0..10)
+            .and_then(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+        else {
+            continue;
+        };
+
+        let hari = (hari_ini - rencana).num_days();
+        if hari <= 0 {
+            continue;
+        }
+
+        t.hari_terlambat = hari;
+        t.denda = hari * denda_per_hari;
+        t.baru_terlambat = status == "dipinjam";
+        result.push(t);
+    }
+
+    // Denda hanya dinaikkan: denda yang diubah manual (lebih besar) tidak ditimpa,
+    // dan denda_per_hari yang belum diatur tidak mereset denda ke 0.
+    // Semua baris diubah sekaligus atau tidak sama sekali.
+    atomic(conn, |conn| {
+        for t in &mut result {
+            t.denda = conn.query_row(
+                "UPDATE transaksi SET status = 'terlambat', hari_terlambat = ?1,
+                     denda = MAX(COALESCE(denda, 0), ?2)
+                 WHERE transaksi_id = ?3
+                 RETURNING denda",
+                (t.hari_terlambat, t.denda, t.transaksi_id),
+                |row| row.get(0),
+            )?;
+        }
+        Ok(())
+    })?;
+
+    Ok(result)
+}
+
+fn map_pengingat(row: &rusqlite::Row) -> Result<crate::models::Pengingat> {
+    Ok(crate::models::Pengingat {
+        pengingat_id: row.get(0)?,
+        jenis: row.get(1)?,
+        ref_id: row.get(2)?,
+        judul: row.get(3)?,
+        pesan: row.get(4)?,
+        tanggal: row.get(5)?,
+        dibaca: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+pub fn get_all_pengingat(
+    conn: &Connection,
+    hanya_belum_dibaca: bool,
+) -> Result<Vec<crate::models::Pengingat>> {
+    let mut stmt = conn.prepare(
+        "SELECT pengingat_id, jenis, ref_id, judul, pesan, tanggal, dibaca, created_at FROM pengingat
+         WHERE (?1 = 0 OR dibaca = 0)
+         ORDER BY created_at DESC, pengingat_id DESC",
+    )?;
+    let pengingat_iter = stmt.query_map([hanya_belum_dibaca], map_pengingat)?;
+
+    let mut result = Vec::new();
+    for p in pengingat_iter {
+        result.push(p?);
+    }
+    Ok(result)
+}
+
+pub fn count_pengingat_belum_dibaca(conn: &Connection) -> Result<i64> {
+    conn.query_row("SELECT COUNT(*) FROM pengingat WHERE dibaca = 0", [], |row| {
+        row.get(0)
+    })
+}
+
+// Simpan pengingat baru. Kombinasi (jenis, ref_id, tanggal) unik sehingga
+// pengingat yang sama tidak dibuat dua kali; None jika sudah pernah ada.
+pub fn create_pengingat(
+    conn: &Connection,
+    jenis: &str,
+    ref_id: Option<i32>,
+    judul: &str,
+    pesan: &str,
+    tanggal: &str,
+) -> Result<Option<crate::models::Pengingat>> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO pengingat (jenis, ref_id, judul, pesan, tanggal, dibaca, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, 0, datetime('now', 'localtime'))",
+        (jenis, ref_id, judul, pesan, tanggal),
+    )?;
+
+    if inserted == 0 {
+        return Ok(None);
+    }
+
+    conn.query_row(
+        "SELECT pengingat_id, jenis, ref_id, judul, pesan, tanggal, dibaca, created_at FROM pengingat WHERE pengingat_id = ?1",
+        [conn.last_insert_rowid()],
+        map_pengingat,
+    )
+    .map(Some)
+}
+
+pub fn set_pengingat_dibaca(conn: &Connection, id: i32, dibaca: bool) -> Result<()> {
+    conn.execute(
+        "UPDATE pengingat SET dibaca = ?1 WHERE pengingat_id = ?2",
+        (dibaca, id),
+    )?;
+    Ok(())
+}
+
+pub fn set_semua_pengingat_dibaca(conn: &Connection) -> Result<()> {
+    conn.execute("UPDATE pengingat SET dibaca = 1 WHERE dibaca = 0", [])?;
+    Ok(())
+}
+
+pub fn delete_pengingat(conn: &Connection, id: i32) -> Result<()> {
+    conn.execute("DELETE FROM pengingat WHERE pengingat_id = ?1", (id,))?;
+    Ok(())
+}
+
+// Tagihan per transaksi sebagai subquery, dipakai untuk satu transaksi, seluruh daftar
+// maupun agregat laporan. total_bayar sudah termasuk potongan diskon; biaya BBM,
+// kelebihan km dan kerusakan ditagihkan bersama denda di atas harga sewa.
+pub const TAGIHAN_TRANSAKSI_SQL: &str = "SELECT *, total_bayar + denda + biaya_km_lebih + biaya_bbm + biaya_kerusakan AS total_tagihan
+     FROM (
+         SELECT t.transaksi_id, t.penyewa_id, t.tanggal_sewa, t.status, COALESCE(t.hari_terlambat, 0) AS hari_terlambat,
+                COALESCE(t.total_bayar, 0) AS total_bayar, COALESCE(t.denda, 0) AS denda,
+                COALESCE(t.biaya_km_lebih, 0) AS biaya_km_lebih, COALESCE(t.biaya_bbm, 0) AS biaya_bbm,
+                (SELECT COALESCE(SUM(k.biaya_dibebankan), 0) FROM kerusakan k WHERE k.transaksi_id = t.transaksi_id) AS biaya_kerusakan,
+                (SELECT COALESCE(SUM(b.jumlah_bayar), 0) FROM bukti_pelunasan b WHERE b.transaksi_id = t.transaksi_id) AS dibayar
+         FROM transaksi t
+     )";
+
+fn query_tagihan() -> String {
+    format!(
+        "SELECT transaksi_id, total_bayar, denda, biaya_km_lebih, biaya_bbm, biaya_kerusakan, total_tagihan, dibayar
+         FROM ({}) WHERE ?1 IS NULL OR transaksi_id = ?1",
+        TAGIHAN_TRANSAKSI_SQL
+    )
+}
+
+fn map_tagihan(row: &rusqlite::Row) -> Result<crate::models::Tagihan> {
+    let total_tagihan: i64 = row.get(6)?;
+    let dibayar: i64 = row.get(7)?;
+
+    Ok(crate::models::Tagihan {
+        transaksi_id: row.get(0)?,
+        total_bayar: row.get(1)?,
+        denda: row.get(2)?,
+        biaya_km_lebih: row.get(3)?,
+        biaya_bbm: row.get(4)?,
+        biaya_kerusakan: row.get(5)?,
+        total_tagihan,
+        dibayar,
+        sisa_bayar: (total_tagihan - dibayar).max(0),
+    })
+}
+
+// Total yang harus dibayar untuk satu transaksi dikurangi pembayaran yang sudah masuk.
+pub fn get_tagihan_transaksi(conn: &Connection, id: i32) -> Result<crate::models::Tagihan> {
+    conn.query_row(&query_tagihan(), [Some(id)], map_tagihan)
+}
+
+pub fn get_all_tagihan(conn: &Connection) -> Result<Vec<crate::models::Tagihan>> {
+    let mut stmt = conn.prepare(&query_tagihan())?;
+    let rows = stmt.query_map([None::<i32>], map_tagihan)?;
+    rows.collect()
+}
+
+const SERVIS_MOTOR_COLUMNS: &str = "servis_id, motor_id, tanggal, jenis, odometer, biaya, suku_cadang, keterangan, servis_berikut_tanggal, servis_berikut_km, pengeluaran_id";
+
+fn map_servis_motor(row: &rusqlite::Row) -> Result<crate::models::ServisMotor> {
+    let pengeluaran_id: Option<i32> = row.get(10)?;
+    Ok(crate::models::ServisMotor {
+        servis_id: row.get(0)?,
+        motor_id: row.get(1)?,
+        tanggal: row.get(2)?,
+        jenis: row.get(3)?,
+        odometer: row.get(4)?,
+        biaya: row.get(5)?,
+        suku_cadang: row.get(6)?,
+        keterangan: row.get(7)?,
+        servis_berikut_tanggal: row.get(8)?,
+        servis_berikut_km: row.get(9)?,
+        pengeluaran_id,
+        catat_pengeluaran: pengeluaran_id.is_some(),
+    })
+}
+
+pub fn get_all_servis_motor(
+    conn: &Connection,
+    motor_id: Option<i32>,
+) -> Result<Vec<crate::models::ServisMotor>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM servis_motor WHERE (?1 IS NULL OR motor_id = ?1) ORDER BY tanggal DESC, servis_id DESC",
+        SERVIS_MOTOR_COLUMNS
+    ))?;
+    let servis_iter = stmt.query_map([motor_id], map_servis_motor)?;
+
+    let mut result = Vec::new();
+    for s in servis_iter {
+        result.push(s?);
+    }
+    Ok(result)
+}
+
+pub fn get_servis_motor_by_id(conn: &Connection, id: i32) -> Result<crate::models::ServisMotor> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM servis_motor WHERE servis_id = ?1",
+            SERVIS_MOTOR_COLUMNS
+        ),
+        [id],
+        map_servis_motor,
+    )
+}
+
+// Data pengeluaran_rental yang mewakili biaya servis
+fn pengeluaran_servis(
+    conn: &Connection,
+    data: &crate::models::ServisMotor,
+) -> Result<crate::models::PengeluaranRental> {
+    let motor = get_motor_by_id(conn, data.motor_id)?;
+    Ok(crate::models::PengeluaranRental {
+        pengeluaran_id: 0,
+        tanggal: data.tanggal.clone(),
+        jenis: "Servis Motor".to_string(),
+        nominal: data.biaya,
+        keterangan: format!("{} - {} ({})", data.jenis, motor.nama, motor.plat),
+        sumber_dana: None,
+        nomor: None,
+        motor_id: Some(data.motor_id),
+    })
+}
+
+pub fn create_servis_motor(conn: &Connection, data: crate::models::ServisMotor) -> Result<()> {
+    atomic(conn, |conn| {
+        let pengeluaran_id = if data.catat_pengeluaran && data.biaya > 0 {
+            Some(insert_pengeluaran_rental(conn, pengeluaran_servis(conn, &data)?)?)
+        } else {
+            None
+        };
+
+        conn.execute(
+            "INSERT INTO servis_motor (motor_id, tanggal, jenis, odometer, biaya, suku_cadang, keterangan, servis_berikut_tanggal, servis_berikut_km, pengeluaran_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            (data.motor_id, &data.tanggal, &data.jenis, data.odometer, data.biaya, &data.suku_cadang, &data.keterangan, &data.servis_berikut_tanggal, data.servis_berikut_km, pengeluaran_id),
+        )?;
+        if let Some(km) = data.odometer {
+            update_odometer_motor(conn, data.motor_id, km)?;
+        }
+        Ok(())
+    })
+}
+
+pub fn update_servis_motor(
+    conn: &Connection,
+    id: i32,
+    data: crate::models::ServisMotor,
+) -> Result<()> {
+    let old = get_servis_motor_by_id(conn, id)?;
+
+    atomic(conn, |conn| {
+        // Sinkronkan pengeluaran yang tertaut dengan data servis terbaru
+        let pengeluaran_id = match (old.pengeluaran_id, data.catat_pengeluaran && data.biaya > 0) {
+            (Some(pid), true) => {
+                let pengeluaran = pengeluaran_servis(conn, &data)?;
+                conn.execute(
+                    "UPDATE pengeluaran_rental SET tanggal = ?1, nominal = ?2, keterangan = ?3, motor_id = ?4 WHERE pengeluaran_id = ?5",
+                    (pengeluaran.tanggal, pengeluaran.nominal, pengeluaran.keterangan, pengeluaran.motor_id, pid),
+                )?;
+                Some(pid as i64)
+            }
+            (Some(pid), false) => {
+                delete_pengeluaran_rental(conn, pid)?;
+                None
+            }
+            (None, true) => Some(insert_pengeluaran_rental(conn, pengeluaran_servis(conn, &data)?)?),
+            (None, false) => None,
+        };
+
+        conn.execute(
+            "UPDATE servis_motor SET motor_id = ?1, tanggal = ?2, jenis = ?3, odometer = ?4, biaya = ?5, suku_cadang = ?6, keterangan = ?7, servis_berikut_tanggal = ?8, servis_berikut_km = ?9, pengeluaran_id = ?10 WHERE servis_id = ?11",
+            (data.motor_id, &data.tanggal, &data.jenis, data.odometer, data.biaya, &data.suku_cadang, &data.keterangan, &data.servis_berikut_tanggal, data.servis_berikut_km, pengeluaran_id, id),
+        )?;
+        if let Some(km) = data.odometer {
+            update_odometer_motor(conn, data.motor_id, km)?;
+        }
+        Ok(())
+    })
+}
+
+pub fn delete_servis_motor(conn: &Connection, id: i32) -> Result<()> {
+    let old = get_servis_motor_by_id(conn, id)?;
+
+    atomic(conn, |conn| {
+        if let Some(pid) = old.pengeluaran_id {
+            delete_pengeluaran_rental(conn, pid)?;
+        }
+        conn.execute("DELETE FROM servis_motor WHERE servis_id = ?1", (id,))?;
+        Ok(())
+    })
+}
+
+// Km terakhir yang diketahui untuk motor
+pub fn get_odometer_motor(conn: &Connection, motor_id: i32) -> Result<Option<i64>> {
+    conn.query_row(
+        "SELECT odometer FROM motor WHERE motor_id = ?1",
+        [motor_id],
+        |row| row.get(0),
+    )
+}
+
+// Motor ditandai perlu servis jika jadwalnya tinggal sekian hari / km lagi
+pub const SERVIS_AMBANG_HARI_DEFAULT: i64 = 7;
+pub const SERVIS_AMBANG_KM_DEFAULT: i64 = 200;
+
+// Motor yang jadwal servis berikutnya sudah lewat atau tinggal `ambang_hari` hari /
+// `ambang_km` km lagi, berdasarkan catatan servis terakhir tiap motor.
+pub fn get_motor_perlu_servis(
+    conn: &Connection,
+    ambang_hari: i64,
+    ambang_km: i64,
+) -> Result<Vec<crate::models::MotorPerluServis>> {
+    let batas_tanggal = (chrono::Local::now().date_naive() + chrono::Duration::days(ambang_hari))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let mut stmt = conn.prepare(
+        "SELECT m.motor_id, m.nama, m.plat, m.status, s.tanggal, s.servis_berikut_tanggal, s.servis_berikut_km
+         FROM motor m
+         JOIN servis_motor s ON s.servis_id = (
+             SELECT servis_id FROM servis_motor
+             WHERE motor_id = m.motor_id
+             ORDER BY tanggal DESC, servis_id DESC
+             LIMIT 1
+         )",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(crate::models::MotorPerluServis {
+                motor_id: row.get(0)?,
+                nama: row.get(1)?,
+                plat: row.get(2)?,
+                status: row.get(3)?,
+                servis_terakhir: row.get(4)?,
+                servis_berikut_tanggal: row.get(5)?,
+                servis_berikut_km: row.get(6)?,
+                odometer: None,
+                alasan: String::new(),
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut result = Vec::new();
+    for mut m in rows {
+        m.odometer = get_odometer_motor(conn, m.motor_id)?;
+
+        let mut alasan = Vec::new();
+        if let Some(tgl) = m.servis_berikut_tanggal.as_deref().filter(|t| !t.is_empty()) {
+            if tgl.get(0..10).unwrap_or(tgl) <= batas_tanggal.as_str() {
+                alasan.push(format!("jadwal servis {}", tgl));
+            }
+        }
+        if let (Some(km), Some(odo)) = (m.servis_berikut_km, m.odometer) {
+            if odo + ambang_km >= km {
+                alasan.push(format!("servis di {} km (sekarang {} km)", km, odo));
+            }
+        }
+
+        if !alasan.is_empty() {
+            m.alasan = alasan.join(", ");
+            result.push(m);
+        }
+    }
+    Ok(result)
+}
+
+const KERUSAKAN_COLUMNS: &str =
+    "kerusakan_id, transaksi_id, motor_id, tanggal, deskripsi, foto, estimasi_biaya, biaya_dibebankan";
+
+fn map_kerusakan(conn: &Connection, row: &rusqlite::Row) -> Result<crate::models::Kerusakan> {
+    // Foto disimpan sebagai JSON array berisi path file
+    let foto: Vec<String> = row
+        .get::<_, Option<String>>(5)?
+        .and_then(|f| serde_json::from_str(&f).ok())
+        .unwrap_or_default();
+    Ok(crate::models::Kerusakan {
+        kerusakan_id: row.get(0)?,
+        transaksi_id: row.get(1)?,
+        motor_id: row.get(2)?,
+        tanggal: row.get(3)?,
+        deskripsi: row.get(4)?,
+        foto_thumbnail: foto
+            .iter()
+            .map(|f| crate::lampiran::thumbnail(conn, f))
+            .collect::<Result<_>>()?,
+        foto,
+        estimasi_biaya: row.get(6)?,
+        biaya_dibebankan: row.get(7)?,
+    })
+}
+
+pub fn get_all_kerusakan(
+    conn: &Connection,
+    transaksi_id: Option<i32>,
+    motor_id: Option<i32>,
+) -> Result<Vec<crate::models::Kerusakan>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM kerusakan
+         WHERE (?1 IS NULL OR transaksi_id = ?1) AND (?2 IS NULL OR motor_id = ?2)
+         ORDER BY tanggal DESC, kerusakan_id DESC",
+        KERUSAKAN_COLUMNS
+    ))?;
+    let kerusakan_iter = stmt.query_map((transaksi_id, motor_id), |row| map_kerusakan(conn, row))?;
+
+    let mut result = Vec::new();
+    for k in kerusakan_iter {
+        result.push(k?);
+    }
+    Ok(result)
+}
+
+pub fn get_kerusakan_by_id(conn: &Connection, id: i32) -> Result<crate::models::Kerusakan> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM kerusakan WHERE kerusakan_id = ?1",
+            KERUSAKAN_COLUMNS
+        ),
+        [id],
+        |row| map_kerusakan(conn, row),
+    )
+}
+
+fn foto_json(foto: &[String]) -> String {
+    serde_json::to_string(foto).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn validasi_biaya_kerusakan(data: &crate::models::Kerusakan) -> Result<()> {
+    if data.estimasi_biaya < 0 || data.biaya_dibebankan < 0 {
+        return Err(validation_error("Biaya kerusakan tidak boleh negatif"));
+    }
+    Ok(())
+}
+
+pub fn create_kerusakan(conn: &Connection, data: crate::models::Kerusakan) -> Result<()> {
+    validasi_biaya_kerusakan(&data)?;
+    // Motor selalu diambil dari transaksinya supaya riwayat motor konsisten
+    let motor_id = get_transaksi_by_id(conn, data.transaksi_id)?.motor_id;
+
+    conn.execute(
+        "INSERT INTO kerusakan (transaksi_id, motor_id, tanggal, deskripsi, foto, estimasi_biaya, biaya_dibebankan) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        (data.transaksi_id, motor_id, data.tanggal, data.deskripsi, foto_json(&data.foto), data.estimasi_biaya, data.biaya_dibebankan),
+    )?;
+    Ok(())
+}
+
+pub fn update_kerusakan(
+    conn: &Connection,
+    id: i32,
+    data: crate::models::Kerusakan,
+) -> Result<()> {
+    validasi_biaya_kerusakan(&data)?;
+    let motor_id = get_transaksi_by_id(conn, data.transaksi_id)?.motor_id;
+
+    conn.execute(
+        "UPDATE kerusakan SET transaksi_id = ?1, motor_id = ?2, tanggal = ?3, deskripsi = ?4, foto = ?5, estimasi_biaya = ?6, biaya_dibebankan = ?7 WHERE kerusakan_id = ?8",
+        (data.transaksi_id, motor_id, data.tanggal, data.deskripsi, foto_json(&data.foto), data.estimasi_biaya, data.biaya_dibebankan, id),
+    )?;
+    Ok(())
+}
+
+pub fn delete_kerusakan(conn: &Connection, id: i32) -> Result<()> {
+    conn.execute("DELETE FROM kerusakan WHERE kerusakan_id = ?1", (id,))?;
+    Ok(())
+}
+
+fn map_dokumen_motor(conn: &Connection, row: &rusqlite::Row) -> Result<crate::models::DokumenMotor> {
+    let foto: Option<String> = row.get(5)?;
+    Ok(crate::models::DokumenMotor {
+        dokumen_id: row.get(0)?,
+        motor_id: row.get(1)?,
+        jenis: row.get(2)?,
+        nomor: row.get(3)?,
+        tanggal_kadaluarsa: row.get(4)?,
+        foto_thumbnail: crate::lampiran::thumbnail_opsional(conn, &foto)?,
+        foto,
+    })
+}
+
+pub fn get_all_dokumen_motor(
+    conn: &Connection,
+    motor_id: Option<i32>,
+) -> Result<Vec<crate::models::DokumenMotor>> {
+    let mut stmt = conn.prepare(
+        "SELECT dokumen_id, motor_id, jenis, nomor, tanggal_kadaluarsa, foto FROM dokumen_motor
+         WHERE (?1 IS NULL OR motor_id = ?1)
+         ORDER BY tanggal_kadaluarsa",
+    )?;
+    let dokumen_iter = stmt.query_map([motor_id], |row| map_dokumen_motor(conn, row))?;
+
+    let mut result = Vec::new();
+    for d in dokumen_iter {
+        result.push(d?);
+    }
+    Ok(result)
+}
+
+pub fn get_dokumen_motor_by_id(conn: &Connection, id: i32) -> Result<crate::models::DokumenMotor> {
+    conn.query_row(
+        "SELECT dokumen_id, motor_id, jenis, nomor, tanggal_kadaluarsa, foto FROM dokumen_motor WHERE dokumen_id = ?1",
+        [id],
+        |row| map_dokumen_motor(conn, row),
+    )
+}
+
+// Jenis dokumen motor yang dikenal. STNK dan pajak wajib berlaku agar motor bisa
+// disewakan (jika pengaturan tolak_sewa_dokumen_kadaluarsa aktif).
+pub const JENIS_DOKUMEN_MOTOR: [&str; 4] = ["STNK", "Pajak", "KIR", "Asuransi"];
+const JENIS_DOKUMEN_WAJIB_SEWA: [&str; 2] = ["STNK", "Pajak"];
+
+// Samakan penulisan jenis dengan daftar di atas, tolak jenis yang tidak dikenal
+fn normalisasi_jenis_dokumen(jenis: &str) -> Result<String> {
+    JENIS_DOKUMEN_MOTOR
+        .iter()
+        .find(|j| j.eq_ignore_ascii_case(jenis.trim()))
+        .map(|j| j.to_string())
+        .ok_or_else(|| {
+            validation_error(format!(
+                "Jenis dokumen '{}' tidak dikenal, pilih salah satu: {}",
+                jenis,
+                JENIS_DOKUMEN_MOTOR.join(", ")
+            ))
+        })
+}
+
+pub fn create_dokumen_motor(conn: &Connection, mut data: crate::models::DokumenMotor) -> Result<()> {
+    data.jenis = normalisasi_jenis_dokumen(&data.jenis)?;
+    conn.execute(
+        "INSERT INTO dokumen_motor (motor_id, jenis, nomor, tanggal_kadaluarsa, foto) VALUES (?1, ?2, ?3, ?4, ?5)",
+        (data.motor_id, data.jenis, data.nomor, data.tanggal_kadaluarsa, data.foto),
+    )?;
+    Ok(())
+}
+
+pub fn update_dokumen_motor(
+    conn: &Connection,
+    id: i32,
+    mut data: crate::models::DokumenMotor,
+) -> Result<()> {
+    data.jenis = normalisasi_jenis_dokumen(&data.jenis)?;
+    conn.execute(
+        "UPDATE dokumen_motor SET motor_id = ?1, jenis = ?2, nomor = ?3, tanggal_kadaluarsa = ?4, foto = ?5 WHERE dokumen_id = ?6",
+        (data.motor_id, data.jenis, data.nomor, data.tanggal_kadaluarsa, data.foto, id),
+    )?;
+    Ok(())
+}
+
+pub fn delete_dokumen_motor(conn: &Connection, id: i32) -> Result<()> {
+    conn.execute("DELETE FROM dokumen_motor WHERE dokumen_id = ?1", (id,))?;
+    Ok(())
+}
+
+// Dokumen mulai diperingatkan sekian hari sebelum kadaluarsa
+pub const DOKUMEN_HARI_PERINGATAN_DEFAULT: i64 = 30;
+
+// Dokumen yang sudah atau akan kadaluarsa dalam `hari` hari ke depan.
+// Hanya dokumen terbaru per motor+jenis yang dihitung, supaya STNK lama
+// yang sudah diperpanjang tidak ikut muncul.
+pub fn get_dokumen_motor_akan_kadaluarsa(
+    conn: &Connection,
+    hari: i64,
+    motor_id: Option<i32>,
+) -> Result<Vec<crate::models::DokumenMotorKadaluarsa>> {
+    let hari_ini = chrono::Local::now().date_naive();
+    let batas = (hari_ini + chrono::Duration::days(hari))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let mut stmt = conn.prepare(
+        "SELECT d.dokumen_id, d.motor_id, m.nama, m.plat, d.jenis, d.nomor, d.tanggal_kadaluarsa
+         FROM dokumen_motor d
+         JOIN motor m ON m.motor_id = d.motor_id
+         WHERE (?2 IS NULL OR d.motor_id = ?2)
+           AND substr(d.tanggal_kadaluarsa, 1, 10) <= ?1
+           AND d.tanggal_kadaluarsa = (
+               SELECT MAX(d2.tanggal_kadaluarsa) FROM dokumen_motor d2
+               WHERE d2.motor_id = d.motor_id AND lower(d2.jenis) = lower(d.jenis)
+           )
+         ORDER BY d.tanggal_kadaluarsa",
+    )?;
+    let rows = stmt.query_map((&batas, motor_id), |row| {
+        let tanggal: String = row.get(6)?;
+        let sisa_hari = tanggal
+            .get(0..10)
+            .and_then(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+            .map(|d| (d - hari_ini).num_days())
+            .unwrap_or(0);
+        Ok(crate::models::DokumenMotorKadaluarsa {
+            dokumen_id: row.get(0)?,
+            motor_id: row.get(1)?,
+            motor: row.get(2)?,
+            plat: row.get(3)?,
+            jenis: row.get(4)?,
+            nomor: row.get(5)?,
+            tanggal_kadaluarsa: tanggal,
+            sisa_hari,
+        })
+    })?;
+
+    let mut result = Vec::new();
+    for r in rows {
+        result.push(r?);
+    }
+    Ok(result)
+}
+
+// NIK disimpan tanpa spasi/tanda baca supaya pencocokan tidak meleset
+pub fn normalisasi_no_ktp(no_ktp: &str) -> String {
+    no_ktp.chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+fn map_penyewa_flag(row: &rusqlite::Row) -> Result<crate::models::PenyewaFlag> {
+    Ok(crate::models::PenyewaFlag {
+        flag_id: row.get(0)?,
+        no_ktp: row.get(1)?,
+        status: row.get(2)?,
+        alasan: row.get(3)?,
+        tanggal: row.get(4)?,
+    })
+}
+
+fn validasi_penyewa_flag(data: &crate::models::PenyewaFlag) -> Result<String> {
+    if !matches!(data.status.as_str(), "blacklist" | "waspada") {
+        return Err(validation_error(format!(
+            "Status '{}' tidak dikenal, gunakan blacklist atau waspada",
+            data.status
+        )));
+    }
+    let no_ktp = normalisasi_no_ktp(&data.no_ktp);
+    if no_ktp.is_empty() {
+        return Err(validation_error("No KTP wajib diisi"));
+    }
+    Ok(no_ktp)
+}
+
+pub fn get_all_penyewa_flag(conn: &Connection) -> Result<Vec<crate::models::PenyewaFlag>> {
+    let mut stmt = conn.prepare(
+        "SELECT flag_id, no_ktp, status, alasan, tanggal FROM penyewa_flag ORDER BY tanggal DESC",
+    )?;
+    let flag_iter = stmt.query_map([], map_penyewa_flag)?;
+
+    let mut result = Vec::new();
+    for f in flag_iter {
+        result.push(f?);
+    }
+    Ok(result)
+}
+
+// Satu NIK hanya punya satu tanda; menandai ulang menimpa status dan alasan sebelumnya
+pub fn create_penyewa_flag(conn: &Connection, data: crate::models::PenyewaFlag) -> Result<()> {
+    let no_ktp = validasi_penyewa_flag(&data)?;
+    conn.execute(
+        "INSERT INTO penyewa_flag (no_ktp, status, alasan, tanggal) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(no_ktp) DO UPDATE SET status = excluded.status, alasan = excluded.alasan, tanggal = excluded.tanggal",
+        (no_ktp, data.status, data.alasan, data.tanggal),
+    )?;
+    Ok(())
+}
+
+pub fn update_penyewa_flag(
+    conn: &Connection,
+    id: i32,
+    data: crate::models::PenyewaFlag,
+) -> Result<()> {
+    let no_ktp = validasi_penyewa_flag(&data)?;
+    conn.execute(
+        "UPDATE penyewa_flag SET no_ktp = ?1, status = ?2, alasan = ?3, tanggal = ?4 WHERE flag_id = ?5",
+        (no_ktp, data.status, data.alasan, data.tanggal, id),
+    )?;
+    Ok(())
+}
+
+pub fn delete_penyewa_flag(conn: &Connection, id: i32) -> Result<()> {
+    conn.execute("DELETE FROM penyewa_flag WHERE flag_id = ?1", (id,))?;
+    Ok(())
+}
+
+pub fn get_penyewa_flag_by_ktp(
+    conn: &Connection,
+    no_ktp: &str,
+) -> Result<Option<crate::models::PenyewaFlag>> {
+    let no_ktp = normalisasi_no_ktp(no_ktp);
+    if no_ktp.is_empty() {
+        return Ok(None);
+    }
+    conn.query_row(
+        "SELECT flag_id, no_ktp, status, alasan, tanggal FROM penyewa_flag WHERE no_ktp = ?1",
+        [no_ktp],
+        map_penyewa_flag,
+    )
+    .optional()
+}
+
+// Penyewa berstatus blacklist ditolak; status waspada hanya dicatat di log,
+// peringatannya ditampilkan frontend lewat cek_penyewa_flag
+pub fn validasi_penyewa_bisa_menyewa(conn: &Connection, penyewa_id: i32) -> Result<()> {
+    let penyewa = get_penyewa_by_id(conn, penyewa_id)?;
+    let Some(flag) = get_penyewa_flag_by_ktp(conn, &penyewa.no_ktp)? else {
+        return Ok(());
+    };
+
+    if flag.status == "blacklist" {
+        return Err(validation_error(format!(
+            "Penyewa {} (NIK {}) masuk blacklist sejak {}: {}",
+            penyewa.nama, penyewa.no_ktp, flag.tanggal, flag.alasan
+        )));
+    }
+
+    println!(
+        "⚠️ Penyewa {} berstatus {}: {}",
+        penyewa.nama, flag.status, flag.alasan
+    );
+    Ok(())
+}
+
+const DOKUMEN_PENYEWA_COLUMNS: &str = "dokumen_id, penyewa_id, jenis, path, created_at";
+
+fn map_dokumen_penyewa(conn: &Connection, row: &rusqlite::Row) -> Result<crate::models::DokumenPenyewa> {
+    let path: String = row.get(3)?;
+    Ok(crate::models::DokumenPenyewa {
+        dokumen_id: row.get(0)?,
+        penyewa_id: row.get(1)?,
+        jenis: row.get(2)?,
+        foto_thumbnail: crate::lampiran::thumbnail(conn, &path)?,
+        path,
+        created_at: row.get(4)?,
+    })
+}
+
+pub fn get_dokumen_penyewa(
+    conn: &Connection,
+    penyewa_id: i32,
+) -> Result<Vec<crate::models::DokumenPenyewa>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM dokumen_penyewa WHERE penyewa_id = ?1 ORDER BY jenis, created_at",
+        DOKUMEN_PENYEWA_COLUMNS
+    ))?;
+    let dokumen_iter = stmt.query_map([penyewa_id], |row| map_dokumen_penyewa(conn, row))?;
+
+    let mut result = Vec::new();
+    for d in dokumen_iter {
+        result.push(d?);
+    }
+    Ok(result)
+}
+
+pub fn get_dokumen_penyewa_by_id(
+    conn: &Connection,
+    id: i32,
+) -> Result<crate::models::DokumenPenyewa> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM dokumen_penyewa WHERE dokumen_id = ?1",
+            DOKUMEN_PENYEWA_COLUMNS
+        ),
+        [id],
+        |row| map_dokumen_penyewa(conn, row),
+    )
+}
+
+pub fn create_dokumen_penyewa(
+    conn: &Connection,
+    penyewa_id: i32,
+    jenis: &str,
+    path: &str,
+) -> Result<crate::models::DokumenPenyewa> {
+    if !matches!(jenis, "ktp" | "sim" | "selfie") {
+        return Err(validation_error(format!(
+            "Jenis dokumen '{}' tidak dikenal, gunakan ktp, sim atau selfie",
+            jenis
+        )));
+    }
+    // Pastikan penyewanya ada
+    get_penyewa_by_id(conn, penyewa_id)?;
+
+    let created_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    conn.execute(
+        "INSERT INTO dokumen_penyewa (penyewa_id, jenis, path, created_at) VALUES (?1, ?2, ?3, ?4)",
+        (penyewa_id, jenis, path, &created_at),
+    )?;
+    get_dokumen_penyewa_by_id(conn, conn.last_insert_rowid() as i32)
+}
+
+pub fn delete_dokumen_penyewa(conn: &Connection, id: i32) -> Result<()> {
+    conn.execute("DELETE FROM dokumen_penyewa WHERE dokumen_id = ?1", (id,))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn db_uji() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::migrations::migrate(&conn).unwrap();
+        conn
+    }
+
+    fn motor_uji(conn: &Connection, status: &str) -> i32 {
+        create_motor(
+            conn,
+            crate::models::Motor {
+                motor_id: 0,
+                nama: "Vario".into(),
+                plat: "H 1234 AB".into(),
+                tipe_motor: "Matic".into(),
+                tahun: "2022".into(),
+                harga_harian: 75000,
+                foto: String::new(),
+                foto_thumbnail: None,
+                status: status.into(),
+                odometer: Some(1000),
+            },
+        )
+        .unwrap();
+        conn.last_insert_rowid() as i32
+    }
+
+    fn penyewa_uji(conn: &Connection) -> i32 {
+        create_penyewa(
+            conn,
+            crate::models::Penyewa {
+                penyewa_id: 0,
+                nama: "Budi".into(),
+                no_hp: "08123".into(),
+                no_ktp: "3374000000000001".into(),
+                alamat: "Semarang".into(),
+            },
+        )
+        .unwrap();
+        conn.last_insert_rowid() as i32
+    }
+
+    fn transaksi_uji(motor_id: i32, penyewa_id: i32, km_awal: Option<i64>) -> crate::models::Transaksi {
+        crate::models::Transaksi {
+            transaksi_id: 0,
+            motor_id,
+            penyewa_id,
+            tanggal_sewa: "2026-01-01".into(),
+            tanggal_kembali_rencana: "2026-01-03".into(),
+            tanggal_kembali_aktual: None,
+            hari_terlambat: None,
+            total_bayar: Some(150000),
+            status: "dipinjam".into(),
+            denda: Some(20000),
+            diskon: None,
+            foto_bukti: None,
+            foto_bukti_thumbnail: None,
+            nomor: None,
+            km_awal,
+            km_akhir: None,
+            biaya_km_lebih: None,
+            bbm_awal: None,
+            bbm_akhir: None,
+            biaya_bbm: None,
+        }
+    }
+
+    #[test]
+    fn km_awal_tidak_boleh_di_bawah_odometer() {
+        let conn = db_uji();
+        let motor_id = motor_uji(&conn, "tersedia");
+        let penyewa_id = penyewa_uji(&conn);
+
+        assert!(create_transaksi(&conn, transaksi_uji(motor_id, penyewa_id, Some(900))).is_err());
+        create_transaksi(&conn, transaksi_uji(motor_id, penyewa_id, Some(1000))).unwrap();
+    }
+
+    #[test]
+    fn tagihan_menjumlahkan_semua_biaya_dan_pembayaran() {
+        let conn = db_uji();
+        let motor_id = motor_uji(&conn, "tersedia");
+        let penyewa_id = penyewa_uji(&conn);
+        create_transaksi(&conn, transaksi_uji(motor_id, penyewa_id, None)).unwrap();
+        let id = conn.last_insert_rowid() as i32;
+
+        conn.execute(
+            "INSERT INTO kerusakan (transaksi_id, motor_id, tanggal, deskripsi, foto, estimasi_biaya, biaya_dibebankan) VALUES (?1, ?2, '2026-01-03', 'spion', '[]', 50000, 30000)",
+            (id, motor_id),
+        )
+        .unwrap();
+        for bayar in [100000, 40000] {
+            conn.execute(
+                "INSERT INTO bukti_pelunasan (transaksi_id, tanggal_bayar, jumlah_bayar, metode_bayar, foto_bukti) VALUES (?1, '2026-01-03', ?2, 'tunai', '')",
+                (id, bayar),
+            )
+            .unwrap();
+        }
+
+        let tagihan = get_tagihan_transaksi(&conn, id).unwrap();
+        assert_eq!(tagihan.total_tagihan, 150000 + 20000 + 30000);
+        assert_eq!(tagihan.dibayar, 140000);
+        assert_eq!(tagihan.sisa_bayar, 60000);
+
+        let semua = get_all_tagihan(&conn).unwrap();
+        assert_eq!(semua.len(), 1);
+        assert_eq!(semua[0].sisa_bayar, 60000);
+    }
+
+    #[test]
+    fn biaya_kerusakan_negatif_ditolak() {
+        let conn = db_uji();
+        let motor_id = motor_uji(&conn, "tersedia");
+        let penyewa_id = penyewa_uji(&conn);
+        create_transaksi(&conn, transaksi_uji(motor_id, penyewa_id, None)).unwrap();
+        let kerusakan = |estimasi_biaya, biaya_dibebankan| crate::models::Kerusakan {
+            kerusakan_id: 0,
+            transaksi_id: conn.last_insert_rowid() as i32,
+            motor_id: 0,
+            tanggal: "2026-01-03".into(),
+            deskripsi: "lecet".into(),
+            foto: Vec::new(),
+            foto_thumbnail: Vec::new(),
+            estimasi_biaya,
+            biaya_dibebankan,
+        };
+
+        assert!(create_kerusakan(&conn, kerusakan(-1, 0)).is_err());
+        assert!(create_kerusakan(&conn, kerusakan(0, -5000)).is_err());
+        create_kerusakan(&conn, kerusakan(50000, 25000)).unwrap();
+    }
+
+    #[test]
+    fn jenis_dokumen_dinormalisasi() {
+        assert_eq!(normalisasi_jenis_dokumen(" stnk ").unwrap(), "STNK");
+        assert_eq!(normalisasi_jenis_dokumen("PAJAK").unwrap(), "Pajak");
+        assert!(normalisasi_jenis_dokumen("STNK baru").is_err());
+    }
+
+    #[test]
+    fn motor_di_bengkel_tidak_bisa_disewa() {
+        let conn = db_uji();
+        for status in STATUS_MOTOR_TIDAK_BISA_DISEWA {
+            let id = motor_uji(&conn, status);
+            assert!(validasi_motor_bisa_disewa(&conn, id).is_err(), "{}", status);
+        }
+        let id = motor_uji(&conn, "tersedia");
+        assert!(validasi_motor_bisa_disewa(&conn, id).is_ok());
+    }
+
+    #[test]
+    fn transaksi_lewat_tanggal_kembali_ditandai_terlambat() {
+        let conn = db_uji();
+        let motor_id = motor_uji(&conn, "tersedia");
+        let penyewa_id = penyewa_uji(&conn);
+        // Rencana kembali 2026-01-03, denda awal 20000
+        create_transaksi(&conn, transaksi_uji(motor_id, penyewa_id, None)).unwrap();
+        let id = conn.last_insert_rowid() as i32;
+        let tanggal = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let status_denda = || -> (String, i64, i64) {
+            conn.query_row(
+                "SELECT status, COALESCE(hari_terlambat, 0), denda FROM transaksi WHERE transaksi_id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap()
+        };
+
+        assert!(tandai_transaksi_terlambat(&conn, tanggal("2026-01-03"))
+            .unwrap()
+            .is_empty());
+        assert_eq!(status_denda().0, "dipinjam");
+
+        // denda_per_hari belum diatur: status berubah, denda lama tidak direset ke 0
+        let list = tandai_transaksi_terlambat(&conn, tanggal("2026-01-05")).unwrap();
+        assert_eq!(list.len(), 1);
+        assert!(list[0].baru_terlambat);
+        assert_eq!(list[0].hari_terlambat, 2);
+        assert_eq!(list[0].denda, 20000);
+        assert_eq!(status_denda(), ("terlambat".to_string(), 2, 20000));
+
+        // Denda naik mengikuti hari terlambat, peringatan "baru" hanya sekali
+        conn.execute(
+            "INSERT OR REPLACE INTO pengaturan (key, value) VALUES ('denda_per_hari', '15000')",
+            [],
+        )
+        .unwrap();
+        let list = tandai_transaksi_terlambat(&conn, tanggal("2026-01-06")).unwrap();
+        assert!(!list[0].baru_terlambat);
+        assert_eq!(status_denda(), ("terlambat".to_string(), 3, 45000));
+
+        // Denda yang dinaikkan manual tidak diturunkan
+        conn.execute("UPDATE transaksi SET denda = 100000 WHERE transaksi_id = ?1", [id])
+            .unwrap();
+        let list = tandai_transaksi_terlambat(&conn, tanggal("2026-01-07")).unwrap();
+        assert_eq!(list[0].denda, 100000);
+        assert_eq!(status_denda(), ("terlambat".to_string(), 4, 100000));
+
+        // Motor yang sudah kembali tidak diperiksa lagi
+        conn.execute(
+            "UPDATE transaksi SET tanggal_kembali_aktual = '2026-01-07' WHERE transaksi_id = ?1",
+            [id],
+        )
+        .unwrap();
+        assert!(tandai_transaksi_terlambat(&conn, tanggal("2026-01-10"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn tutup_pool_baca_menunggu_koneksi_dipinjam() {
+        let pool = r2d2::Pool::builder()
+            .max_size(1)
+            .build(SqliteConnectionManager::memory())
+            .unwrap();
+        *POOL_BACA.lock() = Some(pool);
+
+        let (dipinjam_tx, dipinjam_rx) = mpsc::channel();
+        let (lepas_tx, lepas_rx) = mpsc::channel::<()>();
+        let pembaca = std::thread::spawn(move || {
+            with_reader(|_| {
+                dipinjam_tx.send(()).unwrap();
+                lepas_rx.recv().unwrap();
+                Ok(())
+            })
+        });
+        dipinjam_rx.recv().unwrap();
+
+        let (tutup_tx, tutup_rx) = mpsc::channel();
+        let penutup = std::thread::spawn(move || {
+            let _gerbang = tutup_pool_baca();
+            tutup_tx.send(()).unwrap();
+        });
+        assert!(tutup_rx.recv_timeout(Duration::from_millis(200)).is_err());
+
+        lepas_tx.send(()).unwrap();
+        tutup_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        pembaca.join().unwrap().unwrap();
+        penutup.join().unwrap();
+        assert!(POOL_BACA.lock().is_none());
+    }
+
+    #[test]
+    fn salin_database_lewat_memori() {
+        let path = std::env::temp_dir().join(format!("memori-{}.sqlite", std::process::id()));
+        let conn = buka(&path, None).unwrap();
+        crate::migrations::migrate(&conn).unwrap();
+        motor_uji(&conn, "tersedia");
+
+        let isi = salin_ke_memori(&conn).unwrap();
+        drop(conn);
+        for akhiran in ["sqlite", "sqlite-wal", "sqlite-shm"] {
+            std::fs::remove_file(path.with_extension(akhiran)).ok();
+        }
+
+        // Salinan dari database WAL tetap bisa dibaca dan diubah di memori
+        let salinan = buka_dari_memori(&isi).unwrap();
+        let versi: i32 = salinan
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(versi, crate::migrations::SCHEMA_VERSION);
+        assert_eq!(get_all_motor(&salinan).unwrap().len(), 1);
+        motor_uji(&salinan, "tersedia");
+        assert_eq!(get_all_motor(&salinan).unwrap().len(), 2);
+
+        assert!(buka_dari_memori(b"bukan database")
+            .and_then(|c| c.query_row("SELECT COUNT(*) FROM sqlite_master", [], |_| Ok(())))
+            .is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::stream::{NewStream, StreamBE32, StreamPrimitive};
+use aes_gcm::aead::{KeyInit, OsRng};
+use aes_gcm::Aes256Gcm;
+use argon2::Argon2;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+// Format file terenkripsi:
+//   MAGIC (8) | salt Argon2id (16) | nonce awal STREAM (7) | potongan ciphertext ...
+// Isi dienkripsi per potongan (AES-256-GCM, konstruksi STREAM) supaya arsip besar
+// tidak perlu dimuat utuh ke memori; potongan terakhir ditandai sehingga file yang
+// terpotong ikut terdeteksi.
+pub const MAGIC: &[u8; 8] = b"RMBAK\x00\x01\x00";
+const PANJANG_SALT: usize = 16;
+const PANJANG_NONCE: usize = 7;
+const PANJANG_HEADER: usize = MAGIC.len() + PANJANG_SALT + PANJANG_NONCE;
+const UKURAN_POTONGAN: usize = 64 * 1024;
+const PANJANG_TAG: usize = 16;
+const POTONGAN_CIPHERTEXT: usize = UKURAN_POTONGAN + PANJANG_TAG;
+
+const PANJANG_SANDI_MIN: usize = 8;
+
+const SANDI_SALAH: &str = "Kata sandi salah atau file backup rusak";
+
+fn turunkan_kunci(sandi: &str, salt: &[u8]) -> Result<Aes256Gcm, String> {
+    let mut kunci = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(sandi.as_bytes(), salt, &mut kunci)
+        .map_err(|e| format!("Gagal menurunkan kunci: {}", e))?;
+    Aes256Gcm::new_from_slice(&kunci).map_err(|e| e.to_string())
+}
+
+// Untuk kata sandi baru yang diisi user. Kata sandi database yang sudah ada tidak dicek
+// ulang saat backup: database lama boleh memakai kata sandi yang lebih pendek.
+pub fn validasi_sandi(sandi: &str) -> Result<(), String> {
+    if sandi.chars().count() < PANJANG_SANDI_MIN {
+        return Err(format!(
+            "Kata sandi backup minimal {} karakter",
+            PANJANG_SANDI_MIN
+        ));
+    }
+    Ok(())
+}
+
+pub fn terenkripsi(path: &Path) -> bool {
+    let mut header = [0u8; 8];
+    File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .map(|_| &header == MAGIC)
+        .unwrap_or(false)
+}
+
+fn rusak() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, SANDI_SALAH)
+}
+
+// Writer yang mengenkripsi semua yang ditulis ke dalamnya langsung ke tujuan, jadi isi
+// polos tidak pernah menyentuh disk. Potongan baru dienkripsi saat potongan berikutnya
+// mulai ditulis; potongan terakhir ditandai di selesai(), yang wajib dipanggil.
+pub struct PenulisTerenkripsi<W: Write> {
+    tujuan: W,
+    stream: StreamBE32<Aes256Gcm>,
+    posisi: u32,
+    potongan: Vec<u8>,
+}
+
+impl<W: Write> PenulisTerenkripsi<W> {
+    pub fn new(mut tujuan: W, sandi: &str) -> Result<Self, String> {
+        let mut salt = [0u8; PANJANG_SALT];
+        let mut nonce = [0u8; PANJANG_NONCE];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+        let stream = StreamBE32::from_aead(turunkan_kunci(sandi, &salt)?, &nonce.into());
+
+        [&MAGIC[..], &salt, &nonce]
+            .iter()
+            .try_for_each(|bagian| tujuan.write_all(bagian))
+            .map_err(|e| format!("Gagal menulis file backup: {}", e))?;
+        Ok(Self {
+            tujuan,
+            stream,
+            posisi: 0,
+            potongan: Vec::with_capacity(POTONGAN_CIPHERTEXT),
+        })
+    }
+
+    fn tulis_potongan(&mut self, terakhir: bool) -> io::Result<()> {
+        self.stream
+            .encrypt_in_place(self.posisi, terakhir, b"", &mut self.potongan)
+            .map_err(|_| io::Error::other("Gagal mengenkripsi backup"))?;
+        self.tujuan.write_all(&self.potongan)?;
+        self.potongan.clear();
+        self.posisi = self
+            .posisi
+            .checked_add(1)
+            .ok_or_else(|| io::Error::other("File backup terlalu besar"))?;
+        Ok(())
+    }
+
+    // Tulis potongan terakhir dan kembalikan writer tujuan
+    pub fn selesai(mut self) -> io::Result<W> {
+        self.tulis_potongan(true)?;
+        self.tujuan.flush()?;
+        Ok(self.tujuan)
+    }
+}
+
+impl<W: Write> Write for PenulisTerenkripsi<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+        // Potongan penuh baru dienkripsi di sini, karena baru sekarang pasti bukan yang terakhir
+        if self.potongan.len() == UKURAN_POTONGAN {
+            self.tulis_potongan(false)?;
+        }
+        let n = data.len().min(UKURAN_POTONGAN - self.potongan.len());
+        self.potongan.extend_from_slice(&data[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.tujuan.flush()
+    }
+}
+
+// Reader (dengan seek) atas file terenkripsi, sehingga arsip .zip di dalamnya bisa
+// dibaca langsung tanpa didekripsi dulu ke disk. Setiap potongan didekripsi dan
+// diverifikasi saat dibaca; potongan terakhir sudah dicek di new() sehingga kata
+// sandi salah dan file terpotong langsung ketahuan.
+pub struct PembacaTerenkripsi<R: Read + Seek> {
+    sumber: R,
+    stream: StreamBE32<Aes256Gcm>,
+    jumlah_potongan: u64,
+    panjang: u64,
+    posisi: u64,
+    // Potongan yang terakhir didekripsi: (indeks, isi polos)
+    cache: Option<(u64, Vec<u8>)>,
+}
+
+impl<R: Read + Seek> PembacaTerenkripsi<R> {
+    pub fn new(mut sumber: R, sandi: &str) -> Result<Self, String> {
+        let tidak_lengkap = || "File backup terenkripsi tidak lengkap".to_string();
+        let mut header = [0u8; PANJANG_HEADER];
+        sumber
+            .read_exact(&mut header)
+            .map_err(|_| tidak_lengkap())?;
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err("File bukan backup terenkripsi".to_string());
+        }
+        let salt = &header[MAGIC.len()..MAGIC.len() + PANJANG_SALT];
+        let nonce: [u8; PANJANG_NONCE] = header[MAGIC.len() + PANJANG_SALT..]
+            .try_into()
+            .map_err(|_| "Header backup tidak valid".to_string())?;
+        let stream = StreamBE32::from_aead(turunkan_kunci(sandi, salt)?, &nonce.into());
+
+        let ukuran = sumber
+            .seek(SeekFrom::End(0))
+            .map_err(|e| format!("Gagal membaca file: {}", e))?;
+        // Selalu ada minimal satu potongan (file kosong = satu tag saja)
+        let isi = ukuran - PANJANG_HEADER as u64;
+        let potongan = POTONGAN_CIPHERTEXT as u64;
+        let jumlah_potongan = isi.div_ceil(potongan).max(1);
+        let sisa = isi - (jumlah_potongan - 1) * potongan;
+        if sisa < PANJANG_TAG as u64 || jumlah_potongan > u32::MAX as u64 + 1 {
+            return Err(tidak_lengkap());
+        }
+
+        let mut pembaca = Self {
+            sumber,
+            stream,
+            jumlah_potongan,
+            panjang: isi - jumlah_potongan * PANJANG_TAG as u64,
+            posisi: 0,
+            cache: None,
+        };
+        pembaca
+            .muat_potongan(jumlah_potongan - 1)
+            .map_err(|_| SANDI_SALAH.to_string())?;
+        Ok(pembaca)
+    }
+
+    fn muat_potongan(&mut self, indeks: u64) -> io::Result<&[u8]> {
+        if self.cache.as_ref().map(|(i, _)| *i) != Some(indeks) {
+            let terakhir = indeks + 1 == self.jumlah_potongan;
+            let awal = indeks * POTONGAN_CIPHERTEXT as u64;
+            let panjang = if terakhir {
+                self.panjang - indeks * UKURAN_POTONGAN as u64 + PANJANG_TAG as u64
+            } else {
+                POTONGAN_CIPHERTEXT as u64
+            };
+
+            let mut isi = self.cache.take().map(|(_, isi)| isi).unwrap_or_default();
+            isi.resize(panjang as usize, 0);
+            self.sumber
+                .seek(SeekFrom::Start(PANJANG_HEADER as u64 + awal))?;
+            self.sumber.read_exact(&mut isi).map_err(|_| rusak())?;
+            self.stream
+                .decrypt_in_place(indeks as u32, terakhir, b"", &mut isi)
+                .map_err(|_| rusak())?;
+            self.cache = Some((indeks, isi));
+        }
+        Ok(self
+            .cache
+            .as_ref()
+            .map(|(_, isi)| isi.as_slice())
+            .unwrap_or_default())
+    }
+}
+
+impl<R: Read + Seek> Read for PembacaTerenkripsi<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.posisi >= self.panjang || buf.is_empty() {
+            return Ok(0);
+        }
+        let indeks = self.posisi / UKURAN_POTONGAN as u64;
+        let offset = (self.posisi % UKURAN_POTONGAN as u64) as usize;
+        let potongan = self.muat_potongan(indeks)?;
+        let n = buf.len().min(potongan.len() - offset);
+        buf[..n].copy_from_slice(&potongan[offset..offset + n]);
+        self.posisi += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Read + Seek> Seek for PembacaTerenkripsi<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let baru = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(n) => self.panjang.checked_add_signed(n),
+            SeekFrom::Current(n) => self.posisi.checked_add_signed(n),
+        };
+        self.posisi = baru.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Posisi baca tidak valid")
+        })?;
+        Ok(self.posisi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes_gcm::aead::stream::EncryptorBE32;
+    use std::io::Cursor;
+
+    const SANDI: &str = "rahasia-backup";
+
+    fn enkripsi(isi: &[u8]) -> Vec<u8> {
+        let mut penulis = PenulisTerenkripsi::new(Vec::new(), SANDI).unwrap();
+        // Ditulis sepotong-sepotong seperti oleh ZipWriter
+        for bagian in isi.chunks(1000) {
+            penulis.write_all(bagian).unwrap();
+        }
+        penulis.selesai().unwrap()
+    }
+
+    fn dekripsi(data: Vec<u8>, sandi: &str) -> Result<Vec<u8>, String> {
+        let mut pembaca = PembacaTerenkripsi::new(Cursor::new(data), sandi)?;
+        let mut hasil = Vec::new();
+        pembaca.read_to_end(&mut hasil).map_err(|e| e.to_string())?;
+        Ok(hasil)
+    }
+
+    fn isi_uji(panjang: usize) -> Vec<u8> {
+        (0..panjang).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn file_kosong() {
+        let data = enkripsi(&[]);
+        assert!(data.starts_with(MAGIC));
+        assert_eq!(data.len(), PANJANG_HEADER + PANJANG_TAG);
+        assert!(dekripsi(data, SANDI).unwrap().is_empty());
+    }
+
+    #[test]
+    fn tepat_kelipatan_potongan() {
+        for panjang in [UKURAN_POTONGAN * 2, UKURAN_POTONGAN * 2 + 1] {
+            let isi = isi_uji(panjang);
+            let data = enkripsi(&isi);
+            let potongan = panjang.div_ceil(UKURAN_POTONGAN);
+            assert_eq!(
+                data.len(),
+                PANJANG_HEADER + panjang + potongan * PANJANG_TAG
+            );
+            assert_eq!(dekripsi(data, SANDI).unwrap(), isi);
+        }
+    }
+
+    #[test]
+    fn baca_acak_dengan_seek() {
+        let isi = isi_uji(UKURAN_POTONGAN * 3 + 500);
+        let mut pembaca = PembacaTerenkripsi::new(Cursor::new(enkripsi(&isi)), SANDI).unwrap();
+
+        // Melewati batas potongan, mundur, lalu dari akhir file
+        let mut buf = vec![0u8; 300];
+        for (pos, awal) in [
+            (
+                SeekFrom::Start(UKURAN_POTONGAN as u64 - 100),
+                UKURAN_POTONGAN - 100,
+            ),
+            (SeekFrom::Start(10), 10),
+            (SeekFrom::End(-300), isi.len() - 300),
+        ] {
+            assert_eq!(pembaca.seek(pos).unwrap(), awal as u64);
+            pembaca.read_exact(&mut buf).unwrap();
+            assert_eq!(buf, isi[awal..awal + 300]);
+        }
+        assert_eq!(pembaca.read(&mut buf).unwrap(), 0);
+    }
+
+    // Backup yang dibuat versi sebelumnya (EncryptorBE32 per potongan) tetap bisa dibaca
+    #[test]
+    fn kompatibel_dengan_format_lama() {
+        let isi = isi_uji(UKURAN_POTONGAN + 123);
+        let salt = [7u8; PANJANG_SALT];
+        let nonce = [9u8; PANJANG_NONCE];
+        let mut encryptor =
+            EncryptorBE32::from_aead(turunkan_kunci(SANDI, &salt).unwrap(), &nonce.into());
+        let mut data = [&MAGIC[..], &salt, &nonce].concat();
+        data.extend(encryptor.encrypt_next(&isi[..UKURAN_POTONGAN]).unwrap());
+        data.extend(encryptor.encrypt_last(&isi[UKURAN_POTONGAN..]).unwrap());
+
+        assert_eq!(dekripsi(data, SANDI).unwrap(), isi);
+    }
+
+    #[test]
+    fn file_terpotong_ditolak() {
+        let lengkap = enkripsi(&isi_uji(UKURAN_POTONGAN * 2 + 100));
+
+        // Potong di batas potongan (potongan terakhir hilang utuh) dan di tengah potongan
+        for panjang in [
+            PANJANG_HEADER + POTONGAN_CIPHERTEXT,
+            lengkap.len() - 10,
+            PANJANG_HEADER + 5,
+            PANJANG_HEADER - 1,
+        ] {
+            assert!(dekripsi(lengkap[..panjang].to_vec(), SANDI).is_err());
+        }
+    }
+
+    #[test]
+    fn sandi_salah_ditolak() {
+        let data = enkripsi(b"data rental");
+        assert_eq!(dekripsi(data, "sandi-lain-123").unwrap_err(), SANDI_SALAH);
+        assert!(validasi_sandi("pendek").is_err());
+        assert!(validasi_sandi("rahasia123").is_ok());
+    }
+}
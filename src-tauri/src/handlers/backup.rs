@@ -0,0 +1,76 @@
+use crate::backup;
+use crate::db;
+use crate::models::{BackupHistory, InfoRestore};
+
+// Jumlah riwayat yang ditampilkan di halaman Pengaturan jika tidak ditentukan
+const LIMIT_RIWAYAT_DEFAULT: i64 = 20;
+
+#[tauri::command]
+pub async fn get_backup_history(limit: Option<i64>) -> Result<Vec<BackupHistory>, String> {
+    println!("🔍 Getting Backup History...");
+
+    let limit = limit.unwrap_or(LIMIT_RIWAYAT_DEFAULT);
+    db::baca(move |conn| backup::get_backup_history(conn, limit))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_backup_history: {}", e);
+            e.to_string()
+        })
+}
+
+// Jalankan backup ke folder backup otomatis sekarang (ikut dirotasi)
+#[tauri::command]
+pub async fn jalankan_backup_sekarang() -> Result<BackupHistory, String> {
+    println!("💾 Running Backup...");
+
+    db::blocking(|| backup::backup_terjadwal(backup::PEMICU_MANUAL))
+        .await
+        .and_then(|riwayat| {
+            riwayat.ok_or_else(|| "Folder backup otomatis belum diatur".to_string())
+        })
+        .map_err(|e| {
+            eprintln!("❌ Error jalankan_backup_sekarang: {}", e);
+            e
+        })
+}
+
+// Kata sandi backup otomatis; kosong/None berarti backup otomatis tidak dienkripsi
+// (kecuali database terenkripsi)
+#[tauri::command]
+pub async fn atur_sandi_backup_otomatis(kata_sandi: Option<String>) -> Result<(), String> {
+    println!("🔒 Setting Backup Password...");
+
+    db::blocking(move || backup::atur_sandi_terjadwal(kata_sandi.as_deref()))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error atur_sandi_backup_otomatis: {}", e);
+            e
+        })
+}
+
+// Hanya memberi tahu apakah kata sandi sudah diatur, isinya tidak dikirim ke frontend
+#[tauri::command]
+pub async fn cek_sandi_backup_otomatis() -> Result<bool, String> {
+    db::baca(|conn| backup::baca_jadwal(conn).map(|jadwal| jadwal.sandi.is_some()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_info_restore_terakhir() -> Result<Option<InfoRestore>, String> {
+    println!("🔍 Getting Info Restore...");
+
+    Ok(backup::info_restore_terakhir())
+}
+
+#[tauri::command]
+pub async fn batalkan_restore_terakhir() -> Result<InfoRestore, String> {
+    println!("↩️ Undoing last restore...");
+
+    db::blocking(backup::batalkan_restore_terakhir)
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error batalkan_restore_terakhir: {}", e);
+            e
+        })
+}
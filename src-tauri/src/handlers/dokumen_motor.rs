@@ -0,0 +1,82 @@
+use crate::db;
+use crate::models::{DokumenMotor, DokumenMotorKadaluarsa};
+
+#[tauri::command]
+pub async fn get_all_dokumen_motor(motor_id: Option<i32>) -> Result<Vec<DokumenMotor>, String> {
+    println!("🔍 Getting Dokumen Motor...");
+
+    db::baca(move |conn| db::get_all_dokumen_motor(conn, motor_id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_all_dokumen_motor: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn create_dokumen_motor(data: DokumenMotor) -> Result<(), String> {
+    println!("💾 Creating Dokumen Motor...");
+
+    db::tulis(move |conn| db::create_dokumen_motor(conn, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error create_dokumen_motor: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn get_dokumen_motor_by_id(id: i32) -> Result<DokumenMotor, String> {
+    println!("🔍 Getting Dokumen Motor by ID: {}", id);
+
+    db::baca(move |conn| db::get_dokumen_motor_by_id(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_dokumen_motor_by_id: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn update_dokumen_motor(id: i32, data: DokumenMotor) -> Result<(), String> {
+    println!("💾 Updating Dokumen Motor ID: {}", id);
+
+    db::tulis(move |conn| db::update_dokumen_motor(conn, id, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error update_dokumen_motor: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn delete_dokumen_motor(id: i32) -> Result<(), String> {
+    println!("🗑️ Deleting Dokumen Motor ID: {}", id);
+
+    db::tulis(move |conn| db::delete_dokumen_motor(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error delete_dokumen_motor: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn get_dokumen_motor_akan_kadaluarsa(
+    hari: Option<i64>,
+) -> Result<Vec<DokumenMotorKadaluarsa>, String> {
+    println!("🔍 Getting Dokumen Motor akan kadaluarsa...");
+
+    db::baca(move |conn| {
+        db::get_dokumen_motor_akan_kadaluarsa(
+            conn,
+            hari.unwrap_or(db::DOKUMEN_HARI_PERINGATAN_DEFAULT),
+            None,
+        )
+    })
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error get_dokumen_motor_akan_kadaluarsa: {}", e);
+        e.to_string()
+    })
+}
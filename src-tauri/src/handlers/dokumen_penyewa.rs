@@ -0,0 +1,43 @@
+use crate::db;
+use crate::models::DokumenPenyewa;
+
+#[tauri::command]
+pub async fn get_dokumen_penyewa(penyewa_id: i32) -> Result<Vec<DokumenPenyewa>, String> {
+    println!("🔍 Getting Dokumen Penyewa ID: {}", penyewa_id);
+
+    db::baca(move |conn| db::get_dokumen_penyewa(conn, penyewa_id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_dokumen_penyewa: {}", e);
+            e.to_string()
+        })
+}
+
+// `path` adalah hasil unggah_lampiran / impor_lampiran
+#[tauri::command]
+pub async fn save_dokumen_penyewa(
+    penyewa_id: i32,
+    jenis: String,
+    path: String,
+) -> Result<DokumenPenyewa, String> {
+    println!("💾 Saving Dokumen Penyewa {} ID: {}", jenis, penyewa_id);
+
+    db::tulis(move |conn| db::create_dokumen_penyewa(conn, penyewa_id, &jenis, &path))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error save_dokumen_penyewa: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn delete_dokumen_penyewa(id: i32) -> Result<(), String> {
+    println!("🗑️ Deleting Dokumen Penyewa ID: {}", id);
+
+    db::tulis(move |conn| db::delete_dokumen_penyewa(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error delete_dokumen_penyewa: {}", e);
+            e.to_string()
+        })
+}
@@ -0,0 +1,473 @@
+use crate::db;
+use crate::models::{ImporBaris, ImporLaporan, Motor, Penyewa};
+use calamine::{open_workbook_auto, Data, Reader};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+// Kolom yang dikenali untuk tiap jenis data yang bisa diimpor
+const KOLOM_MOTOR: [&str; 6] = [
+    "nama",
+    "plat",
+    "tipe_motor",
+    "tahun",
+    "harga_harian",
+    "odometer",
+];
+const KOLOM_PENYEWA: [&str; 4] = ["nama", "no_hp", "no_ktp", "alamat"];
+
+enum DataImpor {
+    Motor(Motor),
+    Penyewa(Penyewa),
+}
+
+// Baca file CSV/XLSX menjadi header + baris-baris teks
+fn baca_file(path: &Path) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let mut rows: Vec<Vec<String>> = match ext.as_str() {
+        "csv" | "txt" => baca_csv(path)?,
+        "xlsx" | "xlsm" | "xls" | "ods" => baca_excel(path)?,
+        _ => return Err(format!("Format file .{} tidak didukung", ext)),
+    };
+
+    // Buang baris yang kosong seluruhnya
+    rows.retain(|r| r.iter().any(|c| !c.trim().is_empty()));
+
+    if rows.is_empty() {
+        return Err("File tidak berisi data".to_string());
+    }
+
+    let header = rows.remove(0);
+    Ok((header, rows))
+}
+
+fn baca_csv(path: &Path) -> Result<Vec<Vec<String>>, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Gagal membaca file: {}", e))?;
+    let content = content.trim_start_matches('\u{feff}');
+
+    // Excel berbahasa Indonesia biasanya menyimpan CSV dengan pemisah ';'
+    let first_line = content.lines().next().unwrap_or_default();
+    let delimiter = if first_line.matches(';').count() > first_line.matches(',').count() {
+        b';'
+    } else {
+        b','
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(content.as_bytes());
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("CSV tidak valid: {}", e))?;
+        rows.push(record.iter().map(|c| c.trim().to_string()).collect());
+    }
+    Ok(rows)
+}
+
+fn baca_excel(path: &Path) -> Result<Vec<Vec<String>>, String> {
+    let mut workbook =
+        open_workbook_auto(path).map_err(|e| format!("Gagal membuka file Excel: {}", e))?;
+
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or("File Excel tidak memiliki sheet")?
+        .map_err(|e| format!("Gagal membaca sheet: {}", e))?;
+
+    Ok(range
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Data::Empty => String::new(),
+                    // Angka bulat (tahun, harga, NIK) jangan sampai jadi "2020.0"
+                    Data::Float(f) if f.fract() == 0.0 => format!("{}", *f as i64),
+                    other => other.to_string().trim().to_string(),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+// Cocokkan kolom field -> index kolom di file.
+// `mapping` berisi field -> nama header di file; field yang tidak dipetakan
+// dicocokkan otomatis dengan nama header yang sama (tidak peka huruf besar/kecil).
+fn petakan_kolom(
+    kolom: &[&str],
+    header: &[String],
+    mapping: &HashMap<String, String>,
+) -> Result<HashMap<String, usize>, String> {
+    let normalisasi = |s: &str| s.trim().to_lowercase().replace([' ', '-'], "_");
+
+    let mut result = HashMap::new();
+    for field in kolom {
+        let target = mapping
+            .get(*field)
+            .map(|h| normalisasi(h))
+            .unwrap_or_else(|| field.to_string());
+
+        if let Some(idx) = header.iter().position(|h| normalisasi(h) == target) {
+            result.insert(field.to_string(), idx);
+        } else if mapping.contains_key(*field) {
+            return Err(format!(
+                "Kolom '{}' untuk field '{}' tidak ditemukan di file",
+                mapping[*field], field
+            ));
+        }
+    }
+    Ok(result)
+}
+
+fn ambil(row: &[String], kolom: &HashMap<String, usize>, field: &str) -> String {
+    kolom
+        .get(field)
+        .and_then(|&i| row.get(i))
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+// Bagian ribuan harus 1-3 digit lalu kelompok 3 digit: "1.500.000"
+fn ribuan_valid(bagian: &[&str]) -> bool {
+    bagian.first().is_some_and(|b| (1..=3).contains(&b.len()))
+        && bagian[1..].iter().all(|b| b.len() == 3)
+}
+
+// "Rp 75.000" / "75,000" / "75000" / "75000.00" / "75.000,00" -> 75000.
+// Pemisah ribuan dan desimal dikenali dari posisinya; nilai yang ambigu atau
+// punya pecahan ditolak supaya harga tidak diam-diam dikali 100.
+fn parse_nominal(s: &str) -> Result<Option<i32>, String> {
+    let teks: String = s
+        .trim()
+        .trim_start_matches("Rp")
+        .trim_start_matches("rp")
+        .trim_start_matches("RP")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if teks.is_empty() {
+        return Ok(None);
+    }
+    let tidak_valid = || format!("nominal '{}' tidak valid", s.trim());
+    if !teks
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+    {
+        return Err(tidak_valid());
+    }
+
+    // Jika dua jenis pemisah dipakai, yang terakhir adalah desimal
+    let (bulat, pecahan) = match (teks.rfind('.'), teks.rfind(',')) {
+        (Some(titik), Some(koma)) => {
+            let (posisi, ribuan) = if titik > koma {
+                (titik, ',')
+            } else {
+                (koma, '.')
+            };
+            let bulat = &teks[..posisi];
+            if !ribuan_valid(&bulat.split(ribuan).collect::<Vec<_>>()) {
+                return Err(tidak_valid());
+            }
+            (bulat.replace(ribuan, ""), &teks[posisi + 1..])
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            let pemisah = if teks.contains('.') { '.' } else { ',' };
+            let bagian: Vec<&str> = teks.split(pemisah).collect();
+            match bagian.as_slice() {
+                // Satu pemisah diikuti 1-2 digit: desimal ("75000.00")
+                [bulat, pecahan] if (1..=2).contains(&pecahan.len()) && !bulat.is_empty() => {
+                    (bulat.to_string(), *pecahan)
+                }
+                _ if ribuan_valid(&bagian) => (bagian.concat(), ""),
+                _ => return Err(tidak_valid()),
+            }
+        }
+        (None, None) => (teks.clone(), ""),
+    };
+
+    if !pecahan.chars().all(|c| c.is_ascii_digit()) {
+        return Err(tidak_valid());
+    }
+    if pecahan.chars().any(|c| c != '0') {
+        return Err(format!("nominal '{}' tidak boleh pecahan", s.trim()));
+    }
+    bulat.parse().map(Some).map_err(|_| tidak_valid())
+}
+
+pub fn normalisasi_plat(plat: &str) -> String {
+    plat.chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+fn validasi_motor(row: &[String], kolom: &HashMap<String, usize>) -> Result<Motor, String> {
+    let nama = ambil(row, kolom, "nama");
+    let plat = ambil(row, kolom, "plat").to_uppercase();
+    let harga = ambil(row, kolom, "harga_harian");
+
+    let mut errors = Vec::new();
+    if nama.is_empty() {
+        errors.push("nama wajib diisi".to_string());
+    }
+    if normalisasi_plat(&plat).is_empty() {
+        errors.push("plat wajib diisi".to_string());
+    }
+    let harga_harian = match parse_nominal(&harga) {
+        Ok(Some(h)) if h > 0 => h,
+        Err(e) => {
+            errors.push(format!("harga_harian: {}", e));
+            0
+        }
+        _ => {
+            errors.push(format!("harga_harian '{}' tidak valid", harga));
+            0
+        }
+    };
+    let odometer = parse_nominal(&ambil(row, kolom, "odometer")).unwrap_or_else(|e| {
+        errors.push(format!("odometer: {}", e));
+        None
+    });
+
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    Ok(Motor {
+        motor_id: 0,
+        nama,
+        plat,
+        tipe_motor: ambil(row, kolom, "tipe_motor"),
+        tahun: ambil(row, kolom, "tahun"),
+        harga_harian,
+        foto: String::new(),
+        foto_thumbnail: None,
+        status: "tersedia".to_string(),
+        odometer: odometer.map(i64::from),
+    })
+}
+
+fn validasi_penyewa(row: &[String], kolom: &HashMap<String, usize>) -> Result<Penyewa, String> {
+    let nama = ambil(row, kolom, "nama");
+    let no_ktp = db::normalisasi_no_ktp(&ambil(row, kolom, "no_ktp"));
+
+    let mut errors = Vec::new();
+    if nama.is_empty() {
+        errors.push("nama wajib diisi".to_string());
+    }
+    if no_ktp.is_empty() {
+        errors.push("no_ktp wajib diisi".to_string());
+    } else if no_ktp.len() != 16 || !no_ktp.chars().all(|c| c.is_ascii_digit()) {
+        errors.push(format!("no_ktp '{}' harus 16 digit angka", no_ktp));
+    }
+
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    Ok(Penyewa {
+        penyewa_id: 0,
+        nama,
+        no_hp: ambil(row, kolom, "no_hp"),
+        no_ktp,
+        alamat: ambil(row, kolom, "alamat"),
+    })
+}
+
+// Validasi semua baris. Jika `simpan` true, baris valid langsung dimasukkan
+// dalam satu transaksi database; jika false hanya laporan (dry run).
+fn proses_impor(
+    jenis: &str,
+    file_path: &str,
+    mapping: HashMap<String, String>,
+    simpan: bool,
+) -> Result<ImporLaporan, String> {
+    let kolom_wajib: &[&str] = match jenis {
+        "motor" => &KOLOM_MOTOR,
+        "penyewa" => &KOLOM_PENYEWA,
+        _ => return Err(format!("Jenis impor '{}' tidak dikenal", jenis)),
+    };
+
+    let (header, rows) = baca_file(Path::new(&file_path))?;
+    let kolom = petakan_kolom(kolom_wajib, &header, &mapping)?;
+
+    db::with_connection(|conn| {
+        // Kunci unik yang sudah ada di database: plat untuk motor, NIK untuk penyewa
+        let sudah_ada: HashSet<String> = match jenis {
+            "motor" => db::get_all_motor(conn)?
+                .iter()
+                .map(|m| normalisasi_plat(&m.plat))
+                .collect(),
+            _ => db::get_all_penyewa(conn)?
+                .into_iter()
+                .map(|p| db::normalisasi_no_ktp(&p.no_ktp))
+                .collect(),
+        };
+
+        // Kunci yang sudah muncul di baris sebelumnya pada file yang sama
+        let mut di_file: HashSet<String> = HashSet::new();
+
+        let tx = conn.unchecked_transaction()?;
+        let mut laporan = ImporLaporan {
+            jenis: jenis.to_string(),
+            dry_run: !simpan,
+            total_baris: rows.len(),
+            valid: 0,
+            duplikat: 0,
+            gagal: 0,
+            diimpor: 0,
+            baris: Vec::new(),
+        };
+
+        for (i, row) in rows.iter().enumerate() {
+            // +2: baris 1 adalah header, penomoran baris mulai dari 1
+            let nomor_baris = i + 2;
+
+            let hasil = match jenis {
+                "motor" => validasi_motor(row, &kolom)
+                    .map(|m| (normalisasi_plat(&m.plat), DataImpor::Motor(m))),
+                _ => {
+                    validasi_penyewa(row, &kolom).map(|p| (p.no_ktp.clone(), DataImpor::Penyewa(p)))
+                }
+            };
+
+            let (status, pesan) = match hasil {
+                Err(e) => {
+                    laporan.gagal += 1;
+                    ("gagal", e)
+                }
+                Ok((kunci, _)) if sudah_ada.contains(&kunci) || di_file.contains(&kunci) => {
+                    laporan.duplikat += 1;
+                    let label = if jenis == "motor" { "Plat" } else { "NIK" };
+                    let keterangan = if sudah_ada.contains(&kunci) {
+                        "sudah terdaftar"
+                    } else {
+                        "muncul lebih dari sekali di file"
+                    };
+                    ("duplikat", format!("{} {} {}", label, kunci, keterangan))
+                }
+                Ok((kunci, data)) => {
+                    laporan.valid += 1;
+                    di_file.insert(kunci);
+                    if simpan {
+                        match data {
+                            DataImpor::Motor(m) => db::create_motor(&tx, m)?,
+                            DataImpor::Penyewa(p) => db::create_penyewa(&tx, p)?,
+                        }
+                        laporan.diimpor += 1;
+                    }
+                    ("valid", String::new())
+                }
+            };
+
+            laporan.baris.push(ImporBaris {
+                baris: nomor_baris,
+                status: status.to_string(),
+                pesan,
+            });
+        }
+
+        if simpan {
+            tx.commit()?;
+        }
+
+        Ok(laporan)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_header_impor(file_path: String) -> Result<Vec<String>, String> {
+    println!("🔍 Reading import header: {}", file_path);
+
+    db::blocking(move || baca_file(Path::new(&file_path)))
+        .await
+        .map(|(header, _)| header)
+        .map_err(|e| {
+            eprintln!("❌ Error get_header_impor: {}", e);
+            e
+        })
+}
+
+#[tauri::command]
+pub async fn preview_impor(
+    jenis: String,
+    file_path: String,
+    mapping: Option<HashMap<String, String>>,
+) -> Result<ImporLaporan, String> {
+    println!("🔍 Preview Impor {} dari {}", jenis, file_path);
+
+    db::blocking(move || proses_impor(&jenis, &file_path, mapping.unwrap_or_default(), false))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error preview_impor: {}", e);
+            e
+        })
+}
+
+#[tauri::command]
+pub async fn jalankan_impor(
+    jenis: String,
+    file_path: String,
+    mapping: Option<HashMap<String, String>>,
+) -> Result<ImporLaporan, String> {
+    println!("💾 Impor {} dari {}", jenis, file_path);
+
+    db::blocking(move || proses_impor(&jenis, &file_path, mapping.unwrap_or_default(), true))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error jalankan_impor: {}", e);
+            e
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_nominal_pemisah_ribuan() {
+        assert_eq!(parse_nominal("75000"), Ok(Some(75000)));
+        assert_eq!(parse_nominal("Rp 75.000"), Ok(Some(75000)));
+        assert_eq!(parse_nominal("75,000"), Ok(Some(75000)));
+        assert_eq!(parse_nominal("1.500.000"), Ok(Some(1500000)));
+        assert_eq!(parse_nominal(""), Ok(None));
+    }
+
+    #[test]
+    fn parse_nominal_desimal() {
+        assert_eq!(parse_nominal("75000.00"), Ok(Some(75000)));
+        assert_eq!(parse_nominal("75.000,00"), Ok(Some(75000)));
+        assert_eq!(parse_nominal("75,000.0"), Ok(Some(75000)));
+        assert!(parse_nominal("75.000,50").is_err());
+        assert!(parse_nominal("75000.5").is_err());
+    }
+
+    #[test]
+    fn parse_nominal_ambigu_ditolak() {
+        assert!(parse_nominal("75.00.0").is_err());
+        assert!(parse_nominal("7500.000").is_err());
+        assert!(parse_nominal("75 ribu").is_err());
+        assert!(parse_nominal("1.5.000,00").is_err());
+    }
+
+    #[test]
+    fn nik_dengan_pemisah_dinormalisasi() {
+        let kolom = HashMap::from([("nama".to_string(), 0), ("no_ktp".to_string(), 1)]);
+        for nik in [
+            " 3201 0123 4567 8901 ",
+            "3201-0123-4567-8901",
+            "3201.0123.4567.8901",
+        ] {
+            let baris = vec!["Budi".to_string(), nik.to_string()];
+            let penyewa = validasi_penyewa(&baris, &kolom).unwrap();
+            assert_eq!(penyewa.no_ktp, "3201012345678901");
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::db;
+use crate::models::StatusEnkripsiDatabase;
+
+#[tauri::command]
+pub async fn get_status_enkripsi_database() -> Result<StatusEnkripsiDatabase, String> {
+    Ok(StatusEnkripsiDatabase {
+        didukung: db::ENKRIPSI_DIDUKUNG,
+        terenkripsi: db::db_terenkripsi(),
+        terkunci: db::terkunci(),
+    })
+}
+
+// Dipanggil dari layar buka kunci saat aplikasi dibuka
+#[tauri::command]
+pub async fn buka_kunci_database(kata_sandi: String) -> Result<(), String> {
+    println!("🔓 Unlocking Database...");
+
+    db::blocking(move || {
+        db::buka_kunci(&kata_sandi)
+            .and_then(|_| crate::migrations::siapkan_database())
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error buka_kunci_database: {}", e);
+        e
+    })
+}
+
+// Ubah database plaintext yang sudah ada menjadi database terenkripsi
+#[tauri::command]
+pub async fn aktifkan_enkripsi_database(kata_sandi: String) -> Result<(), String> {
+    println!("🔒 Encrypting Database...");
+
+    crate::enkripsi::validasi_sandi(&kata_sandi)?;
+    db::blocking(move || db::aktifkan_enkripsi(&kata_sandi).map_err(|e| e.to_string()))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error aktifkan_enkripsi_database: {}", e);
+            e
+        })
+}
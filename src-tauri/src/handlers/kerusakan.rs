@@ -0,0 +1,65 @@
+use crate::db;
+use crate::models::Kerusakan;
+
+#[tauri::command]
+pub async fn get_all_kerusakan(
+    transaksi_id: Option<i32>,
+    motor_id: Option<i32>,
+) -> Result<Vec<Kerusakan>, String> {
+    println!("🔍 Getting Kerusakan...");
+
+    db::baca(move |conn| db::get_all_kerusakan(conn, transaksi_id, motor_id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_all_kerusakan: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn create_kerusakan(data: Kerusakan) -> Result<(), String> {
+    println!("💾 Creating Kerusakan...");
+
+    db::tulis(move |conn| db::create_kerusakan(conn, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error create_kerusakan: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn get_kerusakan_by_id(id: i32) -> Result<Kerusakan, String> {
+    println!("🔍 Getting Kerusakan by ID: {}", id);
+
+    db::baca(move |conn| db::get_kerusakan_by_id(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_kerusakan_by_id: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn update_kerusakan(id: i32, data: Kerusakan) -> Result<(), String> {
+    println!("💾 Updating Kerusakan ID: {}", id);
+
+    db::tulis(move |conn| db::update_kerusakan(conn, id, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error update_kerusakan: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn delete_kerusakan(id: i32) -> Result<(), String> {
+    println!("🗑️ Deleting Kerusakan ID: {}", id);
+
+    db::tulis(move |conn| db::delete_kerusakan(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error delete_kerusakan: {}", e);
+            e.to_string()
+        })
+}
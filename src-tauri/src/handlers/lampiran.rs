@@ -0,0 +1,64 @@
+use crate::db;
+use tauri::ipc::{InvokeBody, Request};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+// Ekstensi yang ditawarkan di dialog, tipe aslinya tetap dicek dari isi file
+const EKSTENSI_LAMPIRAN: [&str; 6] = ["png", "jpg", "jpeg", "gif", "webp", "pdf"];
+
+// Unggah file sebagai bytes mentah (body IPC biner), bukan string base64.
+// Dari frontend: invoke("unggah_lampiran", new Uint8Array(await file.arrayBuffer()))
+#[tauri::command]
+pub async fn unggah_lampiran(request: Request<'_>) -> Result<String, String> {
+    println!("💾 Uploading Lampiran...");
+
+    let InvokeBody::Raw(bytes) = request.body() else {
+        return Err("Body harus berupa bytes mentah (Uint8Array/ArrayBuffer)".to_string());
+    };
+
+    // Request tidak bisa dibawa ke thread lain, jadi bytes-nya disalin
+    let bytes = bytes.clone();
+    db::blocking(move || crate::lampiran::simpan(&bytes))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error unggah_lampiran: {}", e);
+            e
+        })
+}
+
+// Buka dialog pilih file lalu salin file yang dipilih langsung dari disk.
+// Path tidak diterima dari frontend, jadi command ini tidak bisa dipakai membaca file sembarang.
+// Mengembalikan None jika dialog dibatalkan.
+#[tauri::command]
+pub async fn impor_lampiran(app: AppHandle) -> Result<Option<String>, String> {
+    println!("💾 Importing Lampiran...");
+
+    db::blocking(move || {
+        let Some(dipilih) = app
+            .dialog()
+            .file()
+            .add_filter("Gambar / PDF", &EKSTENSI_LAMPIRAN)
+            .blocking_pick_file()
+        else {
+            return Ok(None);
+        };
+        let path = dipilih.into_path().map_err(|e| e.to_string())?;
+        crate::lampiran::simpan_dari_path(&path).map(Some)
+    })
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error impor_lampiran: {}", e);
+        e
+    })
+}
+
+// Hapus manual file lampiran yang sudah tidak dipakai record mana pun
+#[tauri::command]
+pub async fn bersihkan_lampiran() -> Result<usize, String> {
+    println!("🗑️ Membersihkan lampiran yatim...");
+
+    db::tulis(crate::lampiran::hapus_yatim).await.map_err(|e| {
+        eprintln!("❌ Error bersihkan_lampiran: {}", e);
+        e.to_string()
+    })
+}
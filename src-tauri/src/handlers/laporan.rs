@@ -0,0 +1,78 @@
+use crate::db;
+use crate::laporan::{self, Periode};
+use crate::models::{ProfitMotor, UtilisasiArmada};
+use std::path::PathBuf;
+
+#[tauri::command]
+pub async fn get_laporan_profit_motor(
+    tanggal_mulai: String,
+    tanggal_selesai: String,
+) -> Result<Vec<ProfitMotor>, String> {
+    println!(
+        "🔍 Getting Laporan Profit Motor {} s/d {}",
+        tanggal_mulai, tanggal_selesai
+    );
+
+    db::baca(move |conn| {
+        let periode = Periode::parse(&tanggal_mulai, &tanggal_selesai)?;
+        laporan::get_profit_motor(conn, &periode)
+    })
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error get_laporan_profit_motor: {}", e);
+        e.to_string()
+    })
+}
+
+// Simpan laporan profit motor sebagai CSV di folder Downloads, kembalikan path file
+#[tauri::command]
+pub async fn ekspor_laporan_profit_motor(
+    tanggal_mulai: String,
+    tanggal_selesai: String,
+) -> Result<String, String> {
+    println!(
+        "💾 Ekspor Laporan Profit Motor {} s/d {}",
+        tanggal_mulai, tanggal_selesai
+    );
+
+    // Nama file dibentuk dari tanggal yang sudah diparse, bukan input mentah,
+    // supaya tidak bisa keluar dari folder Downloads
+    let periode = Periode::parse(&tanggal_mulai, &tanggal_selesai).map_err(|e| e.to_string())?;
+    let data = get_laporan_profit_motor(tanggal_mulai, tanggal_selesai).await?;
+    let bytes = laporan::profit_motor_ke_csv(&data)?;
+
+    let downloads = dirs::download_dir().unwrap_or_else(|| PathBuf::from("."));
+    let file_path = downloads.join(format!(
+        "Laporan_Profit_Motor_{}_{}.csv",
+        periode.mulai.format("%Y-%m-%d"),
+        periode.selesai.format("%Y-%m-%d")
+    ));
+
+    std::fs::write(&file_path, bytes).map_err(|e| {
+        eprintln!("❌ Error ekspor_laporan_profit_motor: {}", e);
+        format!("Gagal menyimpan file: {}", e)
+    })?;
+
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn get_laporan_utilisasi(
+    tanggal_mulai: String,
+    tanggal_selesai: String,
+) -> Result<UtilisasiArmada, String> {
+    println!(
+        "🔍 Getting Laporan Utilisasi {} s/d {}",
+        tanggal_mulai, tanggal_selesai
+    );
+
+    db::baca(move |conn| {
+        let periode = Periode::parse(&tanggal_mulai, &tanggal_selesai)?;
+        laporan::get_utilisasi_armada(conn, &periode)
+    })
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error get_laporan_utilisasi: {}", e);
+        e.to_string()
+    })
+}
@@ -1,7 +1,19 @@
+pub mod backup;
 pub mod bukti_pelunasan;
+pub mod dokumen_motor;
+pub mod dokumen_penyewa;
+pub mod impor;
+pub mod keamanan;
+pub mod kerusakan;
+pub mod lampiran;
+pub mod laporan;
 pub mod motor;
 pub mod pembukuan;
 pub mod pengaturan;
 pub mod pengeluaran_rental;
+pub mod pengingat;
 pub mod penyewa;
+pub mod penyewa_flag;
+pub mod servis_motor;
 pub mod transaksi;
+pub mod whatsapp;
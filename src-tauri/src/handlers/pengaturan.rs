@@ -1,8 +1,17 @@
-use crate::db::with_connection;
+use crate::db;
+
+// Kata sandi dan rahasia lain punya command sendiri dan tidak boleh lewat pengaturan umum
+fn tolak_key_rahasia(key: &str) -> Result<(), String> {
+    if crate::backup::key_rahasia(key) {
+        return Err(format!("Pengaturan '{}' tidak bisa diakses", key));
+    }
+    Ok(())
+}
 
 #[tauri::command]
-pub fn get_pengaturan(key: String) -> Result<String, String> {
-    with_connection(|conn| {
+pub async fn get_pengaturan(key: String) -> Result<String, String> {
+    tolak_key_rahasia(&key)?;
+    db::baca(move |conn| {
         let result = conn.query_row(
             "SELECT value FROM pengaturan WHERE key = ?1",
             [&key],
@@ -13,16 +22,19 @@ pub fn get_pengaturan(key: String) -> Result<String, String> {
             Err(_) => Ok(String::new()),
         }
     })
+    .await
     .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn set_pengaturan(
+pub async fn set_pengaturan(
     key: String,
     value: String,
     keterangan: Option<String>,
 ) -> Result<(), String> {
-    with_connection(|conn| {
+    tolak_key_rahasia(&key)?;
+    db::tulis(move |conn| {
+        crate::penomoran::validasi_pengaturan(conn, &key, &value)?;
         conn.execute(
             "INSERT INTO pengaturan (key, value, keterangan) VALUES (?1, ?2, ?3)
              ON CONFLICT(key) DO UPDATE SET value = ?2, keterangan = ?3",
@@ -30,5 +42,18 @@ pub fn set_pengaturan(
         )?;
         Ok(())
     })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn preview_nomor_dokumen(
+    jenis: String,
+    tanggal: Option<String>,
+) -> Result<String, String> {
+    db::baca(move |conn| {
+        crate::penomoran::pratinjau_nomor(conn, &jenis, &tanggal.unwrap_or_default())
+    })
+    .await
     .map_err(|e| e.to_string())
 }
@@ -0,0 +1,60 @@
+use crate::db;
+use crate::models::Pengingat;
+
+#[tauri::command]
+pub async fn get_all_pengingat(hanya_belum_dibaca: Option<bool>) -> Result<Vec<Pengingat>, String> {
+    println!("🔍 Getting Pengingat...");
+
+    db::baca(move |conn| db::get_all_pengingat(conn, hanya_belum_dibaca.unwrap_or(false)))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_all_pengingat: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn count_pengingat_belum_dibaca() -> Result<i64, String> {
+    db::baca(db::count_pengingat_belum_dibaca)
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error count_pengingat_belum_dibaca: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn tandai_pengingat_dibaca(id: i32, dibaca: Option<bool>) -> Result<(), String> {
+    println!("💾 Marking Pengingat ID: {}", id);
+
+    db::tulis(move |conn| db::set_pengingat_dibaca(conn, id, dibaca.unwrap_or(true)))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error tandai_pengingat_dibaca: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn tandai_semua_pengingat_dibaca() -> Result<(), String> {
+    println!("💾 Marking all Pengingat as read...");
+
+    db::tulis(db::set_semua_pengingat_dibaca)
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error tandai_semua_pengingat_dibaca: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn delete_pengingat(id: i32) -> Result<(), String> {
+    println!("🗑️ Deleting Pengingat ID: {}", id);
+
+    db::tulis(move |conn| db::delete_pengingat(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error delete_pengingat: {}", e);
+            e.to_string()
+        })
+}
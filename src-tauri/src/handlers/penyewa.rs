@@ -1,13 +1,14 @@
-use crate::models::Penyewa;
+use crate::models::{NilaiPenyewa, Penyewa, RiwayatPenyewa};
 use crate::db;
 
 #[tauri::command]
 pub async fn get_all_penyewa() -> Result<Vec<Penyewa>, String> {
     println!("🔍 Getting Penyewa...");
     
-    db::with_connection(|conn| {
+    db::baca(move |conn| {
         db::get_all_penyewa(conn)
     })
+    .await
     .map_err(|e| {
         eprintln!("❌ Error get_all_penyewa: {}", e);
         e.to_string()
@@ -18,9 +19,10 @@ pub async fn get_all_penyewa() -> Result<Vec<Penyewa>, String> {
 pub async fn create_penyewa(data: Penyewa) -> Result<(), String> {
     println!("💾 Creating Penyewa...");
     
-    db::with_connection(|conn| {
+    db::tulis(move |conn| {
         db::create_penyewa(conn, data)
     })
+    .await
     .map_err(|e| {
         eprintln!("❌ Error create_penyewa: {}", e);
         e.to_string()
@@ -31,9 +33,10 @@ pub async fn create_penyewa(data: Penyewa) -> Result<(), String> {
 pub async fn get_penyewa_by_id(id: i32) -> Result<Penyewa, String> {
     println!("🔍 Getting Penyewa by ID: {}", id);
     
-    db::with_connection(|conn| {
+    db::baca(move |conn| {
         db::get_penyewa_by_id(conn, id)
     })
+    .await
     .map_err(|e| {
         eprintln!("❌ Error get_penyewa_by_id: {}", e);
         e.to_string()
@@ -44,9 +47,10 @@ pub async fn get_penyewa_by_id(id: i32) -> Result<Penyewa, String> {
 pub async fn update_penyewa(id: i32, data: Penyewa) -> Result<(), String> {
     println!("💾 Updating Penyewa ID: {}", id);
     
-    db::with_connection(|conn| {
+    db::tulis(move |conn| {
         db::update_penyewa(conn, id, data)
     })
+    .await
     .map_err(|e| {
         eprintln!("❌ Error update_penyewa: {}", e);
         e.to_string()
@@ -57,12 +61,36 @@ pub async fn update_penyewa(id: i32, data: Penyewa) -> Result<(), String> {
 pub async fn delete_penyewa(id: i32) -> Result<(), String> {
     println!("🗑️ Deleting Penyewa ID: {}", id);
     
-    db::with_connection(|conn| {
+    db::tulis(move |conn| {
         db::delete_penyewa(conn, id)
     })
+    .await
     .map_err(|e| {
         eprintln!("❌ Error delete_penyewa: {}", e);
         e.to_string()
     })
 }
 
+#[tauri::command]
+pub async fn get_penyewa_riwayat(id: i32) -> Result<RiwayatPenyewa, String> {
+    println!("🔍 Getting Riwayat Penyewa ID: {}", id);
+
+    db::baca(move |conn| crate::laporan::get_riwayat_penyewa(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_penyewa_riwayat: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn get_top_penyewa(limit: Option<usize>) -> Result<Vec<NilaiPenyewa>, String> {
+    println!("🔍 Getting Top Penyewa...");
+
+    db::baca(move |conn| crate::laporan::get_top_penyewa(conn, limit.unwrap_or(10)))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_top_penyewa: {}", e);
+            e.to_string()
+        })
+}
@@ -0,0 +1,64 @@
+use crate::db;
+use crate::models::PenyewaFlag;
+
+#[tauri::command]
+pub async fn get_all_penyewa_flag() -> Result<Vec<PenyewaFlag>, String> {
+    println!("🔍 Getting Penyewa Flag...");
+
+    db::baca(db::get_all_penyewa_flag).await.map_err(|e| {
+        eprintln!("❌ Error get_all_penyewa_flag: {}", e);
+        e.to_string()
+    })
+}
+
+#[tauri::command]
+pub async fn create_penyewa_flag(data: PenyewaFlag) -> Result<(), String> {
+    println!("💾 Creating Penyewa Flag...");
+
+    db::tulis(move |conn| db::create_penyewa_flag(conn, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error create_penyewa_flag: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn update_penyewa_flag(id: i32, data: PenyewaFlag) -> Result<(), String> {
+    println!("💾 Updating Penyewa Flag ID: {}", id);
+
+    db::tulis(move |conn| db::update_penyewa_flag(conn, id, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error update_penyewa_flag: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn delete_penyewa_flag(id: i32) -> Result<(), String> {
+    println!("🗑️ Deleting Penyewa Flag ID: {}", id);
+
+    db::tulis(move |conn| db::delete_penyewa_flag(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error delete_penyewa_flag: {}", e);
+            e.to_string()
+        })
+}
+
+// Dipanggil form transaksi sebelum menyimpan untuk menampilkan peringatan/penolakan
+#[tauri::command]
+pub async fn cek_penyewa_flag(penyewa_id: i32) -> Result<Option<PenyewaFlag>, String> {
+    println!("🔍 Cek Flag Penyewa ID: {}", penyewa_id);
+
+    db::baca(move |conn| {
+        let penyewa = db::get_penyewa_by_id(conn, penyewa_id)?;
+        db::get_penyewa_flag_by_ktp(conn, &penyewa.no_ktp)
+    })
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error cek_penyewa_flag: {}", e);
+        e.to_string()
+    })
+}
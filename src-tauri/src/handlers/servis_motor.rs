@@ -0,0 +1,83 @@
+use crate::db;
+use crate::models::{MotorPerluServis, ServisMotor};
+
+#[tauri::command]
+pub async fn get_all_servis_motor(motor_id: Option<i32>) -> Result<Vec<ServisMotor>, String> {
+    println!("🔍 Getting Servis Motor...");
+
+    db::baca(move |conn| db::get_all_servis_motor(conn, motor_id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_all_servis_motor: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn create_servis_motor(data: ServisMotor) -> Result<(), String> {
+    println!("💾 Creating Servis Motor...");
+
+    db::tulis(move |conn| db::create_servis_motor(conn, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error create_servis_motor: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn get_servis_motor_by_id(id: i32) -> Result<ServisMotor, String> {
+    println!("🔍 Getting Servis Motor by ID: {}", id);
+
+    db::baca(move |conn| db::get_servis_motor_by_id(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_servis_motor_by_id: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn update_servis_motor(id: i32, data: ServisMotor) -> Result<(), String> {
+    println!("💾 Updating Servis Motor ID: {}", id);
+
+    db::tulis(move |conn| db::update_servis_motor(conn, id, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error update_servis_motor: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn delete_servis_motor(id: i32) -> Result<(), String> {
+    println!("🗑️ Deleting Servis Motor ID: {}", id);
+
+    db::tulis(move |conn| db::delete_servis_motor(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error delete_servis_motor: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn get_motor_perlu_servis(
+    ambang_hari: Option<i64>,
+    ambang_km: Option<i64>,
+) -> Result<Vec<MotorPerluServis>, String> {
+    println!("🔍 Getting Motor perlu servis...");
+
+    db::baca(move |conn| {
+        db::get_motor_perlu_servis(
+            conn,
+            ambang_hari.unwrap_or(db::SERVIS_AMBANG_HARI_DEFAULT),
+            ambang_km.unwrap_or(db::SERVIS_AMBANG_KM_DEFAULT),
+        )
+    })
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error get_motor_perlu_servis: {}", e);
+        e.to_string()
+    })
+}
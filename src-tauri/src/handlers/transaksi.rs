@@ -1,16 +1,12 @@
 use crate::db;
-use crate::models::Transaksi;
-use base64::{engine::general_purpose, Engine as _};
-use std::fs;
+use crate::models::{Tagihan, Transaksi, TransaksiTerlambat};
 use tauri::AppHandle;
-use tauri::Manager;
-use uuid::Uuid;
 
 #[tauri::command]
 pub async fn get_all_transaksi() -> Result<Vec<Transaksi>, String> {
     println!("🔍 Getting Transaksi...");
 
-    db::with_connection(|conn| db::get_all_transaksi(conn)).map_err(|e| {
+    db::baca(db::get_all_transaksi).await.map_err(|e| {
         eprintln!("❌ Error get_all_transaksi: {}", e);
         e.to_string()
     })
@@ -20,67 +16,82 @@ pub async fn get_all_transaksi() -> Result<Vec<Transaksi>, String> {
 pub async fn create_transaksi(data: Transaksi) -> Result<(), String> {
     println!("💾 Creating Transaksi...");
 
-    db::with_connection(|conn| db::create_transaksi(conn, data)).map_err(|e| {
-        eprintln!("❌ Error create_transaksi: {}", e);
-        e.to_string()
-    })
+    db::tulis(move |conn| db::create_transaksi(conn, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error create_transaksi: {}", e);
+            e.to_string()
+        })
 }
 
 #[tauri::command]
 pub async fn get_transaksi_by_id(id: i32) -> Result<Transaksi, String> {
     println!("🔍 Getting Transaksi by ID: {}", id);
 
-    db::with_connection(|conn| db::get_transaksi_by_id(conn, id)).map_err(|e| {
-        eprintln!("❌ Error get_transaksi_by_id: {}", e);
-        e.to_string()
-    })
+    db::baca(move |conn| db::get_transaksi_by_id(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_transaksi_by_id: {}", e);
+            e.to_string()
+        })
 }
 
 #[tauri::command]
 pub async fn update_transaksi(id: i32, data: Transaksi) -> Result<(), String> {
     println!("💾 Updating Transaksi ID: {}", id);
 
-    db::with_connection(|conn| db::update_transaksi(conn, id, data)).map_err(|e| {
-        eprintln!("❌ Error update_transaksi: {}", e);
-        e.to_string()
-    })
+    db::tulis(move |conn| db::update_transaksi(conn, id, data))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error update_transaksi: {}", e);
+            e.to_string()
+        })
 }
 
 #[tauri::command]
 pub async fn delete_transaksi(id: i32) -> Result<(), String> {
     println!("🗑️ Deleting Transaksi ID: {}", id);
 
-    db::with_connection(|conn| db::delete_transaksi(conn, id)).map_err(|e| {
-        eprintln!("❌ Error delete_transaksi: {}", e);
-        e.to_string()
-    })
+    db::tulis(move |conn| db::delete_transaksi(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error delete_transaksi: {}", e);
+            e.to_string()
+        })
 }
 
 #[tauri::command]
-pub async fn save_transaksi_image(app: AppHandle, base64: String) -> Result<String, String> {
-    println!("💾 Saving Transaksi Image...");
-
-    let dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?
-        .join("transaksi");
-
-    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+pub async fn periksa_keterlambatan(app: AppHandle) -> Result<Vec<TransaksiTerlambat>, String> {
+    println!("⏰ Checking overdue Transaksi...");
 
-    let filename = format!("{}.png", Uuid::new_v4());
-    let filepath = dir.join(&filename);
-
-    let base64_data = base64.split(',').nth(1).ok_or("Invalid base64 format")?;
+    db::blocking(move || Ok(crate::scheduler::periksa_keterlambatan(&app))).await
+}
 
-    let bytes = general_purpose::STANDARD
-        .decode(base64_data)
-        .map_err(|e| format!("Failed to decode base64: {}", e))?;
+// Dipanggil Navbar saat mount: event "transaksi-terlambat" dari pemeriksaan pertama
+// bisa terkirim sebelum listener-nya terpasang
+#[tauri::command]
+pub async fn get_transaksi_terlambat_terakhir() -> Result<Vec<TransaksiTerlambat>, String> {
+    Ok(crate::scheduler::ambil_terlambat_terakhir())
+}
 
-    fs::write(&filepath, bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+#[tauri::command]
+pub async fn get_tagihan_transaksi(id: i32) -> Result<Tagihan, String> {
+    println!("🔍 Getting Tagihan Transaksi ID: {}", id);
+
+    db::baca(move |conn| db::get_tagihan_transaksi(conn, id))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error get_tagihan_transaksi: {}", e);
+            e.to_string()
+        })
+}
 
-    let path_string = filepath.to_string_lossy().to_string();
-    println!("✅ Image saved at: {}", path_string);
+#[tauri::command]
+pub async fn get_all_tagihan() -> Result<Vec<Tagihan>, String> {
+    println!("🔍 Getting semua Tagihan...");
 
-    Ok(path_string)
+    db::baca(db::get_all_tagihan).await.map_err(|e| {
+        eprintln!("❌ Error get_all_tagihan: {}", e);
+        e.to_string()
+    })
 }
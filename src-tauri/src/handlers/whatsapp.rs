@@ -0,0 +1,208 @@
+use crate::db;
+use crate::models::PesanWa;
+use chrono::{Datelike, NaiveDate};
+use rusqlite::Connection;
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+// Template bawaan, bisa diganti lewat pengaturan dengan key "template_wa_<jenis>"
+fn template_default(jenis: &str) -> &'static str {
+    match jenis {
+        "terlambat" => "Halo {nama}, motor {motor} ({plat}) seharusnya kembali pada {tanggal_kembali}. Saat ini ada denda keterlambatan {denda}. Mohon segera dikembalikan, terima kasih.",
+        "tagihan" => "Halo {nama}, sisa pembayaran sewa motor {motor} ({plat}) adalah {sisa_bayar}. Terima kasih.",
+        _ => "Halo {nama}, mengingatkan bahwa motor {motor} ({plat}) dijadwalkan kembali pada {tanggal_kembali}. Terima kasih.",
+    }
+}
+
+// 0812-3456-789 / +62 812... / +62 0812... / 812... -> 628123456789
+pub fn normalisasi_no_hp(no_hp: &str) -> String {
+    let digits: String = no_hp.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    if let Some(rest) = digits.strip_prefix("62") {
+        // "+62 0812..." sering ditulis dengan 0 lokal yang tidak ikut dibuang
+        format!("62{}", rest.strip_prefix('0').unwrap_or(rest))
+    } else if let Some(rest) = digits.strip_prefix('0') {
+        format!("62{}", rest)
+    } else if digits.starts_with('8') {
+        format!("62{}", digits)
+    } else {
+        digits
+    }
+}
+
+pub fn format_rupiah(nominal: i64) -> String {
+    let digits = nominal.abs().to_string();
+    let mut result = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push('.');
+        }
+        result.push(c);
+    }
+    if nominal < 0 {
+        format!("-Rp {}", result)
+    } else {
+        format!("Rp {}", result)
+    }
+}
+
+// "2026-10-19" -> "19 Oktober 2026"
+pub fn format_tanggal(tanggal: &str) -> String {
+    const BULAN: [&str; 12] = [
+        "Januari",
+        "Februari",
+        "Maret",
+        "April",
+        "Mei",
+        "Juni",
+        "Juli",
+        "Agustus",
+        "September",
+        "Oktober",
+        "November",
+        "Desember",
+    ];
+
+    match tanggal
+        .get(0..10)
+        .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+    {
+        Some(d) => format!("{} {} {}", d.day(), BULAN[d.month0() as usize], d.year()),
+        None => tanggal.to_string(),
+    }
+}
+
+fn encode_url(s: &str) -> String {
+    let mut result = String::new();
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                result.push(b as char)
+            }
+            _ => result.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    result
+}
+
+fn render_pesan(conn: &Connection, transaksi_id: i32, jenis: &str) -> rusqlite::Result<PesanWa> {
+    let transaksi = db::get_transaksi_by_id(conn, transaksi_id)?;
+    let penyewa = db::get_penyewa_by_id(conn, transaksi.penyewa_id)?;
+    let motor = db::get_motor_by_id(conn, transaksi.motor_id)?;
+    let tagihan = db::get_tagihan_transaksi(conn, transaksi_id)?;
+
+    let template = db::get_pengaturan_value(conn, &format!("template_wa_{}", jenis))?
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| template_default(jenis).to_string());
+
+    let pesan = template
+        .replace("{nama}", &penyewa.nama)
+        .replace("{motor}", &motor.nama)
+        .replace("{plat}", &motor.plat)
+        .replace(
+            "{tanggal_kembali}",
+            &format_tanggal(&transaksi.tanggal_kembali_rencana),
+        )
+        .replace("{denda}", &format_rupiah(tagihan.denda))
+        .replace("{sisa_bayar}", &format_rupiah(tagihan.sisa_bayar))
+        .replace("{nomor}", transaksi.nomor.as_deref().unwrap_or_default());
+
+    let no_hp = normalisasi_no_hp(&penyewa.no_hp);
+    let link = format!("https://wa.me/{}?text={}", no_hp, encode_url(&pesan));
+
+    Ok(PesanWa {
+        transaksi_id,
+        no_hp,
+        pesan,
+        link,
+    })
+}
+
+#[tauri::command]
+pub async fn render_pesan_wa(transaksi_id: i32, jenis: Option<String>) -> Result<PesanWa, String> {
+    println!(
+        "🔍 Rendering WhatsApp message for Transaksi ID: {}",
+        transaksi_id
+    );
+
+    let jenis = jenis.unwrap_or_else(|| "pengingat".to_string());
+    db::baca(move |conn| render_pesan(conn, transaksi_id, &jenis))
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error render_pesan_wa: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn kirim_pesan_wa(
+    app: AppHandle,
+    transaksi_id: i32,
+    jenis: Option<String>,
+) -> Result<PesanWa, String> {
+    let pesan = render_pesan_wa(transaksi_id, jenis).await?;
+
+    if pesan.no_hp.is_empty() {
+        return Err("Nomor HP penyewa belum diisi".to_string());
+    }
+
+    app.opener()
+        .open_url(&pesan.link, None::<&str>)
+        .map_err(|e| {
+            eprintln!("❌ Error kirim_pesan_wa: {}", e);
+            format!("Gagal membuka WhatsApp: {}", e)
+        })?;
+
+    Ok(pesan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_hp_dinormalisasi_ke_format_internasional() {
+        for no_hp in [
+            "0812-3456-789",
+            "+62 812 3456 789",
+            "+62 0812 3456 789",
+            "62812345678 9",
+            "812 3456 789",
+        ] {
+            assert_eq!(normalisasi_no_hp(no_hp), "628123456789", "{}", no_hp);
+        }
+        assert_eq!(normalisasi_no_hp(""), "");
+        assert_eq!(normalisasi_no_hp("+1 555 0100"), "15550100");
+    }
+
+    #[test]
+    fn rupiah_dengan_pemisah_ribuan() {
+        assert_eq!(format_rupiah(0), "Rp 0");
+        assert_eq!(format_rupiah(500), "Rp 500");
+        assert_eq!(format_rupiah(1000), "Rp 1.000");
+        assert_eq!(format_rupiah(75000), "Rp 75.000");
+        assert_eq!(format_rupiah(1234567), "Rp 1.234.567");
+        assert_eq!(format_rupiah(-20000), "-Rp 20.000");
+    }
+
+    #[test]
+    fn tanggal_dengan_nama_bulan() {
+        assert_eq!(format_tanggal("2026-10-19"), "19 Oktober 2026");
+        assert_eq!(format_tanggal("2026-01-03 14:30:00"), "3 Januari 2026");
+        assert_eq!(format_tanggal("besok"), "besok");
+        assert_eq!(format_tanggal(""), "");
+    }
+
+    #[test]
+    fn url_encode_spasi_dan_karakter_khusus() {
+        assert_eq!(encode_url("Halo Budi"), "Halo%20Budi");
+        assert_eq!(
+            encode_url("Rp 1.000, (B 1234 XY)"),
+            "Rp%201.000%2C%20%28B%201234%20XY%29"
+        );
+        assert_eq!(encode_url("a&b=c?d#e"), "a%26b%3Dc%3Fd%23e");
+        assert_eq!(encode_url("baris\nbaru"), "baris%0Abaru");
+        assert_eq!(encode_url("é"), "%C3%A9");
+        assert_eq!(encode_url("-_.~"), "-_.~");
+    }
+}
@@ -0,0 +1,674 @@
+use crate::db;
+use chrono::{Duration, Local, NaiveDateTime};
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageDecoder, ImageReader};
+use rusqlite::{Connection, OptionalExtension, Result};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+// Kolom yang menyimpan path lampiran: (tabel, kolom id, kolom path, isinya JSON array)
+const REFERENSI: [(&str, &str, &str, bool); 6] = [
+    ("motor", "motor_id", "foto", false),
+    ("transaksi", "transaksi_id", "foto_bukti", false),
+    ("bukti_pelunasan", "bukti_id", "foto_bukti", false),
+    ("kerusakan", "kerusakan_id", "foto", true),
+    ("dokumen_motor", "dokumen_id", "foto", false),
+    ("dokumen_penyewa", "dokumen_id", "path", false),
+];
+
+// File yang baru diunggah belum tentu sudah disimpan ke record (form belum disubmit),
+// jadi jangan dihapus sebelum lewat masa tenggang ini
+const MASA_TENGGANG_JAM: i64 = 24;
+
+// Foto diperkecil sampai sisi terpanjangnya maksimal UKURAN_MAKS px lalu disimpan ulang sebagai JPEG
+const UKURAN_MAKS: u32 = 1600;
+const KUALITAS_JPEG: u8 = 80;
+const UKURAN_THUMBNAIL: u32 = 320;
+const KUALITAS_THUMBNAIL: u8 = 70;
+const AKHIRAN_THUMBNAIL: &str = ".thumb.jpg";
+
+// Batas ukuran file asli yang diterima, bisa diubah lewat pengaturan ukuran_maks_lampiran_mb
+const UKURAN_MAKS_MB_DEFAULT: u64 = 10;
+
+// Semua lampiran disimpan di <folder data>/lampiran/<sha256>.<ext>
+pub fn folder() -> PathBuf {
+    crate::paths::lampiran_dir()
+}
+
+// Tipe file dikenali dari magic bytes, bukan dari ekstensi/prefix data URL
+pub fn deteksi_mime(bytes: &[u8]) -> Option<(&'static str, &'static str)> {
+    if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some(("image/png", "png"))
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(("image/jpeg", "jpg"))
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some(("image/gif", "gif"))
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some(("image/webp", "webp"))
+    } else if bytes.starts_with(b"%PDF-") {
+        Some(("application/pdf", "pdf"))
+    } else {
+        None
+    }
+}
+
+pub fn ukuran_maks(conn: &Connection) -> Result<u64> {
+    let mb = db::get_pengaturan_value(conn, "ukuran_maks_lampiran_mb")?
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|&mb| mb > 0)
+        .unwrap_or(UKURAN_MAKS_MB_DEFAULT);
+    Ok(mb * 1024 * 1024)
+}
+
+fn validasi_ukuran(ukuran: u64, maks: u64) -> std::result::Result<(), String> {
+    if ukuran == 0 {
+        return Err("File kosong".to_string());
+    }
+    if ukuran > maks {
+        return Err(format!(
+            "Ukuran file {:.1} MB melebihi batas {} MB",
+            ukuran as f64 / 1024.0 / 1024.0,
+            maks / 1024 / 1024
+        ));
+    }
+    Ok(())
+}
+
+fn encode_jpeg(img: &DynamicImage, kualitas: u8) -> std::result::Result<Vec<u8>, String> {
+    // JPEG tidak punya alpha: gambar transparan ditaruh di atas latar putih
+    let rgb = if img.color().has_alpha() {
+        let mut latar =
+            image::RgbImage::from_pixel(img.width(), img.height(), image::Rgb([255; 3]));
+        for (x, y, p) in img.to_rgba8().enumerate_pixels() {
+            let a = p[3] as u32;
+            let q = latar.get_pixel_mut(x, y);
+            for i in 0..3 {
+                q[i] = ((p[i] as u32 * a + 255 * (255 - a)) / 255) as u8;
+            }
+        }
+        latar
+    } else {
+        img.to_rgb8()
+    };
+
+    let mut out = Vec::new();
+    JpegEncoder::new_with_quality(&mut out, kualitas)
+        .encode_image(&rgb)
+        .map_err(|e| format!("Gagal mengompres gambar: {}", e))?;
+    Ok(out)
+}
+
+// Baca gambar dengan memperhatikan orientasi EXIF (foto HP sering tersimpan miring)
+fn decode_gambar(bytes: &[u8]) -> std::result::Result<DynamicImage, String> {
+    let mut decoder = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?
+        .into_decoder()
+        .map_err(|e| format!("Gambar tidak bisa dibaca: {}", e))?;
+    let orientasi = decoder.orientation().map_err(|e| e.to_string())?;
+    let mut img = DynamicImage::from_decoder(decoder)
+        .map_err(|e| format!("Gambar tidak bisa dibaca: {}", e))?;
+    img.apply_orientation(orientasi);
+    Ok(img)
+}
+
+struct FileSiap {
+    isi: Vec<u8>,
+    mime: &'static str,
+    ext: &'static str,
+    thumbnail: Option<Vec<u8>>,
+}
+
+// Perkecil dan kompres ulang foto.
+// PDF dan GIF (bisa animasi) disimpan apa adanya tanpa thumbnail.
+fn proses(bytes: &[u8]) -> std::result::Result<FileSiap, String> {
+    let (mime, ext) = deteksi_mime(bytes)
+        .ok_or("Tipe file tidak didukung, gunakan gambar PNG/JPEG/GIF/WEBP atau PDF")?;
+    if !matches!(mime, "image/png" | "image/jpeg" | "image/webp") {
+        return Ok(FileSiap {
+            isi: bytes.to_vec(),
+            mime,
+            ext,
+            thumbnail: None,
+        });
+    }
+
+    let img = decode_gambar(bytes)?;
+    let thumbnail = encode_jpeg(
+        &img.thumbnail(UKURAN_THUMBNAIL, UKURAN_THUMBNAIL),
+        KUALITAS_THUMBNAIL,
+    )?;
+
+    let diperkecil = img.width() > UKURAN_MAKS || img.height() > UKURAN_MAKS;
+    let img = if diperkecil {
+        img.resize(
+            UKURAN_MAKS,
+            UKURAN_MAKS,
+            image::imageops::FilterType::Lanczos3,
+        )
+    } else {
+        img
+    };
+    let jpeg = encode_jpeg(&img, KUALITAS_JPEG)?;
+
+    // Gambar kecil yang tidak jadi lebih hemat setelah dikompres disimpan apa adanya
+    if !diperkecil && jpeg.len() >= bytes.len() {
+        return Ok(FileSiap {
+            isi: bytes.to_vec(),
+            mime,
+            ext,
+            thumbnail: Some(thumbnail),
+        });
+    }
+    Ok(FileSiap {
+        isi: jpeg,
+        mime: "image/jpeg",
+        ext: "jpg",
+        thumbnail: Some(thumbnail),
+    })
+}
+
+// Tulis ke file sementara dulu supaya tidak ada file setengah jadi dengan nama final
+fn tulis(path: &Path, bytes: &[u8]) -> std::result::Result<(), String> {
+    if path.exists() {
+        return Ok(());
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+    fs::rename(&tmp, path).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+// Simpan isi file ke penyimpanan lampiran dan kembalikan path absolutnya.
+// Foto diperkecil/dikompres dan dibuatkan thumbnail <hash>.thumb.jpg.
+// File dengan isi yang sama hanya disimpan sekali.
+// Pengolahan gambar dilakukan di luar lock database; penulisan file dan pencatatannya
+// memakai koneksi yang sama dengan hapus_yatim supaya file tidak terhapus di antaranya.
+pub fn simpan(bytes: &[u8]) -> std::result::Result<String, String> {
+    let maks = db::with_connection(ukuran_maks).map_err(|e| e.to_string())?;
+    validasi_ukuran(bytes.len() as u64, maks)?;
+
+    let FileSiap {
+        isi,
+        mime,
+        ext,
+        thumbnail,
+    } = proses(bytes)?;
+
+    let hash = hex::encode(Sha256::digest(&isi));
+    let nama_file = format!("{}.{}", hash, ext);
+
+    let dir = folder();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let path = dir.join(&nama_file);
+
+    db::with_connection(|conn| {
+        tulis(&path, &isi).map_err(db::validation_error)?;
+        let nama_thumbnail = match &thumbnail {
+            Some(thumbnail) => {
+                let nama = format!("{}{}", hash, AKHIRAN_THUMBNAIL);
+                tulis(&dir.join(&nama), thumbnail).map_err(db::validation_error)?;
+                Some(nama)
+            }
+            None => None,
+        };
+
+        // Unggah ulang isi yang sama memperbarui created_at supaya ikut masa tenggang lagi
+        conn.execute(
+            "INSERT INTO lampiran (hash, nama_file, mime, ukuran, created_at, thumbnail) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(hash) DO UPDATE SET created_at = excluded.created_at, thumbnail = excluded.thumbnail",
+            (
+                &hash,
+                &nama_file,
+                mime,
+                isi.len() as i64,
+                Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                &nama_thumbnail,
+            ),
+        )
+    })
+    .map_err(|e| e.to_string())?;
+
+    let path_string = path.to_string_lossy().to_string();
+    println!("✅ Lampiran saved at: {}", path_string);
+    Ok(path_string)
+}
+
+// Salin file dari disk (hasil dialog pilih file) tanpa lewat base64.
+// Yang dibaca paling banyak batas ukuran + 1 byte, jadi file raksasa (atau yang membesar
+// setelah dicek) tidak sempat dibaca seluruhnya ke memori.
+pub fn simpan_dari_path(path: &Path) -> std::result::Result<String, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Gagal membaca file: {}", e))?;
+    let meta = file
+        .metadata()
+        .map_err(|e| format!("Gagal membaca file: {}", e))?;
+    if !meta.is_file() {
+        return Err(format!("{} bukan file", path.display()));
+    }
+
+    let maks = db::with_connection(ukuran_maks).map_err(|e| e.to_string())?;
+    validasi_ukuran(meta.len(), maks)?;
+
+    let mut bytes = Vec::new();
+    file.take(maks + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Gagal membaca file: {}", e))?;
+    validasi_ukuran(bytes.len() as u64, maks)?;
+    simpan(&bytes)
+}
+
+// Path thumbnail untuk sebuah lampiran dari nama yang dicatat saat disimpan,
+// None jika tidak ada (PDF, file lama di luar folder lampiran)
+pub fn thumbnail(conn: &Connection, path: &str) -> Result<Option<String>> {
+    let (Some(nama), Some(dir)) = (nama_file(path), Path::new(path).parent()) else {
+        return Ok(None);
+    };
+    let thumb: Option<String> = conn
+        .prepare_cached("SELECT thumbnail FROM lampiran WHERE hash = ?1")?
+        .query_row([hash_file(nama)], |row| row.get(0))
+        .optional()?
+        .flatten();
+    Ok(thumb.map(|t| dir.join(t).to_string_lossy().to_string()))
+}
+
+pub fn thumbnail_opsional(conn: &Connection, path: &Option<String>) -> Result<Option<String>> {
+    match path.as_deref().filter(|p| !p.is_empty()) {
+        Some(path) => thumbnail(conn, path),
+        None => Ok(None),
+    }
+}
+
+// Isi kolom thumbnail untuk lampiran yang disimpan sebelum kolom itu ada
+pub fn isi_thumbnail_lama(conn: &Connection) -> Result<()> {
+    let dir = folder();
+    let hashes: Vec<String> = {
+        let mut stmt = conn.prepare("SELECT hash FROM lampiran WHERE thumbnail IS NULL")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect::<Result<_>>()?
+    };
+    for hash in hashes {
+        let nama = format!("{}{}", hash, AKHIRAN_THUMBNAIL);
+        if dir.join(&nama).exists() {
+            conn.execute(
+                "UPDATE lampiran SET thumbnail = ?1 WHERE hash = ?2",
+                (&nama, &hash),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// Nama file dari path Windows maupun Unix
+fn nama_file(path: &str) -> Option<&str> {
+    path.rsplit(['/', '\\']).next().filter(|n| !n.is_empty())
+}
+
+fn isi_kolom(nilai: &str, json: bool) -> Vec<String> {
+    if json {
+        serde_json::from_str(nilai).unwrap_or_default()
+    } else {
+        vec![nilai.to_string()]
+    }
+}
+
+// Hash (bagian nama file sebelum titik pertama) dari file lampiran maupun thumbnail-nya
+fn hash_file(nama: &str) -> &str {
+    nama.split('.').next().unwrap_or(nama)
+}
+
+// Query yang menghasilkan (tabel, id, path) untuk setiap rujukan lampiran di satu kolom.
+// `baris` adalah alias baris sumbernya: NEW/OLD di trigger, atau `t` untuk seluruh tabel.
+fn pilih_referensi(
+    (tabel, kolom_id, kolom, json): (&str, &str, &str, bool),
+    baris: &str,
+    seluruh_tabel: bool,
+) -> String {
+    let dari = if seluruh_tabel {
+        format!("{tabel} {baris}")
+    } else {
+        String::new()
+    };
+    if json {
+        let dari = if seluruh_tabel {
+            format!("{dari}, ")
+        } else {
+            dari
+        };
+        format!(
+            "SELECT '{tabel}', {baris}.{kolom_id}, j.value
+             FROM {dari}json_each(CASE WHEN json_valid({baris}.{kolom}) THEN {baris}.{kolom} ELSE '[]' END) j
+             WHERE j.value != ''"
+        )
+    } else {
+        let dari = if seluruh_tabel {
+            format!("FROM {dari}")
+        } else {
+            dari
+        };
+        format!(
+            "SELECT '{tabel}', {baris}.{kolom_id}, {baris}.{kolom} {dari}
+             WHERE {baris}.{kolom} IS NOT NULL AND {baris}.{kolom} != ''"
+        )
+    }
+}
+
+// Rujukan lampiran dicatat di tabel lampiran_referensi dan dijaga trigger di setiap
+// tabel pada REFERENSI, jadi pembersihan tidak perlu memindai semua record.
+// Dipanggil dari migrasi: trigger dibuat ulang dan isinya dibangun ulang dari data yang ada.
+pub fn siapkan_referensi(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS lampiran_referensi (
+            tabel TEXT NOT NULL,
+            ref_id INTEGER NOT NULL,
+            path TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_lampiran_referensi_ref ON lampiran_referensi(tabel, ref_id);
+        DELETE FROM lampiran_referensi;",
+    )?;
+
+    for referensi in REFERENSI {
+        let (tabel, kolom_id, kolom, _) = referensi;
+        let tambah = |baris: &str| {
+            format!(
+                "INSERT INTO lampiran_referensi (tabel, ref_id, path) {};",
+                pilih_referensi(referensi, baris, false)
+            )
+        };
+        let hapus = format!(
+            "DELETE FROM lampiran_referensi WHERE tabel = '{tabel}' AND ref_id = OLD.{kolom_id};"
+        );
+
+        conn.execute_batch(&format!(
+            "DROP TRIGGER IF EXISTS trg_lampiran_{tabel}_insert;
+             DROP TRIGGER IF EXISTS trg_lampiran_{tabel}_update;
+             DROP TRIGGER IF EXISTS trg_lampiran_{tabel}_delete;
+             CREATE TRIGGER trg_lampiran_{tabel}_insert AFTER INSERT ON {tabel}
+             BEGIN {tambah_new} END;
+             CREATE TRIGGER trg_lampiran_{tabel}_update AFTER UPDATE OF {kolom} ON {tabel}
+             BEGIN {hapus} {tambah_new} END;
+             CREATE TRIGGER trg_lampiran_{tabel}_delete AFTER DELETE ON {tabel}
+             BEGIN {hapus} END;
+             INSERT INTO lampiran_referensi (tabel, ref_id, path) {isi};",
+            tambah_new = tambah("NEW"),
+            isi = pilih_referensi(referensi, "t", true),
+        ))?;
+    }
+    Ok(())
+}
+
+// Hash semua lampiran yang masih dipakai record mana pun
+fn hash_terpakai(conn: &Connection) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare("SELECT DISTINCT path FROM lampiran_referensi")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+    let mut terpakai = HashSet::new();
+    for path in rows {
+        if let Some(nama) = nama_file(&path?) {
+            terpakai.insert(hash_file(nama).to_string());
+        }
+    }
+    Ok(terpakai)
+}
+
+fn lewat_masa_tenggang(created_at: &str) -> bool {
+    NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S")
+        .map(|t| Local::now().naive_local() - t > Duration::hours(MASA_TENGGANG_JAM))
+        .unwrap_or(true)
+}
+
+// Hapus lampiran (beserta thumbnail) yang tidak dirujuk record mana pun,
+// kembalikan jumlah file yang dihapus
+pub fn hapus_yatim(conn: &Connection) -> Result<usize> {
+    let terpakai = hash_terpakai(conn)?;
+    let dir = folder();
+    let mut dihapus = 0;
+
+    let hapus_file = |nama: &str| -> bool {
+        let path = dir.join(nama);
+        !path.exists() || fs::remove_file(&path).is_ok()
+    };
+
+    let terdaftar: Vec<(String, String, String)> = {
+        let mut stmt = conn.prepare("SELECT hash, nama_file, created_at FROM lampiran")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        rows.collect::<Result<_>>()?
+    };
+
+    let mut dikenal = HashSet::new();
+    for (hash, nama, created_at) in terdaftar {
+        dikenal.insert(hash.clone());
+        if terpakai.contains(&hash) || !lewat_masa_tenggang(&created_at) {
+            continue;
+        }
+        if !hapus_file(&nama) {
+            continue;
+        }
+        hapus_file(&format!("{}{}", hash, AKHIRAN_THUMBNAIL));
+        conn.execute("DELETE FROM lampiran WHERE hash = ?1", [&hash])?;
+        dihapus += 1;
+    }
+
+    // File di folder lampiran yang tidak tercatat (mis. sisa restore database lama)
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let nama = entry.file_name().to_string_lossy().to_string();
+            let hash = hash_file(&nama);
+            if dikenal.contains(hash) || terpakai.contains(hash) {
+                continue;
+            }
+            let lama = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.elapsed().ok())
+                .is_some_and(|d| d.as_secs() > MASA_TENGGANG_JAM as u64 * 3600);
+            if lama && fs::remove_file(entry.path()).is_ok() {
+                dihapus += 1;
+            }
+        }
+    }
+
+    if dihapus > 0 {
+        println!("🗑️ {} lampiran yatim dihapus", dihapus);
+    }
+    Ok(dihapus)
+}
+
+// Terapkan `ubah` ke setiap path lampiran di semua record dan simpan yang berubah
+fn petakan_path(conn: &Connection, ubah: impl Fn(&str) -> Option<String>) -> Result<()> {
+    for (tabel, kolom_id, kolom, json) in REFERENSI {
+        let rows: Vec<(i64, String)> = {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {kolom_id}, {kolom} FROM {tabel} WHERE {kolom} IS NOT NULL AND {kolom} != ''"
+            ))?;
+            let iter = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            iter.collect::<Result<_>>()?
+        };
+
+        for (id, nilai) in rows {
+            let paths: Vec<String> = isi_kolom(&nilai, json)
+                .into_iter()
+                .map(|p| ubah(&p).unwrap_or(p))
+                .collect();
+            let baru = if json {
+                serde_json::to_string(&paths).unwrap_or(nilai.clone())
+            } else {
+                paths.into_iter().next().unwrap_or_default()
+            };
+            if baru != nilai {
+                conn.execute(
+                    &format!("UPDATE {tabel} SET {kolom} = ?1 WHERE {kolom_id} = ?2"),
+                    (baru, id),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Arahkan ulang path lampiran di semua record ke folder lampiran saat ini
+// (setelah restore di komputer lain atau folder data dipindah)
+pub fn perbaiki_path(conn: &Connection, dir: &Path) -> Result<()> {
+    let terdaftar: HashSet<String> = {
+        let mut stmt = conn.prepare("SELECT nama_file FROM lampiran")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect::<Result<_>>()?
+    };
+
+    petakan_path(conn, |path| {
+        nama_file(path)
+            .filter(|nama| terdaftar.contains(*nama))
+            .map(|nama| dir.join(nama).to_string_lossy().to_string())
+    })
+}
+
+// Ganti path file tertentu di semua record (mis. file yang dipindahkan dengan nama lain)
+pub fn ganti_path(conn: &Connection, peta: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let peta: HashMap<String, String> = peta
+        .iter()
+        .map(|(lama, baru)| {
+            (
+                lama.to_string_lossy().to_string(),
+                baru.to_string_lossy().to_string(),
+            )
+        })
+        .collect();
+
+    petakan_path(conn, |path| peta.get(path).cloned())
+}
+
+// Ganti awalan path di semua record dari folder lama ke folder baru
+pub fn ganti_folder(conn: &Connection, lama: &Path, baru: &Path) -> Result<()> {
+    let lama = lama.to_string_lossy().to_string();
+    let baru = baru.to_string_lossy().to_string();
+
+    petakan_path(conn, |path| {
+        path.strip_prefix(&lama)
+            .map(|sisa| format!("{}{}", baru, sisa))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn referensi(conn: &Connection) -> Vec<(String, i64, String)> {
+        let mut stmt = conn
+            .prepare("SELECT tabel, ref_id, path FROM lampiran_referensi ORDER BY tabel, path")
+            .unwrap();
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap();
+        rows.collect::<Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn referensi_mengikuti_perubahan_record() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::migrations::migrate(&conn).unwrap();
+
+        conn.execute_batch(
+            "INSERT INTO motor (motor_id, nama, foto, plat, tipe_motor, tahun, harga_harian, status)
+             VALUES (1, 'Beat', '/data/lampiran/aaa.jpg', 'B 1', 'matic', '2020', 50000, 'tersedia');
+             INSERT INTO kerusakan (kerusakan_id, transaksi_id, motor_id, tanggal, deskripsi, foto)
+             VALUES (1, 1, 1, '2026-10-01', 'lecet', '[\"/data/lampiran/bbb.jpg\",\"C:\\\\lampiran\\\\ccc.png\"]');",
+        )
+        .unwrap();
+        assert_eq!(
+            referensi(&conn),
+            vec![
+                ("kerusakan".into(), 1, "/data/lampiran/bbb.jpg".into()),
+                ("kerusakan".into(), 1, "C:\\lampiran\\ccc.png".into()),
+                ("motor".into(), 1, "/data/lampiran/aaa.jpg".into()),
+            ]
+        );
+        let terpakai = hash_terpakai(&conn).unwrap();
+        assert!(["aaa", "bbb", "ccc"].iter().all(|h| terpakai.contains(*h)));
+
+        conn.execute_batch(
+            "UPDATE motor SET foto = '/data/lampiran/ddd.jpg' WHERE motor_id = 1;
+             UPDATE kerusakan SET foto = 'bukan json' WHERE kerusakan_id = 1;",
+        )
+        .unwrap();
+        assert_eq!(
+            referensi(&conn),
+            vec![("motor".into(), 1, "/data/lampiran/ddd.jpg".into())]
+        );
+
+        conn.execute("DELETE FROM motor WHERE motor_id = 1", [])
+            .unwrap();
+        assert!(referensi(&conn).is_empty());
+    }
+
+    #[test]
+    fn migrasi_membangun_ulang_referensi() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::migrations::migrate(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO dokumen_penyewa (penyewa_id, jenis, path, created_at)
+             VALUES (1, 'ktp', '/data/lampiran/eee.jpg', '2026-10-01');
+             DELETE FROM lampiran_referensi;",
+        )
+        .unwrap();
+
+        crate::migrations::migrate(&conn).unwrap();
+        assert_eq!(referensi(&conn).len(), 1);
+    }
+
+    #[test]
+    fn thumbnail_dari_catatan_lampiran() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::migrations::migrate(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO lampiran (hash, nama_file, mime, ukuran, created_at, thumbnail)
+             VALUES ('aaa', 'aaa.jpg', 'image/jpeg', 1, '2026-10-01 00:00:00', 'aaa.thumb.jpg'),
+                    ('bbb', 'bbb.pdf', 'application/pdf', 1, '2026-10-01 00:00:00', NULL);",
+        )
+        .unwrap();
+
+        let dir = Path::new("/data/lampiran");
+        assert_eq!(
+            thumbnail(&conn, &dir.join("aaa.jpg").to_string_lossy()).unwrap(),
+            Some(dir.join("aaa.thumb.jpg").to_string_lossy().to_string())
+        );
+        assert_eq!(thumbnail(&conn, "/data/lampiran/bbb.pdf").unwrap(), None);
+        assert_eq!(thumbnail(&conn, "/lama/foto.png").unwrap(), None);
+        assert_eq!(
+            thumbnail_opsional(&conn, &Some(String::new())).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn unggah_ulang_tidak_kalah_dengan_hapus_yatim() {
+        let _giliran = crate::paths::folder_data_uji();
+        crate::migrations::init_db().unwrap();
+        let isi = b"%PDF-1.4 lampiran uji hapus yatim";
+        let kedaluwarsa = || {
+            db::with_connection(|conn| {
+                conn.execute(
+                    "UPDATE lampiran SET created_at = '2000-01-01 00:00:00' WHERE nama_file LIKE '%.pdf'",
+                    [],
+                )
+            })
+            .unwrap();
+        };
+
+        // Unggah ulang lampiran lama yang belum dirujuk: masa tenggangnya mulai lagi
+        let path = simpan(isi).unwrap();
+        kedaluwarsa();
+        assert_eq!(simpan(isi).unwrap(), path);
+        assert_eq!(db::with_connection(hapus_yatim).unwrap(), 0);
+        assert!(Path::new(&path).exists());
+
+        // Sudah dibersihkan lebih dulu: file ditulis ulang, tidak tercatat tanpa file
+        kedaluwarsa();
+        assert_eq!(db::with_connection(hapus_yatim).unwrap(), 1);
+        assert!(!Path::new(&path).exists());
+        assert_eq!(simpan(isi).unwrap(), path);
+        assert_eq!(fs::read(&path).unwrap(), isi);
+    }
+}
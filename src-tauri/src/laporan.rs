@@ -0,0 +1,710 @@
+use crate::db;
+use crate::models::{
+    NilaiPenyewa, ProfitMotor, RiwayatPenyewa, UtilisasiArmada, UtilisasiTipe, UtilisasiTitik,
+};
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use rusqlite::{Connection, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// Periode laporan, tanggal_selesai ikut dihitung (inklusif)
+pub struct Periode {
+    pub mulai: NaiveDate,
+    pub selesai: NaiveDate,
+}
+
+impl Periode {
+    pub fn parse(tanggal_mulai: &str, tanggal_selesai: &str) -> Result<Self> {
+        let mulai = parse_tanggal(tanggal_mulai).ok_or_else(|| {
+            db::validation_error(format!("Tanggal mulai '{}' tidak valid", tanggal_mulai))
+        })?;
+        let selesai = parse_tanggal(tanggal_selesai).ok_or_else(|| {
+            db::validation_error(format!("Tanggal selesai '{}' tidak valid", tanggal_selesai))
+        })?;
+        if selesai < mulai {
+            return Err(db::validation_error(
+                "Tanggal selesai tidak boleh sebelum tanggal mulai",
+            ));
+        }
+        Ok(Periode { mulai, selesai })
+    }
+
+    pub fn jumlah_hari(&self) -> i64 {
+        (self.selesai - self.mulai).num_days() + 1
+    }
+
+    pub fn memuat(&self, tanggal: NaiveDate) -> bool {
+        tanggal >= self.mulai && tanggal <= self.selesai
+    }
+
+    fn mulai_str(&self) -> String {
+        self.mulai.format("%Y-%m-%d").to_string()
+    }
+
+    fn selesai_str(&self) -> String {
+        self.selesai.format("%Y-%m-%d").to_string()
+    }
+}
+
+pub fn parse_tanggal(s: &str) -> Option<NaiveDate> {
+    s.get(0..10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+}
+
+// Rentang hari motor terpakai oleh satu transaksi: [mulai, selesai).
+// Transaksi yang belum kembali dihitung sampai hari ini atau tanggal rencana, mana yang lebih akhir.
+pub struct Sewa {
+    pub motor_id: i32,
+    pub mulai: NaiveDate,
+    pub selesai: NaiveDate,
+    pub total_bayar: i64,
+    pub denda: i64,
+    pub biaya_tambahan: i64,
+}
+
+impl Sewa {
+    pub fn lama_hari(&self) -> i64 {
+        (self.selesai - self.mulai).num_days()
+    }
+
+    // Jumlah hari sewa yang jatuh di dalam periode
+    pub fn hari_dalam(&self, periode: &Periode) -> i64 {
+        let mulai = self.mulai.max(periode.mulai);
+        let selesai = self.selesai.min(periode.selesai + Duration::days(1));
+        (selesai - mulai).num_days().max(0)
+    }
+}
+
+// Semua transaksi yang rentang sewanya bersinggungan dengan periode
+pub fn get_sewa(conn: &Connection, periode: &Periode) -> Result<Vec<Sewa>> {
+    let hari_ini = Local::now().date_naive();
+
+    let mut stmt = conn.prepare(
+        "SELECT t.motor_id, t.tanggal_sewa, t.tanggal_kembali_rencana, t.tanggal_kembali_aktual,
+                COALESCE(t.total_bayar, 0), COALESCE(t.denda, 0),
+                COALESCE(t.biaya_km_lebih, 0) + COALESCE(t.biaya_bbm, 0)
+                    + COALESCE((SELECT SUM(k.biaya_dibebankan) FROM kerusakan k WHERE k.transaksi_id = t.transaksi_id), 0)
+         FROM transaksi t
+         WHERE t.motor_id IS NOT NULL AND substr(t.tanggal_sewa, 1, 10) <= ?1",
+    )?;
+
+    let mut rows = stmt.query([periode.selesai_str()])?;
+    let mut result = Vec::new();
+    while let Some(row) = rows.next()? {
+        let tanggal_sewa: String = row.get::<_, Option<String>>(1)?.unwrap_or_default();
+        let Some(mulai) = parse_tanggal(&tanggal_sewa) else {
+            continue;
+        };
+        let rencana = row
+            .get::<_, Option<String>>(2)?
+            .as_deref()
+            .and_then(parse_tanggal);
+        let aktual = row
+            .get::<_, Option<String>>(3)?
+            .as_deref()
+            .and_then(parse_tanggal);
+        let selesai = match aktual {
+            Some(aktual) => aktual,
+            None => rencana.unwrap_or(hari_ini).max(hari_ini),
+        };
+
+        let sewa = Sewa {
+            motor_id: row.get(0)?,
+            mulai,
+            // Sewa di hari yang sama tetap dihitung satu hari
+            selesai: selesai.max(mulai + Duration::days(1)),
+            total_bayar: row.get(4)?,
+            denda: row.get(5)?,
+            biaya_tambahan: row.get(6)?,
+        };
+        if sewa.hari_dalam(periode) > 0 || periode.memuat(sewa.mulai) {
+            result.push(sewa);
+        }
+    }
+    Ok(result)
+}
+
+// Laba per motor dalam periode, diurutkan dari laba bersih terbesar.
+// Pendapatan diakui pada tanggal sewa; biaya pada tanggal servis/pengeluaran.
+pub fn get_profit_motor(conn: &Connection, periode: &Periode) -> Result<Vec<ProfitMotor>> {
+    let hari_tersedia = periode.jumlah_hari();
+
+    let mut hasil: HashMap<i32, ProfitMotor> = db::get_all_motor(conn)?
+        .into_iter()
+        .map(|m| {
+            (
+                m.motor_id,
+                ProfitMotor {
+                    peringkat: 0,
+                    motor_id: m.motor_id,
+                    nama: m.nama,
+                    plat: m.plat,
+                    tipe_motor: m.tipe_motor,
+                    jumlah_transaksi: 0,
+                    pendapatan_sewa: 0,
+                    denda: 0,
+                    biaya_tambahan: 0,
+                    total_pendapatan: 0,
+                    diterima: 0,
+                    biaya_servis: 0,
+                    biaya_pengeluaran: 0,
+                    total_biaya: 0,
+                    hari_disewa: 0,
+                    hari_tersedia,
+                    utilisasi: 0.0,
+                    laba_bersih: 0,
+                },
+            )
+        })
+        .collect();
+
+    for sewa in get_sewa(conn, periode)? {
+        let Some(p) = hasil.get_mut(&sewa.motor_id) else {
+            continue;
+        };
+        p.hari_disewa += sewa.hari_dalam(periode);
+        if periode.memuat(sewa.mulai) {
+            p.jumlah_transaksi += 1;
+            p.pendapatan_sewa += sewa.total_bayar;
+            p.denda += sewa.denda;
+            p.biaya_tambahan += sewa.biaya_tambahan;
+        }
+    }
+
+    let (mulai, selesai) = (periode.mulai_str(), periode.selesai_str());
+    let jumlah_per_motor = |sql: &str| -> Result<Vec<(i32, i64)>> {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map((&mulai, &selesai), |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    };
+
+    for (motor_id, jumlah) in jumlah_per_motor(
+        "SELECT t.motor_id, SUM(b.jumlah_bayar) FROM bukti_pelunasan b
+         JOIN transaksi t ON t.transaksi_id = b.transaksi_id
+         WHERE substr(b.tanggal_bayar, 1, 10) BETWEEN ?1 AND ?2
+         GROUP BY t.motor_id",
+    )? {
+        if let Some(p) = hasil.get_mut(&motor_id) {
+            p.diterima = jumlah;
+        }
+    }
+
+    for (motor_id, jumlah) in jumlah_per_motor(
+        "SELECT motor_id, SUM(biaya) FROM servis_motor
+         WHERE substr(tanggal, 1, 10) BETWEEN ?1 AND ?2
+         GROUP BY motor_id",
+    )? {
+        if let Some(p) = hasil.get_mut(&motor_id) {
+            p.biaya_servis = jumlah;
+        }
+    }
+
+    // Pengeluaran yang dibuat otomatis dari servis sudah terhitung di biaya_servis
+    for (motor_id, jumlah) in jumlah_per_motor(
+        "SELECT motor_id, SUM(nominal) FROM pengeluaran_rental
+         WHERE motor_id IS NOT NULL
+           AND substr(tanggal, 1, 10) BETWEEN ?1 AND ?2
+           AND pengeluaran_id NOT IN (SELECT pengeluaran_id FROM servis_motor WHERE pengeluaran_id IS NOT NULL)
+         GROUP BY motor_id",
+    )? {
+        if let Some(p) = hasil.get_mut(&motor_id) {
+            p.biaya_pengeluaran = jumlah;
+        }
+    }
+
+    let mut hasil: Vec<ProfitMotor> = hasil
+        .into_values()
+        .map(|mut p| {
+            p.total_pendapatan = p.pendapatan_sewa + p.denda + p.biaya_tambahan;
+            p.total_biaya = p.biaya_servis + p.biaya_pengeluaran;
+            p.laba_bersih = p.total_pendapatan - p.total_biaya;
+            p.hari_disewa = p.hari_disewa.min(hari_tersedia);
+            p.utilisasi = persen(p.hari_disewa, hari_tersedia);
+            p
+        })
+        .collect();
+
+    hasil.sort_by(|a, b| {
+        b.laba_bersih
+            .cmp(&a.laba_bersih)
+            .then(b.total_pendapatan.cmp(&a.total_pendapatan))
+            .then(a.motor_id.cmp(&b.motor_id))
+    });
+    for (i, p) in hasil.iter_mut().enumerate() {
+        p.peringkat = i + 1;
+    }
+
+    Ok(hasil)
+}
+
+// Persentase dibulatkan 2 desimal
+pub fn persen(bagian: i64, total: i64) -> f64 {
+    bagi(bagian * 100, total)
+}
+
+// Pembagian dibulatkan 2 desimal, 0 jika penyebut 0
+pub fn bagi(pembilang: i64, penyebut: i64) -> f64 {
+    if penyebut <= 0 {
+        return 0.0;
+    }
+    (pembilang as f64 * 100.0 / penyebut as f64).round() / 100.0
+}
+
+// Tingkat hunian armada: motor-hari disewa dibanding motor-hari tersedia,
+// lama sewa rata-rata dan RevPAR (pendapatan sewa per motor-hari tersedia).
+// Pendapatan sewa (total_bayar) dibagi rata ke tiap hari sewanya supaya
+// deret waktunya tidak menumpuk di tanggal sewa. Denda dan biaya tambahan tidak ikut.
+pub fn get_utilisasi_armada(conn: &Connection, periode: &Periode) -> Result<UtilisasiArmada> {
+    let tipe_motor: HashMap<i32, String> = db::get_all_motor(conn)?
+        .into_iter()
+        .map(|m| {
+            let tipe = m.tipe_motor.trim().to_string();
+            let tipe = if tipe.is_empty() {
+                "-".to_string()
+            } else {
+                tipe
+            };
+            (m.motor_id, tipe)
+        })
+        .collect();
+    let jumlah_motor = tipe_motor.len() as i64;
+    let jumlah_hari = periode.jumlah_hari();
+
+    // Motor yang disewa dan pendapatan per hari dalam periode
+    let mut disewa: Vec<HashSet<i32>> = vec![HashSet::new(); jumlah_hari as usize];
+    let mut pendapatan: Vec<i64> = vec![0; jumlah_hari as usize];
+
+    let mut per_tipe: BTreeMap<String, UtilisasiTipe> = BTreeMap::new();
+    for tipe in tipe_motor.values() {
+        let t = per_tipe
+            .entry(tipe.clone())
+            .or_insert_with(|| UtilisasiTipe {
+                tipe_motor: tipe.clone(),
+                jumlah_motor: 0,
+                motor_hari_tersedia: 0,
+                motor_hari_disewa: 0,
+                okupansi: 0.0,
+                jumlah_transaksi: 0,
+                rata_rata_lama_sewa: 0.0,
+                pendapatan: 0,
+                revpar: 0.0,
+            });
+        t.jumlah_motor += 1;
+        t.motor_hari_tersedia += jumlah_hari;
+    }
+
+    let mut jumlah_transaksi = 0;
+    let mut total_lama = 0;
+    let mut lama_per_tipe: HashMap<String, i64> = HashMap::new();
+
+    for sewa in get_sewa(conn, periode)? {
+        // Transaksi untuk motor yang sudah dihapus tidak ikut dihitung
+        let Some(tipe) = tipe_motor.get(&sewa.motor_id) else {
+            continue;
+        };
+        let lama = sewa.lama_hari();
+        let per_hari = sewa.total_bayar / lama;
+        let sisa = sewa.total_bayar - per_hari * lama;
+
+        for i in 0..lama {
+            let tanggal = sewa.mulai + Duration::days(i);
+            if !periode.memuat(tanggal) {
+                continue;
+            }
+            let idx = (tanggal - periode.mulai).num_days() as usize;
+            // Sisa pembagian dimasukkan ke hari terakhir
+            let nilai = per_hari + if i == lama - 1 { sisa } else { 0 };
+
+            disewa[idx].insert(sewa.motor_id);
+            pendapatan[idx] += nilai;
+            if let Some(t) = per_tipe.get_mut(tipe) {
+                t.pendapatan += nilai;
+            }
+        }
+
+        if periode.memuat(sewa.mulai) {
+            jumlah_transaksi += 1;
+            total_lama += lama;
+            if let Some(t) = per_tipe.get_mut(tipe) {
+                t.jumlah_transaksi += 1;
+                *lama_per_tipe.entry(tipe.clone()).or_default() += lama;
+            }
+        }
+    }
+
+    for motor in disewa.iter().flatten() {
+        if let Some(t) = per_tipe.get_mut(&tipe_motor[motor]) {
+            t.motor_hari_disewa += 1;
+        }
+    }
+
+    let titik = |tanggal: NaiveDate, hari: i64, motor_hari_disewa: i64, pendapatan: i64| {
+        let motor_hari_tersedia = jumlah_motor * hari;
+        UtilisasiTitik {
+            tanggal: tanggal.format("%Y-%m-%d").to_string(),
+            hari,
+            motor_hari_tersedia,
+            motor_hari_disewa,
+            okupansi: persen(motor_hari_disewa, motor_hari_tersedia),
+            pendapatan,
+            revpar: bagi(pendapatan, motor_hari_tersedia),
+        }
+    };
+
+    let harian: Vec<UtilisasiTitik> = (0..jumlah_hari)
+        .map(|i| {
+            let idx = i as usize;
+            titik(
+                periode.mulai + Duration::days(i),
+                1,
+                disewa[idx].len() as i64,
+                pendapatan[idx],
+            )
+        })
+        .collect();
+
+    // Minggu dimulai hari Senin
+    let mut mingguan: Vec<UtilisasiTitik> = Vec::new();
+    for (i, h) in harian.iter().enumerate() {
+        let tanggal = periode.mulai + Duration::days(i as i64);
+        if i == 0 || tanggal.weekday().num_days_from_monday() == 0 {
+            mingguan.push(titik(tanggal, 0, 0, 0));
+        }
+        if let Some(m) = mingguan.last_mut() {
+            m.hari += 1;
+            m.motor_hari_tersedia += h.motor_hari_tersedia;
+            m.motor_hari_disewa += h.motor_hari_disewa;
+            m.pendapatan += h.pendapatan;
+        }
+    }
+    for m in mingguan.iter_mut() {
+        m.okupansi = persen(m.motor_hari_disewa, m.motor_hari_tersedia);
+        m.revpar = bagi(m.pendapatan, m.motor_hari_tersedia);
+    }
+
+    let per_tipe: Vec<UtilisasiTipe> = per_tipe
+        .into_values()
+        .map(|mut t| {
+            t.okupansi = persen(t.motor_hari_disewa, t.motor_hari_tersedia);
+            t.revpar = bagi(t.pendapatan, t.motor_hari_tersedia);
+            t.rata_rata_lama_sewa = bagi(
+                lama_per_tipe.get(&t.tipe_motor).copied().unwrap_or(0),
+                t.jumlah_transaksi,
+            );
+            t
+        })
+        .collect();
+
+    let motor_hari_tersedia = jumlah_motor * jumlah_hari;
+    let motor_hari_disewa: i64 = harian.iter().map(|h| h.motor_hari_disewa).sum();
+    let total_pendapatan: i64 = pendapatan.iter().sum();
+
+    Ok(UtilisasiArmada {
+        tanggal_mulai: periode.mulai_str(),
+        tanggal_selesai: periode.selesai_str(),
+        jumlah_motor,
+        motor_hari_tersedia,
+        motor_hari_disewa,
+        okupansi: persen(motor_hari_disewa, motor_hari_tersedia),
+        jumlah_transaksi,
+        rata_rata_lama_sewa: bagi(total_lama, jumlah_transaksi),
+        pendapatan: total_pendapatan,
+        revpar: bagi(total_pendapatan, motor_hari_tersedia),
+        harian,
+        mingguan,
+        per_tipe,
+    })
+}
+
+pub fn profit_motor_ke_csv(data: &[ProfitMotor]) -> std::result::Result<Vec<u8>, String> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(b';')
+        .from_writer(Vec::new());
+
+    writer
+        .write_record([
+            "Peringkat",
+            "Motor",
+            "Plat",
+            "Tipe",
+            "Jumlah Transaksi",
+            "Pendapatan Sewa",
+            "Denda",
+            "Biaya Tambahan",
+            "Total Pendapatan",
+            "Diterima",
+            "Biaya Servis",
+            "Biaya Pengeluaran",
+            "Total Biaya",
+            "Hari Disewa",
+            "Hari Tersedia",
+            "Utilisasi (%)",
+            "Laba Bersih",
+        ])
+        .map_err(|e| e.to_string())?;
+
+    for p in data {
+        writer
+            .write_record([
+                p.peringkat.to_string(),
+                p.nama.clone(),
+                p.plat.clone(),
+                p.tipe_motor.clone(),
+                p.jumlah_transaksi.to_string(),
+                p.pendapatan_sewa.to_string(),
+                p.denda.to_string(),
+                p.biaya_tambahan.to_string(),
+                p.total_pendapatan.to_string(),
+                p.diterima.to_string(),
+                p.biaya_servis.to_string(),
+                p.biaya_pengeluaran.to_string(),
+                p.total_biaya.to_string(),
+                p.hari_disewa.to_string(),
+                p.hari_tersedia.to_string(),
+                format!("{:.2}", p.utilisasi).replace('.', ","),
+                p.laba_bersih.to_string(),
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    // BOM supaya Excel membaca UTF-8 dengan benar
+    bytes.splice(0..0, "\u{feff}".bytes());
+    Ok(bytes)
+}
+
+// Ringkasan tagihan per penyewa dalam satu query. Penyewa tanpa transaksi tidak ikut.
+// penyewa_id = None untuk semua penyewa, urut dari total belanja terbesar.
+fn query_nilai_penyewa(
+    conn: &Connection,
+    penyewa_id: Option<i32>,
+    limit: Option<usize>,
+) -> Result<Vec<NilaiPenyewa>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT p.penyewa_id, p.nama, p.no_hp, COUNT(*), SUM(x.total_tagihan), SUM(x.dibayar),
+                SUM(CASE WHEN x.status = 'terlambat' OR x.hari_terlambat > 0 THEN 1 ELSE 0 END),
+                SUM(x.denda), SUM(MAX(x.total_tagihan - x.dibayar, 0)), MAX(x.tanggal_sewa)
+         FROM penyewa p
+         JOIN ({}) x ON x.penyewa_id = p.penyewa_id
+         WHERE ?1 IS NULL OR p.penyewa_id = ?1
+         GROUP BY p.penyewa_id
+         ORDER BY SUM(x.total_tagihan) DESC, COUNT(*) DESC, p.penyewa_id
+         LIMIT ?2",
+        db::TAGIHAN_TRANSAKSI_SQL
+    ))?;
+    // LIMIT -1 berarti tanpa batas
+    let limit = limit.map_or(-1, |l| l as i64);
+    let rows = stmt.query_map((penyewa_id, limit), |row| {
+        Ok(NilaiPenyewa {
+            peringkat: 0,
+            penyewa_id: row.get(0)?,
+            nama: row.get(1)?,
+            no_hp: row.get(2)?,
+            jumlah_transaksi: row.get(3)?,
+            total_belanja: row.get(4)?,
+            total_dibayar: row.get(5)?,
+            jumlah_terlambat: row.get(6)?,
+            total_denda: row.get(7)?,
+            sisa_hutang: row.get(8)?,
+            sewa_terakhir: row.get(9)?,
+        })
+    })?;
+
+    rows.collect()
+}
+
+pub fn get_riwayat_penyewa(conn: &Connection, penyewa_id: i32) -> Result<RiwayatPenyewa> {
+    let penyewa = db::get_penyewa_by_id(conn, penyewa_id)?;
+    let transaksi = db::get_transaksi_by_penyewa(conn, penyewa_id)?;
+    let pembayaran = db::get_bukti_pelunasan_by_penyewa(conn, penyewa_id)?;
+    let ringkasan = match query_nilai_penyewa(conn, Some(penyewa_id), None)?.pop() {
+        Some(nilai) => nilai,
+        // Belum pernah menyewa
+        None => NilaiPenyewa {
+            peringkat: 0,
+            penyewa_id,
+            nama: penyewa.nama.clone(),
+            no_hp: penyewa.no_hp.clone(),
+            jumlah_transaksi: 0,
+            total_belanja: 0,
+            total_dibayar: 0,
+            jumlah_terlambat: 0,
+            total_denda: 0,
+            sisa_hutang: 0,
+            sewa_terakhir: None,
+        },
+    };
+
+    Ok(RiwayatPenyewa {
+        penyewa,
+        ringkasan,
+        transaksi,
+        pembayaran,
+    })
+}
+
+// Penyewa dengan total belanja terbesar. Penyewa tanpa transaksi tidak ikut.
+pub fn get_top_penyewa(conn: &Connection, limit: usize) -> Result<Vec<NilaiPenyewa>> {
+    let mut hasil = query_nilai_penyewa(conn, None, Some(limit))?;
+    for (i, n) in hasil.iter_mut().enumerate() {
+        n.peringkat = i + 1;
+    }
+    Ok(hasil)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn periode_hanya_memakai_tanggal() {
+        let periode = Periode::parse("2026-01-01/../../rahasia", "2026-01-31").unwrap();
+        assert_eq!(periode.mulai.format("%Y-%m-%d").to_string(), "2026-01-01");
+        assert_eq!(periode.jumlah_hari(), 31);
+        assert!(Periode::parse("../2026-01-01", "2026-01-31").is_err());
+        assert!(Periode::parse("2026-02-01", "2026-01-31").is_err());
+    }
+
+    #[test]
+    fn utilisasi_armada_dari_fixture() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::migrations::migrate(&conn).unwrap();
+        // 1 Maret 2026 hari Minggu: minggu pertama cuma sehari, lalu Senin 2 dan 9 Maret
+        conn.execute_batch(
+            "INSERT INTO motor (motor_id, nama, foto, plat, tipe_motor, tahun, harga_harian, status) VALUES
+                 (1, 'Beat', '', 'H 1 A', 'Matic', '2022', 75000, 'tersedia'),
+                 (2, 'Vario', '', 'H 2 A', ' Matic ', '2023', 90000, 'tersedia'),
+                 (3, 'CBR', '', 'H 3 A', 'Sport', '2021', 150000, 'tersedia');
+             INSERT INTO transaksi (transaksi_id, motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana, tanggal_kembali_aktual, total_bayar, status, denda) VALUES
+                 -- Mulai sebelum periode: 4 hari x 25.000, hanya 1-2 Maret yang masuk
+                 (1, 1, 1, '2026-02-27', '2026-03-03', '2026-03-03', 100000, 'kembali', 0),
+                 -- 3 hari x 33.333, sisa 1 rupiah masuk ke hari terakhir (4 Maret)
+                 (2, 2, 1, '2026-03-02 08:00:00', '2026-03-05', '2026-03-05 17:00:00', 100000, 'kembali', 0),
+                 -- Kembali di hari yang sama tetap dihitung satu hari
+                 (3, 3, 1, '2026-03-09', '2026-03-09', '2026-03-09', 70000, 'kembali', 0),
+                 -- Lewat akhir periode: 3 hari x 30.000, dendanya tidak ikut pendapatan
+                 (4, 1, 1, '2026-03-13', '2026-03-16', '2026-03-16', 90000, 'kembali', 20000),
+                 -- Motor sudah dihapus dan sewa sebelum periode tidak dihitung
+                 (5, 99, 1, '2026-03-05', '2026-03-07', '2026-03-07', 50000, 'kembali', 0),
+                 (6, 3, 1, '2026-02-01', '2026-02-03', '2026-02-03', 300000, 'kembali', 0);",
+        )
+        .unwrap();
+
+        let periode = Periode::parse("2026-03-01", "2026-03-14").unwrap();
+        let u = get_utilisasi_armada(&conn, &periode).unwrap();
+
+        assert_eq!(u.jumlah_motor, 3);
+        assert_eq!(u.motor_hari_tersedia, 42);
+        assert_eq!(u.motor_hari_disewa, 8);
+        assert_eq!(u.okupansi, 19.05);
+        assert_eq!(u.pendapatan, 50000 + 100000 + 70000 + 60000);
+        assert_eq!(u.revpar, 6666.67);
+        assert_eq!(u.jumlah_transaksi, 3);
+        assert_eq!(u.rata_rata_lama_sewa, 2.33);
+
+        let harian: Vec<(i64, i64)> = u
+            .harian
+            .iter()
+            .map(|h| (h.motor_hari_disewa, h.pendapatan))
+            .collect();
+        assert_eq!(u.harian.len(), 14);
+        assert_eq!(
+            harian[..5],
+            [(1, 25000), (2, 58333), (1, 33333), (1, 33334), (0, 0)]
+        );
+        assert_eq!(harian[8], (1, 70000));
+        assert_eq!(harian[12..], [(1, 30000), (1, 30000)]);
+        assert_eq!(u.harian[1].okupansi, 66.67);
+        assert_eq!(u.harian[1].revpar, 19444.33);
+
+        let mingguan: Vec<_> = u
+            .mingguan
+            .iter()
+            .map(|m| {
+                (
+                    m.tanggal.as_str(),
+                    m.hari,
+                    m.motor_hari_tersedia,
+                    m.motor_hari_disewa,
+                    m.pendapatan,
+                    m.okupansi,
+                    m.revpar,
+                )
+            })
+            .collect();
+        assert_eq!(
+            mingguan,
+            vec![
+                ("2026-03-01", 1, 3, 1, 25000, 33.33, 8333.33),
+                ("2026-03-02", 7, 21, 4, 125000, 19.05, 5952.38),
+                ("2026-03-09", 6, 18, 3, 130000, 16.67, 7222.22),
+            ]
+        );
+
+        let per_tipe: Vec<_> = u
+            .per_tipe
+            .iter()
+            .map(|t| {
+                (
+                    t.tipe_motor.as_str(),
+                    t.jumlah_motor,
+                    t.motor_hari_tersedia,
+                    t.motor_hari_disewa,
+                    t.okupansi,
+                    t.jumlah_transaksi,
+                    t.rata_rata_lama_sewa,
+                    t.pendapatan,
+                    t.revpar,
+                )
+            })
+            .collect();
+        assert_eq!(
+            per_tipe,
+            vec![
+                ("Matic", 2, 28, 7, 25.0, 2, 3.0, 210000, 7500.0),
+                ("Sport", 1, 14, 1, 7.14, 1, 1.0, 70000, 5000.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn top_penyewa_dari_satu_query() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::migrations::migrate(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO penyewa (penyewa_id, nama, no_hp, no_ktp, alamat) VALUES
+                 (1, 'Ani', '081', '1', '-'), (2, 'Budi', '082', '2', '-'), (3, 'Cici', '083', '3', '-');
+             INSERT INTO transaksi (transaksi_id, motor_id, penyewa_id, tanggal_sewa, tanggal_kembali_rencana, total_bayar, status, denda, hari_terlambat, biaya_bbm) VALUES
+                 (1, 1, 1, '2026-01-01', '2026-01-02', 100000, 'kembali', 0, 0, 0),
+                 (2, 1, 1, '2026-02-01', '2026-02-02', 100000, 'terlambat', 20000, 1, 10000),
+                 (3, 1, 2, '2026-01-05', '2026-01-06', 150000, 'kembali', 0, 0, 0);
+             INSERT INTO kerusakan (transaksi_id, motor_id, tanggal, deskripsi, estimasi_biaya, biaya_dibebankan) VALUES
+                 (2, 1, '2026-02-02', 'spion', 50000, 30000);
+             INSERT INTO bukti_pelunasan (transaksi_id, tanggal_bayar, jumlah_bayar, metode_bayar, foto_bukti) VALUES
+                 (1, '2026-01-02', 100000, 'tunai', ''), (3, '2026-01-06', 200000, 'tunai', '');",
+        )
+        .unwrap();
+
+        let top = get_top_penyewa(&conn, 10).unwrap();
+        assert_eq!(top.len(), 2);
+        let ani = &top[0];
+        assert_eq!((ani.peringkat, ani.penyewa_id), (1, 1));
+        assert_eq!(ani.jumlah_transaksi, 2);
+        assert_eq!(ani.total_belanja, 100000 + 100000 + 20000 + 10000 + 30000);
+        assert_eq!(ani.total_dibayar, 100000);
+        assert_eq!(ani.total_denda, 20000);
+        assert_eq!(ani.jumlah_terlambat, 1);
+        assert_eq!(ani.sisa_hutang, 160000);
+        assert_eq!(ani.sewa_terakhir.as_deref(), Some("2026-02-01"));
+        // Kelebihan bayar tidak mengurangi hutang transaksi lain
+        assert_eq!(top[1].sisa_hutang, 0);
+
+        assert_eq!(get_top_penyewa(&conn, 1).unwrap().len(), 1);
+        assert_eq!(
+            get_riwayat_penyewa(&conn, 3)
+                .unwrap()
+                .ringkasan
+                .jumlah_transaksi,
+            0
+        );
+    }
+}
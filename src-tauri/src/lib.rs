@@ -1,14 +1,10 @@
-// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-#[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {}! You've been greeted from Rust!", name)
-}
-
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
+// Inti aplikasi tanpa command Tauri: database, migrasi, backup/enkripsi, lampiran,
+// penomoran dan folder data. Dipakai main.rs dan juga examples/ (mis. bench_dashboard).
+pub mod backup;
+pub mod db;
+pub mod enkripsi;
+pub mod lampiran;
+pub mod migrations;
+pub mod models;
+pub mod paths;
+pub mod penomoran;
@@ -12,11 +12,7 @@ const SECRET_KEY: &str = "rent_motor_secret_key_v1_secure_8823";
 type HmacSha256 = Hmac<Sha256>;
 
 fn get_license_file_path() -> PathBuf {
-    let mut path = PathBuf::from(std::env::var("APPDATA").expect("APPDATA not found"));
-    path.push("AplikasiPembukuan");
-    fs::create_dir_all(&path).ok();
-    path.push("license.dat");
-    path
+    crate::paths::license_path()
 }
 
 #[command]
@@ -2,36 +2,65 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod commands;
-mod db;
 mod handlers;
+mod laporan;
 mod license;
-mod migrations;
-mod models;
+mod pengingat;
+mod scheduler;
+
+use aplikasipembukuan_lib::{backup, db, enkripsi, lampiran, migrations, models, paths, penomoran};
 
 fn main() {
-    migrations::init_db().expect("init db gagal");
+    backup::bersihkan_file_sementara();
+
+    // Database terenkripsi disiapkan setelah dibuka lewat buka_kunci_database
+    if db::terkunci() {
+        println!("🔒 Database terenkripsi, menunggu kata sandi...");
+    } else {
+        migrations::siapkan_database().expect("init db gagal");
+    }
 
     tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_notification::init())
+        .setup(|app| {
+            scheduler::start(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             handlers::motor::get_all_motor,
             handlers::motor::create_motor,
             handlers::motor::update_motor,
             handlers::motor::delete_motor,
             handlers::motor::get_motor_by_id,
-            handlers::motor::save_motor_image,
             handlers::penyewa::get_all_penyewa,
             handlers::penyewa::create_penyewa,
             handlers::penyewa::get_penyewa_by_id,
             handlers::penyewa::update_penyewa,
             handlers::penyewa::delete_penyewa,
+            handlers::penyewa::get_penyewa_riwayat,
+            handlers::penyewa::get_top_penyewa,
+            handlers::dokumen_penyewa::get_dokumen_penyewa,
+            handlers::dokumen_penyewa::save_dokumen_penyewa,
+            handlers::dokumen_penyewa::delete_dokumen_penyewa,
+            handlers::penyewa_flag::get_all_penyewa_flag,
+            handlers::penyewa_flag::create_penyewa_flag,
+            handlers::penyewa_flag::update_penyewa_flag,
+            handlers::penyewa_flag::delete_penyewa_flag,
+            handlers::penyewa_flag::cek_penyewa_flag,
             handlers::transaksi::get_all_transaksi,
             handlers::transaksi::create_transaksi,
             handlers::transaksi::get_transaksi_by_id,
             handlers::transaksi::update_transaksi,
             handlers::transaksi::delete_transaksi,
-            handlers::transaksi::save_transaksi_image,
+            handlers::transaksi::periksa_keterlambatan,
+            handlers::transaksi::get_transaksi_terlambat_terakhir,
+            handlers::transaksi::get_tagihan_transaksi,
+            handlers::transaksi::get_all_tagihan,
+            handlers::whatsapp::render_pesan_wa,
+            handlers::whatsapp::kirim_pesan_wa,
             // License Commands
             license::get_machine_id,
             license::verify_license,
@@ -42,21 +71,67 @@ fn main() {
             handlers::bukti_pelunasan::get_bukti_pelunasan_by_id,
             handlers::bukti_pelunasan::update_bukti_pelunasan,
             handlers::bukti_pelunasan::delete_bukti_pelunasan,
-            handlers::bukti_pelunasan::save_bukti_pelunasan_image,
             handlers::pengeluaran_rental::get_all_pengeluaran_rental,
             handlers::pengeluaran_rental::create_pengeluaran_rental,
             handlers::pengeluaran_rental::get_pengeluaran_rental_by_id,
             handlers::pengeluaran_rental::update_pengeluaran_rental,
             handlers::pengeluaran_rental::delete_pengeluaran_rental,
+            handlers::dokumen_motor::get_all_dokumen_motor,
+            handlers::dokumen_motor::create_dokumen_motor,
+            handlers::dokumen_motor::get_dokumen_motor_by_id,
+            handlers::dokumen_motor::update_dokumen_motor,
+            handlers::dokumen_motor::delete_dokumen_motor,
+            handlers::dokumen_motor::get_dokumen_motor_akan_kadaluarsa,
+            handlers::lampiran::bersihkan_lampiran,
+            handlers::lampiran::unggah_lampiran,
+            handlers::lampiran::impor_lampiran,
+            handlers::laporan::get_laporan_profit_motor,
+            handlers::laporan::ekspor_laporan_profit_motor,
+            handlers::laporan::get_laporan_utilisasi,
+            handlers::kerusakan::get_all_kerusakan,
+            handlers::kerusakan::create_kerusakan,
+            handlers::kerusakan::get_kerusakan_by_id,
+            handlers::kerusakan::update_kerusakan,
+            handlers::kerusakan::delete_kerusakan,
+            handlers::servis_motor::get_all_servis_motor,
+            handlers::servis_motor::create_servis_motor,
+            handlers::servis_motor::get_servis_motor_by_id,
+            handlers::servis_motor::update_servis_motor,
+            handlers::servis_motor::delete_servis_motor,
+            handlers::servis_motor::get_motor_perlu_servis,
             commands::open_folder,
             commands::save_file,
             commands::get_downloads_path,
             commands::get_db_path_string,
             commands::backup_database,
             commands::import_database,
+            commands::cek_backup_terenkripsi,
+            handlers::backup::get_backup_history,
+            handlers::backup::jalankan_backup_sekarang,
+            handlers::backup::atur_sandi_backup_otomatis,
+            handlers::backup::cek_sandi_backup_otomatis,
+            handlers::backup::get_info_restore_terakhir,
+            handlers::backup::batalkan_restore_terakhir,
+            handlers::keamanan::get_status_enkripsi_database,
+            handlers::keamanan::buka_kunci_database,
+            handlers::keamanan::aktifkan_enkripsi_database,
             handlers::pengaturan::get_pengaturan,
             handlers::pengaturan::set_pengaturan,
+            handlers::pengaturan::preview_nomor_dokumen,
+            handlers::impor::get_header_impor,
+            handlers::impor::preview_impor,
+            handlers::impor::jalankan_impor,
+            handlers::pengingat::get_all_pengingat,
+            handlers::pengingat::count_pengingat_belum_dibaca,
+            handlers::pengingat::tandai_pengingat_dibaca,
+            handlers::pengingat::tandai_semua_pengingat_dibaca,
+            handlers::pengingat::delete_pengingat,
         ])
-        .run(tauri::generate_context!())
-        .expect("error running tauri app");
+        .build(tauri::generate_context!())
+        .expect("error building tauri app")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                scheduler::backup_saat_keluar();
+            }
+        });
 }
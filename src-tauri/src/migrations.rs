@@ -1,9 +1,26 @@
-use crate::db::get_connection;
-use rusqlite::Result;
+use crate::db::with_connection;
+use rusqlite::{Connection, Result};
+
+// Versi skema database, disimpan di PRAGMA user_version dan di manifest backup.
+// Naikkan setiap kali ada migration baru.
+pub const SCHEMA_VERSION: i32 = 2;
+
+// Dijalankan saat start, atau setelah database terenkripsi dibuka dengan kata sandi
+pub fn siapkan_database() -> Result<()> {
+    init_db()?;
+    if let Err(e) = crate::paths::migrasi_folder_lama() {
+        eprintln!("❌ Error migrasi folder data: {}", e);
+    }
+    with_connection(crate::backup::pindahkan_sandi_lama)
+}
 
 pub fn init_db() -> Result<()> {
-    let conn = get_connection()?;
+    with_connection(migrate)
+}
 
+// Buat tabel yang belum ada dan jalankan semua migration. Idempotent, juga dipakai
+// untuk menaikkan database hasil restore dari backup versi lama.
+pub fn migrate(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         "
         CREATE TABLE IF NOT EXISTS motor (
@@ -72,6 +89,94 @@ pub fn init_db() -> Result<()> {
             foto_bukti TEXT
         );
 
+        CREATE TABLE IF NOT EXISTS nomor_dokumen (
+            jenis TEXT NOT NULL,
+            periode TEXT NOT NULL,
+            nomor_terakhir INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (jenis, periode)
+        );
+
+        CREATE TABLE IF NOT EXISTS pengingat (
+            pengingat_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            jenis TEXT NOT NULL,
+            ref_id INTEGER,
+            judul TEXT NOT NULL,
+            pesan TEXT NOT NULL,
+            tanggal TEXT NOT NULL,
+            dibaca INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            UNIQUE (jenis, ref_id, tanggal)
+        );
+
+        CREATE TABLE IF NOT EXISTS servis_motor (
+            servis_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            motor_id INTEGER NOT NULL,
+            tanggal TEXT NOT NULL,
+            jenis TEXT NOT NULL,
+            odometer INTEGER,
+            biaya INTEGER NOT NULL DEFAULT 0,
+            suku_cadang TEXT,
+            keterangan TEXT,
+            servis_berikut_tanggal TEXT,
+            servis_berikut_km INTEGER,
+            pengeluaran_id INTEGER
+        );
+
+        CREATE TABLE IF NOT EXISTS kerusakan (
+            kerusakan_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            transaksi_id INTEGER NOT NULL,
+            motor_id INTEGER NOT NULL,
+            tanggal TEXT NOT NULL,
+            deskripsi TEXT NOT NULL,
+            foto TEXT,
+            estimasi_biaya INTEGER NOT NULL DEFAULT 0,
+            biaya_dibebankan INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS dokumen_motor (
+            dokumen_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            motor_id INTEGER NOT NULL,
+            jenis TEXT NOT NULL,
+            nomor TEXT NOT NULL,
+            tanggal_kadaluarsa TEXT NOT NULL,
+            foto TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS penyewa_flag (
+            flag_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            no_ktp TEXT NOT NULL UNIQUE,
+            status TEXT NOT NULL,
+            alasan TEXT NOT NULL,
+            tanggal TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS dokumen_penyewa (
+            dokumen_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            penyewa_id INTEGER NOT NULL,
+            jenis TEXT NOT NULL,
+            path TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS lampiran (
+            hash TEXT PRIMARY KEY,
+            nama_file TEXT NOT NULL,
+            mime TEXT NOT NULL,
+            ukuran INTEGER NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS backup_history (
+            backup_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            waktu TEXT NOT NULL,
+            pemicu TEXT NOT NULL,
+            path TEXT NOT NULL,
+            ukuran INTEGER NOT NULL DEFAULT 0,
+            jumlah_file INTEGER NOT NULL DEFAULT 0,
+            status TEXT NOT NULL,
+            pesan TEXT
+        );
+
         CREATE TABLE IF NOT EXISTS pengeluaran_rental (
             pengeluaran_id INTEGER PRIMARY KEY AUTOINCREMENT,
             tanggal TEXT,
@@ -107,5 +212,67 @@ pub fn init_db() -> Result<()> {
         [],
     );
 
+    // Migration: tautkan pengeluaran_rental ke motor (mis. biaya servis)
+    let _ = conn.execute(
+        "ALTER TABLE pengeluaran_rental ADD COLUMN motor_id INTEGER",
+        [],
+    );
+
+    // Migration: odometer motor dan km saat ambil/kembali
+    let _ = conn.execute(
+        "ALTER TABLE motor ADD COLUMN odometer INTEGER DEFAULT 0",
+        [],
+    );
+    for kolom in ["km_awal", "km_akhir", "biaya_km_lebih"] {
+        let _ = conn.execute(
+            &format!("ALTER TABLE transaksi ADD COLUMN {} INTEGER", kolom),
+            [],
+        );
+    }
+    conn.execute(
+        "UPDATE motor SET odometer = MAX(COALESCE(odometer, 0), COALESCE((SELECT MAX(odometer) FROM servis_motor s WHERE s.motor_id = motor.motor_id), 0))",
+        [],
+    )?;
+
+    // Migration: level BBM (persen) saat ambil/kembali dan biaya isi ulang
+    for kolom in ["bbm_awal", "bbm_akhir", "biaya_bbm"] {
+        let _ = conn.execute(
+            &format!("ALTER TABLE transaksi ADD COLUMN {} INTEGER", kolom),
+            [],
+        );
+    }
+
+    // Migration: nomor dokumen persisten untuk transaksi, pembayaran dan pengeluaran
+    for tabel in ["transaksi", "bukti_pelunasan", "pengeluaran_rental"] {
+        let _ = conn.execute(&format!("ALTER TABLE {} ADD COLUMN nomor TEXT", tabel), []);
+    }
+    crate::penomoran::isi_nomor_kosong(conn)?;
+    conn.execute_batch(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_transaksi_nomor ON transaksi(nomor);
+         CREATE UNIQUE INDEX IF NOT EXISTS idx_bukti_pelunasan_nomor ON bukti_pelunasan(nomor);
+         CREATE UNIQUE INDEX IF NOT EXISTS idx_pengeluaran_rental_nomor ON pengeluaran_rental(nomor);",
+    )?;
+
+    // Migration: samakan penulisan jenis dokumen motor (dulu teks bebas)
+    for jenis in crate::db::JENIS_DOKUMEN_MOTOR {
+        conn.execute(
+            "UPDATE dokumen_motor SET jenis = ?1 WHERE lower(trim(jenis)) = lower(?1)",
+            [jenis],
+        )?;
+    }
+
+    // Migration: nama file thumbnail dicatat di lampiran, bukan dicek ke disk setiap kali dibaca
+    if conn
+        .execute("ALTER TABLE lampiran ADD COLUMN thumbnail TEXT", [])
+        .is_ok()
+    {
+        crate::lampiran::isi_thumbnail_lama(conn)?;
+    }
+
+    // Migration: catatan rujukan lampiran untuk pembersihan file yatim
+    crate::lampiran::siapkan_referensi(conn)?;
+
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
     Ok(())
 }
@@ -9,7 +9,12 @@ pub struct Motor {
     pub tahun: String,
     pub harga_harian: i32,
     pub foto: String,
+    // Diisi saat membaca data, diabaikan saat menyimpan
+    #[serde(default)]
+    pub foto_thumbnail: Option<String>,
     pub status: String,
+    #[serde(default)]
+    pub odometer: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -35,6 +40,22 @@ pub struct Transaksi {
     pub denda: Option<i64>,
     pub diskon: Option<i64>,
     pub foto_bukti: Option<String>,
+    #[serde(default)]
+    pub foto_bukti_thumbnail: Option<String>,
+    #[serde(default)]
+    pub nomor: Option<String>,
+    #[serde(default)]
+    pub km_awal: Option<i64>,
+    #[serde(default)]
+    pub km_akhir: Option<i64>,
+    #[serde(default)]
+    pub biaya_km_lebih: Option<i64>,
+    #[serde(default)]
+    pub bbm_awal: Option<i64>,
+    #[serde(default)]
+    pub bbm_akhir: Option<i64>,
+    #[serde(default)]
+    pub biaya_bbm: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -45,6 +66,10 @@ pub struct BuktiPelunasan {
     pub jumlah_bayar: i64,
     pub metode_bayar: String,
     pub foto_bukti: String,
+    #[serde(default)]
+    pub foto_bukti_thumbnail: Option<String>,
+    #[serde(default)]
+    pub nomor: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -55,4 +80,295 @@ pub struct PengeluaranRental {
     pub nominal: i64,
     pub keterangan: String,
     pub sumber_dana: Option<String>,
+    #[serde(default)]
+    pub nomor: Option<String>,
+    #[serde(default)]
+    pub motor_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ImporBaris {
+    pub baris: usize,
+    pub status: String,
+    pub pesan: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ImporLaporan {
+    pub jenis: String,
+    pub dry_run: bool,
+    pub total_baris: usize,
+    pub valid: usize,
+    pub duplikat: usize,
+    pub gagal: usize,
+    pub diimpor: usize,
+    pub baris: Vec<ImporBaris>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TransaksiTerlambat {
+    pub transaksi_id: i32,
+    pub nomor: Option<String>,
+    pub penyewa: String,
+    pub no_hp: String,
+    pub motor: String,
+    pub plat: String,
+    pub tanggal_kembali_rencana: String,
+    pub hari_terlambat: i64,
+    pub denda: i64,
+    pub baru_terlambat: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Pengingat {
+    pub pengingat_id: i32,
+    pub jenis: String,
+    pub ref_id: Option<i32>,
+    pub judul: String,
+    pub pesan: String,
+    pub tanggal: String,
+    pub dibaca: bool,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Tagihan {
+    pub transaksi_id: i32,
+    pub total_bayar: i64,
+    pub denda: i64,
+    pub biaya_km_lebih: i64,
+    pub biaya_bbm: i64,
+    pub biaya_kerusakan: i64,
+    pub total_tagihan: i64,
+    pub dibayar: i64,
+    pub sisa_bayar: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PesanWa {
+    pub transaksi_id: i32,
+    pub no_hp: String,
+    pub pesan: String,
+    pub link: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ServisMotor {
+    pub servis_id: i32,
+    pub motor_id: i32,
+    pub tanggal: String,
+    pub jenis: String,
+    pub odometer: Option<i64>,
+    pub biaya: i64,
+    pub suku_cadang: Option<String>,
+    pub keterangan: Option<String>,
+    pub servis_berikut_tanggal: Option<String>,
+    pub servis_berikut_km: Option<i64>,
+    pub pengeluaran_id: Option<i32>,
+    // Jika true, biaya servis ikut dicatat sebagai pengeluaran_rental
+    #[serde(default)]
+    pub catat_pengeluaran: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MotorPerluServis {
+    pub motor_id: i32,
+    pub nama: String,
+    pub plat: String,
+    pub status: String,
+    pub servis_terakhir: Option<String>,
+    pub servis_berikut_tanggal: Option<String>,
+    pub servis_berikut_km: Option<i64>,
+    pub odometer: Option<i64>,
+    pub alasan: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Kerusakan {
+    pub kerusakan_id: i32,
+    pub transaksi_id: i32,
+    #[serde(default)]
+    pub motor_id: i32,
+    pub tanggal: String,
+    pub deskripsi: String,
+    #[serde(default)]
+    pub foto: Vec<String>,
+    #[serde(default)]
+    pub foto_thumbnail: Vec<Option<String>>,
+    pub estimasi_biaya: i64,
+    pub biaya_dibebankan: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DokumenMotor {
+    pub dokumen_id: i32,
+    pub motor_id: i32,
+    pub jenis: String,
+    pub nomor: String,
+    pub tanggal_kadaluarsa: String,
+    pub foto: Option<String>,
+    #[serde(default)]
+    pub foto_thumbnail: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DokumenMotorKadaluarsa {
+    pub dokumen_id: i32,
+    pub motor_id: i32,
+    pub motor: String,
+    pub plat: String,
+    pub jenis: String,
+    pub nomor: String,
+    pub tanggal_kadaluarsa: String,
+    // Negatif berarti sudah lewat
+    pub sisa_hari: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ProfitMotor {
+    pub peringkat: usize,
+    pub motor_id: i32,
+    pub nama: String,
+    pub plat: String,
+    pub tipe_motor: String,
+    pub jumlah_transaksi: i64,
+    pub pendapatan_sewa: i64,
+    pub denda: i64,
+    // Biaya km lebih + BBM + kerusakan yang dibebankan ke penyewa
+    pub biaya_tambahan: i64,
+    pub total_pendapatan: i64,
+    // Pembayaran (bukti pelunasan) yang masuk dalam periode
+    pub diterima: i64,
+    pub biaya_servis: i64,
+    pub biaya_pengeluaran: i64,
+    pub total_biaya: i64,
+    pub hari_disewa: i64,
+    pub hari_tersedia: i64,
+    pub utilisasi: f64,
+    pub laba_bersih: i64,
+}
+
+// Satu titik deret waktu utilisasi (harian atau mingguan)
+#[derive(Serialize, Deserialize)]
+pub struct UtilisasiTitik {
+    // Tanggal awal rentang (untuk mingguan: hari Senin, dipotong awal periode)
+    pub tanggal: String,
+    pub hari: i64,
+    pub motor_hari_tersedia: i64,
+    pub motor_hari_disewa: i64,
+    pub okupansi: f64,
+    pub pendapatan: i64,
+    pub revpar: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UtilisasiTipe {
+    pub tipe_motor: String,
+    pub jumlah_motor: i64,
+    pub motor_hari_tersedia: i64,
+    pub motor_hari_disewa: i64,
+    pub okupansi: f64,
+    pub jumlah_transaksi: i64,
+    pub rata_rata_lama_sewa: f64,
+    pub pendapatan: i64,
+    pub revpar: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UtilisasiArmada {
+    pub tanggal_mulai: String,
+    pub tanggal_selesai: String,
+    pub jumlah_motor: i64,
+    pub motor_hari_tersedia: i64,
+    pub motor_hari_disewa: i64,
+    pub okupansi: f64,
+    pub jumlah_transaksi: i64,
+    pub rata_rata_lama_sewa: f64,
+    pub pendapatan: i64,
+    pub revpar: f64,
+    pub harian: Vec<UtilisasiTitik>,
+    pub mingguan: Vec<UtilisasiTitik>,
+    pub per_tipe: Vec<UtilisasiTipe>,
+}
+
+// Ringkasan nilai seumur hidup seorang penyewa
+#[derive(Serialize, Deserialize)]
+pub struct NilaiPenyewa {
+    pub peringkat: usize,
+    pub penyewa_id: i32,
+    pub nama: String,
+    pub no_hp: String,
+    pub jumlah_transaksi: i64,
+    // Total tagihan semua transaksi (sewa + denda + biaya tambahan)
+    pub total_belanja: i64,
+    pub total_dibayar: i64,
+    pub jumlah_terlambat: i64,
+    pub total_denda: i64,
+    pub sisa_hutang: i64,
+    pub sewa_terakhir: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RiwayatPenyewa {
+    pub penyewa: Penyewa,
+    pub ringkasan: NilaiPenyewa,
+    pub transaksi: Vec<Transaksi>,
+    pub pembayaran: Vec<BuktiPelunasan>,
+}
+
+// Tanda risiko penyewa, dicocokkan lewat no_ktp supaya tetap berlaku
+// walaupun penyewa mendaftar ulang dengan nama lain
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PenyewaFlag {
+    pub flag_id: i32,
+    pub no_ktp: String,
+    // "blacklist" (tidak boleh menyewa) atau "waspada" (hanya peringatan)
+    pub status: String,
+    pub alasan: String,
+    pub tanggal: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DokumenPenyewa {
+    pub dokumen_id: i32,
+    pub penyewa_id: i32,
+    // "ktp", "sim" atau "selfie"
+    pub jenis: String,
+    pub path: String,
+    #[serde(default)]
+    pub foto_thumbnail: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BackupHistory {
+    pub backup_id: i32,
+    pub waktu: String,
+    // "harian", "keluar" (saat aplikasi ditutup) atau "manual"
+    pub pemicu: String,
+    pub path: String,
+    pub ukuran: i64,
+    pub jumlah_file: i64,
+    // "sukses" atau "gagal"
+    pub status: String,
+    pub pesan: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StatusEnkripsiDatabase {
+    // Aplikasi dibangun dengan feature "sqlcipher"
+    pub didukung: bool,
+    pub terenkripsi: bool,
+    // Terenkripsi dan kata sandi belum dimasukkan
+    pub terkunci: bool,
+}
+
+// Restore terakhir yang masih bisa dibatalkan
+#[derive(Serialize, Deserialize)]
+pub struct InfoRestore {
+    pub waktu: String,
+    // File backup yang dipulihkan
+    pub sumber: String,
+    // Arsip kondisi sebelum restore
+    pub snapshot: String,
 }
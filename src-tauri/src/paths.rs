@@ -0,0 +1,289 @@
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Folder data di bawah folder data user: %APPDATA% di Windows,
+// ~/.local/share di Linux, ~/Library/Application Support di macOS
+const NAMA_FOLDER: &str = "AplikasiPembukuan";
+
+// Override lokasi folder data untuk instalasi portable dan testing.
+// Argumen --data-dir lebih diutamakan daripada environment variable.
+pub const ENV_DATA_DIR: &str = "APLIKASI_PEMBUKUAN_DATA_DIR";
+const ARG_DATA_DIR: &str = "--data-dir";
+
+// app_data_dir Tauri (identifier di tauri.conf.json), tempat foto disimpan versi lama
+const IDENTIFIER_TAURI: &str = "com.administrator.aplikasipembukuan";
+pub const FOLDER_FOTO_LAMA: [&str; 5] = [
+    "motor",
+    "transaksi",
+    "kerusakan",
+    "dokumen_motor",
+    "penyewa",
+];
+
+// Folder di dalam folder data tempat bukti pelunasan disimpan versi lama
+pub const FOLDER_UPLOADS_LAMA: &str = "uploads";
+
+// File penanda bahwa migrasi folder lama sudah pernah dijalankan
+const PENANDA_MIGRASI: &str = ".migrasi_folder_data";
+
+struct DataDir {
+    path: PathBuf,
+    override_: bool,
+}
+
+static DATA_DIR: Lazy<DataDir> = Lazy::new(|| {
+    let data_dir = tentukan_data_dir(
+        std::env::args().skip(1),
+        std::env::var_os(ENV_DATA_DIR),
+        || folder_data_user().join(NAMA_FOLDER),
+    );
+
+    fs::create_dir_all(&data_dir.path).ok();
+    println!("DATA DIR => {:?}", data_dir.path);
+    data_dir
+});
+
+fn tentukan_data_dir(
+    args: impl Iterator<Item = String>,
+    env: Option<OsString>,
+    bawaan: impl FnOnce() -> PathBuf,
+) -> DataDir {
+    let override_path =
+        dari_argumen(args).or_else(|| env.filter(|v| !v.is_empty()).map(PathBuf::from));
+
+    DataDir {
+        override_: override_path.is_some(),
+        path: override_path.map(absolut).unwrap_or_else(bawaan),
+    }
+}
+
+fn dari_argumen(mut args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    while let Some(arg) = args.next() {
+        if arg == ARG_DATA_DIR {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix(&format!("{}=", ARG_DATA_DIR)) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+fn absolut(path: PathBuf) -> PathBuf {
+    if path.is_absolute() {
+        return path;
+    }
+    std::env::current_dir()
+        .map(|cwd| cwd.join(&path))
+        .unwrap_or(path)
+}
+
+fn folder_data_user() -> PathBuf {
+    dirs::data_dir().unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub fn data_dir() -> &'static Path {
+    &DATA_DIR.path
+}
+
+pub fn db_path() -> PathBuf {
+    data_dir().join("rental_motor.sqlite")
+}
+
+pub fn license_path() -> PathBuf {
+    data_dir().join("license.dat")
+}
+
+pub fn lampiran_dir() -> PathBuf {
+    data_dir().join("lampiran")
+}
+
+fn hash_file(path: &Path) -> io::Result<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+// Nama bebas di folder tujuan: foto.jpg -> foto-1.jpg, foto-2.jpg, ...
+fn nama_bebas(dest: &Path, nama: &Path) -> PathBuf {
+    let stem = nama.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = nama
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|i| dest.join(format!("{}-{}{}", stem, i, ext)))
+        .find(|p| !p.exists())
+        .unwrap_or_else(|| dest.join(nama))
+}
+
+// Pindahkan isi folder (tanpa subfolder), kembalikan pasangan (path lama, path baru).
+// rename gagal jika beda drive, jadi fallback ke copy + hapus.
+// File yang namanya sudah ada di tujuan hanya dihapus jika isinya sama persis;
+// jika berbeda, dipindahkan dengan nama lain supaya tidak ada foto yang hilang.
+fn pindahkan_isi(src: &Path, dest: &Path) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    fs::create_dir_all(dest).map_err(|e| format!("Gagal membuat folder: {}", e))?;
+
+    let mut dipindah = Vec::new();
+    for entry in fs::read_dir(src).map_err(|e| format!("Gagal membaca folder: {}", e))? {
+        let entry = entry.map_err(|e| format!("Gagal membaca folder: {}", e))?;
+        let asal = entry.path();
+        if !asal.is_file() {
+            continue;
+        }
+        let gagal = |e: io::Error| format!("Gagal memindahkan {}: {}", asal.display(), e);
+
+        let mut tujuan = dest.join(entry.file_name());
+        if tujuan.exists() {
+            if hash_file(&asal).map_err(gagal)? == hash_file(&tujuan).map_err(gagal)? {
+                fs::remove_file(&asal).map_err(gagal)?;
+                dipindah.push((asal, tujuan));
+                continue;
+            }
+            tujuan = nama_bebas(dest, Path::new(&entry.file_name()));
+        }
+
+        if fs::rename(&asal, &tujuan).is_err() {
+            fs::copy(&asal, &tujuan).map_err(gagal)?;
+            fs::remove_file(&asal).map_err(gagal)?;
+        }
+        dipindah.push((asal, tujuan));
+    }
+    fs::remove_dir(src).ok();
+    Ok(dipindah)
+}
+
+// Sekali jalan: pindahkan foto yang dulu disimpan di app_data_dir Tauri ke folder data
+// yang sama dengan database, lalu perbarui path-nya di database.
+// Tidak dijalankan saat folder data di-override supaya instalasi portable/test
+// tidak mengambil data instalasi utama.
+pub fn migrasi_folder_lama() -> Result<(), String> {
+    let penanda = data_dir().join(PENANDA_MIGRASI);
+    if DATA_DIR.override_ || penanda.exists() {
+        return Ok(());
+    }
+
+    let folder_tauri = folder_data_user().join(IDENTIFIER_TAURI);
+    for nama in FOLDER_FOTO_LAMA {
+        let lama = folder_tauri.join(nama);
+        if !lama.is_dir() {
+            continue;
+        }
+        let baru = data_dir().join(nama);
+        let dipindah = pindahkan_isi(&lama, &baru)?;
+
+        crate::db::with_connection(|conn| crate::lampiran::ganti_path(conn, &dipindah))
+            .map_err(|e| format!("Gagal memperbarui path foto: {}", e))?;
+        println!(
+            "📦 {} file dipindahkan dari {:?} ke {:?}",
+            dipindah.len(),
+            lama,
+            baru
+        );
+    }
+
+    fs::write(&penanda, chrono::Local::now().to_rfc3339())
+        .map_err(|e| format!("Gagal menulis penanda migrasi: {}", e))
+}
+
+// Folder data sementara untuk test yang memakai database dan folder data global.
+// Guard dipegang selama test berjalan supaya test semacam ini tidak saling mengganggu.
+#[cfg(test)]
+pub fn folder_data_uji() -> parking_lot::MutexGuard<'static, ()> {
+    static GILIRAN: parking_lot::Mutex<()> = parking_lot::const_mutex(());
+    static SIAPKAN: std::sync::Once = std::sync::Once::new();
+
+    let giliran = GILIRAN.lock();
+    SIAPKAN.call_once(|| {
+        let folder = std::env::temp_dir().join(format!("aplikasi-uji-{}", std::process::id()));
+        fs::remove_dir_all(&folder).ok();
+        std::env::set_var(ENV_DATA_DIR, &folder);
+    });
+    // Jangan sampai test menyentuh folder data asli
+    assert!(
+        data_dir().starts_with(std::env::temp_dir()),
+        "folder data sudah dipakai sebelum folder_data_uji dipanggil"
+    );
+    giliran
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn bawaan() -> PathBuf {
+        PathBuf::from("/home/user/.local/share").join(NAMA_FOLDER)
+    }
+
+    fn folder_uji(nama: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("paths-{}-{}", std::process::id(), nama));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn data_dir_dari_argumen_atau_env() {
+        let d = tentukan_data_dir(args(&["--data-dir", "/portable"]), None, bawaan);
+        assert_eq!(d.path, PathBuf::from("/portable"));
+        assert!(d.override_);
+
+        let d = tentukan_data_dir(
+            args(&["--data-dir=/dari-arg"]),
+            Some("/dari-env".into()),
+            bawaan,
+        );
+        assert_eq!(d.path, PathBuf::from("/dari-arg"));
+
+        let d = tentukan_data_dir(args(&["--lain"]), Some("/dari-env".into()), bawaan);
+        assert_eq!(d.path, PathBuf::from("/dari-env"));
+        assert!(d.override_);
+
+        let d = tentukan_data_dir(args(&[]), Some("".into()), bawaan);
+        assert_eq!(d.path, bawaan());
+        assert!(!d.override_);
+
+        // Path relatif diukur dari folder kerja saat ini
+        let d = tentukan_data_dir(args(&["--data-dir", "data"]), None, bawaan);
+        assert_eq!(d.path, std::env::current_dir().unwrap().join("data"));
+    }
+
+    #[test]
+    fn pindahkan_isi_tidak_menimpa_file_berbeda() {
+        let src = folder_uji("src");
+        let dest = folder_uji("dest");
+        fs::write(src.join("sama.jpg"), b"isi").unwrap();
+        fs::write(dest.join("sama.jpg"), b"isi").unwrap();
+        fs::write(src.join("beda.jpg"), b"lama").unwrap();
+        fs::write(dest.join("beda.jpg"), b"baru").unwrap();
+        fs::write(src.join("baru.jpg"), b"x").unwrap();
+
+        let mut dipindah = pindahkan_isi(&src, &dest).unwrap();
+        dipindah.sort();
+
+        assert_eq!(
+            dipindah,
+            vec![
+                (src.join("baru.jpg"), dest.join("baru.jpg")),
+                (src.join("beda.jpg"), dest.join("beda-1.jpg")),
+                (src.join("sama.jpg"), dest.join("sama.jpg")),
+            ]
+        );
+        assert_eq!(fs::read(dest.join("beda.jpg")).unwrap(), b"baru");
+        assert_eq!(fs::read(dest.join("beda-1.jpg")).unwrap(), b"lama");
+        assert!(!src.exists());
+
+        fs::remove_dir_all(&dest).ok();
+    }
+}
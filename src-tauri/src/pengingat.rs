@@ -0,0 +1,135 @@
+use crate::db;
+use crate::models::{Pengingat, TransaksiTerlambat};
+use chrono::{Local, Timelike};
+use rusqlite::{Connection, Result};
+
+// Jam (0-23) mulai dikirimnya pengingat "kembali hari ini" jika belum diatur
+const JAM_PENGINGAT_DEFAULT: u32 = 7;
+
+// Buat pengingat untuk motor yang jatuh tempo hari ini, transaksi yang terlambat
+// motor yang mendekati jadwal servis dan dokumen motor yang akan kadaluarsa.
+// Hanya pengingat yang benar-benar baru yang dikembalikan, untuk dikirim sebagai notifikasi OS.
+pub fn buat_pengingat(
+    conn: &Connection,
+    terlambat: &[TransaksiTerlambat],
+) -> Result<Vec<Pengingat>> {
+    let sekarang = Local::now();
+    let hari_ini = sekarang.format("%Y-%m-%d").to_string();
+    let mut baru = Vec::new();
+
+    let jam_pengingat = db::get_pengaturan_value(conn, "jam_pengingat")?
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(JAM_PENGINGAT_DEFAULT);
+
+    if sekarang.hour() >= jam_pengingat {
+        let mut stmt = conn.prepare(
+            "SELECT t.transaksi_id, COALESCE(p.nama, '-'), COALESCE(m.nama, '-'), COALESCE(m.plat, '-')
+             FROM transaksi t
+             LEFT JOIN penyewa p ON p.penyewa_id = t.penyewa_id
+             LEFT JOIN motor m ON m.motor_id = t.motor_id
+             WHERE t.status = 'dipinjam'
+               AND (t.tanggal_kembali_aktual IS NULL OR t.tanggal_kembali_aktual = '')
+               AND substr(t.tanggal_kembali_rencana, 1, 10) = ?1",
+        )?;
+        let jatuh_tempo = stmt
+            .query_map([&hari_ini], |row| {
+                Ok((
+                    row.get::<_, i32>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        for (transaksi_id, penyewa, motor, plat) in jatuh_tempo {
+            let pesan = format!(
+                "{} ({}) disewa {} dijadwalkan kembali hari ini.",
+                motor, plat, penyewa
+            );
+            if let Some(p) = db::create_pengingat(
+                conn,
+                "jatuh_tempo",
+                Some(transaksi_id),
+                "Motor kembali hari ini",
+                &pesan,
+                &hari_ini,
+            )? {
+                baru.push(p);
+            }
+        }
+    }
+
+    for t in terlambat {
+        let pesan = format!(
+            "{} ({}) disewa {} terlambat {} hari, denda berjalan Rp {}.",
+            t.motor, t.plat, t.penyewa, t.hari_terlambat, t.denda
+        );
+        // Satu pengingat per transaksi per tanggal rencana kembali
+        if let Some(p) = db::create_pengingat(
+            conn,
+            "terlambat",
+            Some(t.transaksi_id),
+            "Sewa terlambat dikembalikan",
+            &pesan,
+            &t.tanggal_kembali_rencana,
+        )? {
+            baru.push(p);
+        }
+    }
+
+    let perlu_servis = db::get_motor_perlu_servis(
+        conn,
+        db::SERVIS_AMBANG_HARI_DEFAULT,
+        db::SERVIS_AMBANG_KM_DEFAULT,
+    )?;
+    for m in perlu_servis {
+        let pesan = format!("{} ({}) perlu servis: {}.", m.nama, m.plat, m.alasan);
+        // Satu pengingat per jadwal servis (tanggal atau km berikutnya)
+        let jadwal = m
+            .servis_berikut_tanggal
+            .clone()
+            .filter(|t| !t.is_empty())
+            .or(m.servis_berikut_km.map(|km| format!("{} km", km)))
+            .unwrap_or_default();
+        if let Some(p) = db::create_pengingat(
+            conn,
+            "servis",
+            Some(m.motor_id),
+            "Motor perlu servis",
+            &pesan,
+            &jadwal,
+        )? {
+            baru.push(p);
+        }
+    }
+
+    let hari_peringatan = db::get_pengaturan_value(conn, "hari_peringatan_dokumen")?
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(db::DOKUMEN_HARI_PERINGATAN_DEFAULT);
+    for d in db::get_dokumen_motor_akan_kadaluarsa(conn, hari_peringatan, None)? {
+        let pesan = if d.sisa_hari < 0 {
+            format!(
+                "{} {} ({}) sudah kadaluarsa sejak {}.",
+                d.jenis, d.motor, d.plat, d.tanggal_kadaluarsa
+            )
+        } else {
+            format!(
+                "{} {} ({}) kadaluarsa {} ({} hari lagi).",
+                d.jenis, d.motor, d.plat, d.tanggal_kadaluarsa, d.sisa_hari
+            )
+        };
+        if let Some(p) = db::create_pengingat(
+            conn,
+            "dokumen",
+            Some(d.dokumen_id),
+            "Dokumen motor akan kadaluarsa",
+            &pesan,
+            &d.tanggal_kadaluarsa,
+        )? {
+            baru.push(p);
+        }
+    }
+
+    Ok(baru)
+}
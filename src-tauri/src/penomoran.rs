@@ -0,0 +1,351 @@
+use crate::db;
+use chrono::{Datelike, Local, NaiveDate};
+use rusqlite::{Connection, OptionalExtension, Result};
+
+// Jenis dokumen yang mendapat nomor: (jenis, tabel, kolom id, kolom tanggal)
+pub const JENIS_DOKUMEN: [(&str, &str, &str, &str); 3] = [
+    ("transaksi", "transaksi", "transaksi_id", "tanggal_sewa"),
+    (
+        "bukti_pelunasan",
+        "bukti_pelunasan",
+        "bukti_id",
+        "tanggal_bayar",
+    ),
+    (
+        "pengeluaran_rental",
+        "pengeluaran_rental",
+        "pengeluaran_id",
+        "tanggal",
+    ),
+];
+
+const RESET_NOMOR: [&str; 3] = ["bulanan", "tahunan", "tidak"];
+
+// Batas pencarian nomor yang belum terpakai, supaya format yang tidak pernah
+// menghasilkan nomor baru tidak membuat loop tanpa akhir
+const MAKS_PERCOBAAN: i64 = 10_000;
+
+// Format bawaan jika belum diatur di pengaturan
+fn format_default(jenis: &str) -> Result<&'static str> {
+    match jenis {
+        "transaksi" => Ok("INV/{YYYY}/{MM}/{NNNN}"),
+        "bukti_pelunasan" => Ok("BYR/{YYYY}/{MM}/{NNNN}"),
+        "pengeluaran_rental" => Ok("PGL/{YYYY}/{MM}/{NNNN}"),
+        _ => Err(db::validation_error(format!(
+            "Jenis dokumen '{}' tidak dikenal",
+            jenis
+        ))),
+    }
+}
+
+fn tabel_dokumen(jenis: &str) -> Result<(&'static str, &'static str)> {
+    JENIS_DOKUMEN
+        .iter()
+        .find(|(j, ..)| *j == jenis)
+        .map(|(_, tabel, kolom_id, _)| (*tabel, *kolom_id))
+        .ok_or_else(|| db::validation_error(format!("Jenis dokumen '{}' tidak dikenal", jenis)))
+}
+
+// Setiap token nomor urut harus lengkap: {N}, {NN}, {NNNN}, ... Token yang tidak
+// ditutup tidak pernah diganti, sehingga semua dokumen mendapat nomor yang sama.
+fn validasi_token_urut(format: &str) -> Result<()> {
+    let mut sisa = format;
+    while let Some(start) = sisa.find("{N") {
+        let token = &sisa[start + 1..];
+        let lebar = token.chars().take_while(|c| *c == 'N').count();
+        if !token[lebar..].starts_with('}') {
+            return Err(db::validation_error(
+                "Token nomor urut harus berbentuk {N...}, misalnya {NNNN}",
+            ));
+        }
+        sisa = &token[lebar + 1..];
+    }
+    Ok(())
+}
+
+// Counter di-reset per periode, jadi nomor yang dirender harus memuat token
+// periode tersebut. Tanpa itu nomor periode baru bentrok dengan periode lama.
+fn validasi_format(format: &str, reset: &str) -> Result<()> {
+    validasi_token_urut(format)?;
+    if !RESET_NOMOR.contains(&reset) {
+        return Err(db::validation_error(format!(
+            "Reset nomor '{}' tidak dikenal (pilih bulanan, tahunan atau tidak)",
+            reset
+        )));
+    }
+    let ada_tahun = format.contains("{YYYY}") || format.contains("{YY}");
+    if reset != "tidak" && !ada_tahun {
+        return Err(db::validation_error(format!(
+            "Format nomor dengan reset {} harus memuat {{YYYY}} atau {{YY}}",
+            reset
+        )));
+    }
+    if reset == "bulanan" && !format.contains("{MM}") {
+        return Err(db::validation_error(
+            "Format nomor dengan reset bulanan harus memuat {MM}",
+        ));
+    }
+    Ok(())
+}
+
+// Dipanggil set_pengaturan sebelum menyimpan format_nomor_* / reset_nomor_*
+pub fn validasi_pengaturan(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    let (jenis, format, reset) = if let Some(jenis) = key.strip_prefix("format_nomor_") {
+        let (_, reset) = pengaturan_nomor(conn, jenis)?;
+        let format = if value.trim().is_empty() {
+            format_default(jenis)?.to_string()
+        } else {
+            value.to_string()
+        };
+        (jenis, format, reset)
+    } else if let Some(jenis) = key.strip_prefix("reset_nomor_") {
+        let (format, _) = pengaturan_nomor(conn, jenis)?;
+        (jenis, format, value.to_string())
+    } else {
+        return Ok(());
+    };
+    tabel_dokumen(jenis)?;
+    validasi_format(&format, &reset)
+}
+
+fn parse_tanggal(tanggal: &str) -> NaiveDate {
+    tanggal
+        .get(0..10)
+        .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+        .unwrap_or_else(|| Local::now().date_naive())
+}
+
+// Key periode counter sesuai aturan reset: "bulanan", "tahunan" atau "tidak"
+fn periode(reset: &str, tanggal: NaiveDate) -> String {
+    match reset {
+        "tahunan" => format!("{}", tanggal.year()),
+        "tidak" => "-".to_string(),
+        _ => format!("{}-{:02}", tanggal.year(), tanggal.month()),
+    }
+}
+
+// Ganti placeholder {YYYY}, {YY}, {MM}, {DD} dan {N..} (jumlah N = panjang nomor urut)
+fn render(format: &str, tanggal: NaiveDate, urut: i64) -> String {
+    let mut result = format
+        .replace("{YYYY}", &format!("{}", tanggal.year()))
+        .replace("{YY}", &format!("{:02}", tanggal.year() % 100))
+        .replace("{MM}", &format!("{:02}", tanggal.month()))
+        .replace("{DD}", &format!("{:02}", tanggal.day()));
+
+    while let Some(start) = result.find("{N") {
+        let Some(len) = result[start..].find('}') else {
+            break;
+        };
+        let token = &result[start..start + len + 1];
+        let lebar = token.len() - 2;
+        result = result.replacen(token, &format!("{:0lebar$}", urut), 1);
+    }
+
+    if !format.contains("{N") {
+        result.push_str(&format!("{:04}", urut));
+    }
+
+    result
+}
+
+fn pengaturan_nomor(conn: &Connection, jenis: &str) -> Result<(String, String)> {
+    let format = match db::get_pengaturan_value(conn, &format!("format_nomor_{}", jenis))?
+        .filter(|v| !v.trim().is_empty())
+    {
+        Some(format) => format,
+        None => format_default(jenis)?.to_string(),
+    };
+    let reset = db::get_pengaturan_value(conn, &format!("reset_nomor_{}", jenis))?
+        .unwrap_or_else(|| "bulanan".to_string());
+    Ok((format, reset))
+}
+
+fn nomor_tidak_ditemukan() -> rusqlite::Error {
+    db::validation_error(format!(
+        "Tidak ada nomor dokumen kosong dalam {} percobaan, periksa format nomor di Pengaturan",
+        MAKS_PERCOBAAN
+    ))
+}
+
+fn nomor_terpakai(conn: &Connection, jenis: &str, nomor: &str) -> Result<bool> {
+    let (tabel, _) = tabel_dokumen(jenis)?;
+    conn.query_row(
+        &format!("SELECT EXISTS(SELECT 1 FROM {} WHERE nomor = ?1)", tabel),
+        [nomor],
+        |row| row.get(0),
+    )
+}
+
+// Ambil nomor dokumen berikutnya dan naikkan counter-nya.
+// Panggil di dalam transaksi database yang sama dengan INSERT dokumennya,
+// supaya counter ikut di-rollback jika INSERT gagal (nomor tidak bolong).
+pub fn nomor_berikutnya(conn: &Connection, jenis: &str, tanggal: &str) -> Result<String> {
+    let (format, reset) = pengaturan_nomor(conn, jenis)?;
+    // Format lama yang tersimpan sebelum validasi token ada
+    validasi_token_urut(&format)?;
+    let tanggal = parse_tanggal(tanggal);
+    let periode = periode(&reset, tanggal);
+
+    // Lewati nomor yang sudah dipakai, mis. setelah reset diganti dari bulanan
+    // ke tahunan di tengah tahun counter periode baru mulai lagi dari 1
+    for _ in 0..MAKS_PERCOBAAN {
+        let urut: i64 = conn.query_row(
+            "INSERT INTO nomor_dokumen (jenis, periode, nomor_terakhir) VALUES (?1, ?2, 1)
+             ON CONFLICT(jenis, periode) DO UPDATE SET nomor_terakhir = nomor_terakhir + 1
+             RETURNING nomor_terakhir",
+            (jenis, &periode),
+            |row| row.get(0),
+        )?;
+        let nomor = render(&format, tanggal, urut);
+        if !nomor_terpakai(conn, jenis, &nomor)? {
+            return Ok(nomor);
+        }
+    }
+    Err(nomor_tidak_ditemukan())
+}
+
+// Pratinjau nomor berikutnya tanpa menaikkan counter
+pub fn pratinjau_nomor(conn: &Connection, jenis: &str, tanggal: &str) -> Result<String> {
+    let (format, reset) = pengaturan_nomor(conn, jenis)?;
+    validasi_token_urut(&format)?;
+    let tanggal = parse_tanggal(tanggal);
+    let periode = periode(&reset, tanggal);
+
+    let mut urut: i64 = conn
+        .query_row(
+            "SELECT nomor_terakhir FROM nomor_dokumen WHERE jenis = ?1 AND periode = ?2",
+            (jenis, &periode),
+            |row| row.get(0),
+        )
+        .optional()?
+        .unwrap_or(0);
+
+    for _ in 0..MAKS_PERCOBAAN {
+        urut += 1;
+        let nomor = render(&format, tanggal, urut);
+        if !nomor_terpakai(conn, jenis, &nomor)? {
+            return Ok(nomor);
+        }
+    }
+    Err(nomor_tidak_ditemukan())
+}
+
+// Beri nomor ke data lama yang dibuat sebelum penomoran ada,
+// urut berdasarkan tanggal dokumen lalu id.
+pub fn isi_nomor_kosong(conn: &Connection) -> Result<()> {
+    db::atomic(conn, |conn| {
+        for (jenis, tabel, kolom_id, kolom_tanggal) in JENIS_DOKUMEN {
+            let rows: Vec<(i64, String)> = {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {id}, COALESCE({tgl}, '') FROM {tabel}
+                     WHERE nomor IS NULL OR nomor = ''
+                     ORDER BY {tgl}, {id}",
+                    id = kolom_id,
+                    tgl = kolom_tanggal,
+                    tabel = tabel
+                ))?;
+                let iter = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                iter.collect::<Result<_>>()?
+            };
+
+            for (id, tanggal) in rows {
+                let nomor = nomor_berikutnya(conn, jenis, &tanggal)?;
+                conn.execute(
+                    &format!("UPDATE {} SET nomor = ?1 WHERE {} = ?2", tabel, kolom_id),
+                    (nomor, id),
+                )?;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_harus_memuat_token_periode() {
+        assert!(validasi_format("INV/{YYYY}/{MM}/{NNNN}", "bulanan").is_ok());
+        assert!(validasi_format("INV/{NNNN}", "bulanan").is_err());
+        assert!(validasi_format("INV/{YYYY}/{NNNN}", "bulanan").is_err());
+        assert!(validasi_format("INV/{YY}/{NNNN}", "tahunan").is_ok());
+        assert!(validasi_format("INV/{NNNN}", "tahunan").is_err());
+        assert!(validasi_format("INV/{NNNN}", "tidak").is_ok());
+        assert!(validasi_format("INV/{YYYY}/{NNNN}", "mingguan").is_err());
+    }
+
+    #[test]
+    fn token_urut_harus_lengkap() {
+        assert!(validasi_format("INV/{YYYY}/{MM}/{N", "bulanan").is_err());
+        assert!(validasi_format("INV/{YYYY}/{MM}/{NNNX}", "bulanan").is_err());
+        assert!(validasi_format("INV/{YYYY}/{MM}/{N}-{NN", "bulanan").is_err());
+        assert!(validasi_format("INV/{YYYY}/{MM}/{N}", "bulanan").is_ok());
+        assert!(validasi_format("INV/{YYYY}/{MM}/{NN}-{NNNN}", "bulanan").is_ok());
+    }
+
+    #[test]
+    fn render_nomor() {
+        let tanggal = NaiveDate::from_ymd_opt(2026, 10, 5).unwrap();
+        assert_eq!(
+            render("INV/{YYYY}/{MM}/{NNNN}", tanggal, 7),
+            "INV/2026/10/0007"
+        );
+        assert_eq!(render("BYR-{YY}{MM}{DD}-", tanggal, 12), "BYR-261005-0012");
+    }
+
+    #[test]
+    fn nomor_terpakai_dilewati() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE pengaturan (key TEXT PRIMARY KEY, value TEXT, keterangan TEXT);
+             CREATE TABLE nomor_dokumen (jenis TEXT, periode TEXT, nomor_terakhir INTEGER,
+                 PRIMARY KEY (jenis, periode));
+             CREATE TABLE transaksi (transaksi_id INTEGER PRIMARY KEY, nomor TEXT UNIQUE);
+             INSERT INTO pengaturan VALUES ('reset_nomor_transaksi', 'tahunan', '');
+             INSERT INTO transaksi (nomor) VALUES ('INV/2026/10/0001'), ('INV/2026/10/0002');",
+        )
+        .unwrap();
+
+        assert_eq!(
+            pratinjau_nomor(&conn, "transaksi", "2026-10-19").unwrap(),
+            "INV/2026/10/0003"
+        );
+        assert_eq!(
+            nomor_berikutnya(&conn, "transaksi", "2026-10-19").unwrap(),
+            "INV/2026/10/0003"
+        );
+        assert!(pratinjau_nomor(&conn, "lainnya", "2026-10-19").is_err());
+    }
+
+    #[test]
+    fn format_rusak_tersimpan_tidak_membuat_loop() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE pengaturan (key TEXT PRIMARY KEY, value TEXT, keterangan TEXT);
+             CREATE TABLE nomor_dokumen (jenis TEXT, periode TEXT, nomor_terakhir INTEGER,
+                 PRIMARY KEY (jenis, periode));
+             CREATE TABLE transaksi (transaksi_id INTEGER PRIMARY KEY, nomor TEXT UNIQUE);
+             INSERT INTO pengaturan VALUES ('format_nomor_transaksi', 'INV/{YYYY}/{MM}/{N', '');",
+        )
+        .unwrap();
+
+        assert!(
+            validasi_pengaturan(&conn, "format_nomor_transaksi", "INV/{YYYY}/{MM}/{N").is_err()
+        );
+        assert!(nomor_berikutnya(&conn, "transaksi", "2026-10-19").is_err());
+        assert!(pratinjau_nomor(&conn, "transaksi", "2026-10-19").is_err());
+
+        // Format tanpa token urut selalu ditambah nomor urut, jadi tetap berhenti
+        conn.execute(
+            "UPDATE pengaturan SET value = 'INV/{YYYY}/{MM}/' WHERE key = 'format_nomor_transaksi'",
+            [],
+        )
+        .unwrap();
+        assert_eq!(
+            nomor_berikutnya(&conn, "transaksi", "2026-10-19").unwrap(),
+            "INV/2026/10/0001"
+        );
+    }
+}
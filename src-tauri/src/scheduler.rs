@@ -0,0 +1,145 @@
+use crate::db;
+use crate::models::TransaksiTerlambat;
+use parking_lot::{const_mutex, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+
+// Jeda antar pemeriksaan di background
+const INTERVAL: Duration = Duration::from_secs(15 * 60);
+const INTERVAL_TERKUNCI: Duration = Duration::from_secs(5);
+
+// Hasil pemeriksaan keterlambatan terakhir. Pemeriksaan pertama berjalan sebelum
+// webview sempat memasang listener, jadi Navbar mengambilnya lewat command saat mount.
+static TERLAMBAT_TERAKHIR: Mutex<Vec<TransaksiTerlambat>> = const_mutex(Vec::new());
+
+// Dijalankan sekali saat aplikasi start, lalu berulang setiap INTERVAL
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        // Database terenkripsi belum dibuka, tunggu sampai kata sandi dimasukkan
+        if db::terkunci() {
+            thread::sleep(INTERVAL_TERKUNCI);
+            continue;
+        }
+        let terlambat = periksa_keterlambatan(&app);
+        kirim_pengingat(&app, &terlambat);
+        backup_harian();
+        bersihkan_lampiran();
+        thread::sleep(INTERVAL);
+    });
+}
+
+pub fn periksa_keterlambatan(app: &AppHandle) -> Vec<TransaksiTerlambat> {
+    let hari_ini = chrono::Local::now().date_naive();
+    match db::with_connection(|conn| db::tandai_transaksi_terlambat(conn, hari_ini)) {
+        Ok(list) => {
+            let baru = list.iter().filter(|t| t.baru_terlambat).count();
+            if baru > 0 {
+                println!("⏰ {} transaksi baru terlambat", baru);
+            }
+            *TERLAMBAT_TERAKHIR.lock() = list.clone();
+
+            // UI mendengarkan event ini untuk menampilkan peringatan ke staf
+            if !list.is_empty() {
+                if let Err(e) = app.emit("transaksi-terlambat", &list) {
+                    eprintln!("❌ Error emit transaksi-terlambat: {}", e);
+                }
+            }
+            list
+        }
+        Err(e) => {
+            eprintln!("❌ Error periksa_keterlambatan: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+// Hasil pemeriksaan terakhir untuk UI yang baru dibuka. Tanda baru_terlambat
+// dikembalikan sekali saja supaya peringatannya tidak muncul berulang.
+pub fn ambil_terlambat_terakhir() -> Vec<TransaksiTerlambat> {
+    let mut terakhir = TERLAMBAT_TERAKHIR.lock();
+    let list = terakhir.clone();
+    for t in terakhir.iter_mut() {
+        t.baru_terlambat = false;
+    }
+    list
+}
+
+// Simpan pengingat baru ke inbox dan tampilkan sebagai notifikasi OS
+fn kirim_pengingat(app: &AppHandle, terlambat: &[TransaksiTerlambat]) {
+    let baru = match db::with_connection(|conn| crate::pengingat::buat_pengingat(conn, terlambat)) {
+        Ok(baru) => baru,
+        Err(e) => {
+            eprintln!("❌ Error buat_pengingat: {}", e);
+            return;
+        }
+    };
+
+    for p in &baru {
+        if let Err(e) = app
+            .notification()
+            .builder()
+            .title(&p.judul)
+            .body(&p.pesan)
+            .show()
+        {
+            eprintln!("❌ Error notifikasi pengingat: {}", e);
+        }
+    }
+
+    if !baru.is_empty() {
+        if let Err(e) = app.emit("pengingat-baru", &baru) {
+            eprintln!("❌ Error emit pengingat-baru: {}", e);
+        }
+    }
+}
+
+// Backup otomatis sekali sehari ke folder yang diatur di Pengaturan
+fn backup_harian() {
+    match crate::backup::perlu_backup_harian() {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => {
+            eprintln!("❌ Error cek backup harian: {}", e);
+            return;
+        }
+    }
+
+    match crate::backup::backup_terjadwal(crate::backup::PEMICU_HARIAN) {
+        Ok(Some(riwayat)) => println!("💾 Backup harian tersimpan di {}", riwayat.path),
+        Ok(None) => {}
+        Err(e) => eprintln!("❌ Error backup harian: {}", e),
+    }
+}
+
+// File lampiran yang tidak dirujuk record mana pun lagi dihapus di sini,
+// bukan di setiap command update/delete
+fn bersihkan_lampiran() {
+    if let Err(e) = db::with_connection(crate::lampiran::hapus_yatim) {
+        eprintln!("❌ Error bersihkan lampiran: {}", e);
+    }
+}
+
+// Dipanggil dari RunEvent::Exit, jadi harus selesai sebelum proses berakhir
+pub fn backup_saat_keluar() {
+    if db::terkunci() {
+        return;
+    }
+    let aktif = match db::with_connection(crate::backup::baca_jadwal) {
+        Ok(jadwal) => jadwal.saat_keluar,
+        Err(e) => {
+            eprintln!("❌ Error baca pengaturan backup: {}", e);
+            return;
+        }
+    };
+    if !aktif {
+        return;
+    }
+
+    match crate::backup::backup_terjadwal(crate::backup::PEMICU_KELUAR) {
+        Ok(Some(riwayat)) => println!("💾 Backup saat keluar tersimpan di {}", riwayat.path),
+        Ok(None) => {}
+        Err(e) => eprintln!("❌ Error backup saat keluar: {}", e),
+    }
+}
@@ -2,6 +2,8 @@ import { BrowserRouter, Routes, Route } from "react-router-dom"
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import Activation from "./components/pages/Activation/Activation";
+import BukaKunci from "./components/pages/BukaKunci/BukaKunci";
+import { KeamananService } from "./services/keamanan.service";
 import { Lock } from "lucide-react";
 
 import PageWrapper from "./components/layout/PageWrapper"
@@ -9,6 +11,7 @@ import Dashboard from "./components/pages/Dashboard/Dashboard"
 import MotorList from "./components/pages/Motor/MotorList"
 import MotorAdd from "./components/pages/Motor/MotorAdd"
 import MotorEdit from "./components/pages/Motor/MotorEdit"
+import MotorDetail from "./components/pages/Motor/MotorDetail"
 import PenyewaList from "./components/pages/Penyewa/PenyewaList";
 import PenyewaAdd from "./components/pages/Penyewa/PenyewaAdd";
 import PenyewaEdit from "./components/pages/Penyewa/PenyewaEdit";
@@ -25,9 +28,12 @@ import PengeluaranRentalEdit from "./components/pages/PengeluaranRental/Pengelua
 import KasList from "./components/pages/Kas/KasList";
 import LaporanBulanan from "./components/pages/Laporan/LaporanBulanan";
 import Pengaturan from "./components/pages/Pengaturan/Pengaturan";
+import PengingatList from "./components/pages/Pengingat/PengingatList";
+import ImporData from "./components/pages/Impor/ImporData";
 
 export default function App() {
   const [isLicensed, setIsLicensed] = useState<boolean | null>(null);
+  const [dbTerkunci, setDbTerkunci] = useState(false);
 
   useEffect(() => {
     checkLicense();
@@ -36,6 +42,10 @@ export default function App() {
   const checkLicense = async () => {
     try {
       const valid = await invoke<boolean>("check_license_status");
+      if (valid) {
+        const status = await KeamananService.getStatusEnkripsi();
+        setDbTerkunci(status.terkunci);
+      }
       setIsLicensed(valid);
       // setIsLicensed(true); // Uncomment for debugging without backend
     } catch (err) {
@@ -61,6 +71,10 @@ export default function App() {
     return <Activation />;
   }
 
+  if (dbTerkunci) {
+    return <BukaKunci onTerbuka={() => setDbTerkunci(false)} />;
+  }
+
   return (
     <BrowserRouter>
       <PageWrapper>
@@ -69,6 +83,7 @@ export default function App() {
           <Route path="/motor" element={<MotorList />} />
           <Route path="/motor/tambah" element={<MotorAdd />} />
           <Route path="/motor/edit/:id" element={<MotorEdit />} />
+          <Route path="/motor/detail/:id" element={<MotorDetail />} />
           <Route path="/penyewa" element={<PenyewaList />} />
           <Route path="/penyewa/tambah" element={<PenyewaAdd />} />
           <Route path="/penyewa/edit/:id" element={<PenyewaEdit />} />
@@ -84,6 +99,8 @@ export default function App() {
           <Route path="/pengeluaran_rental/edit/:id" element={<PengeluaranRentalEdit />} />
           <Route path="/kas" element={<KasList />} />
           <Route path="/laporan" element={<LaporanBulanan />} />
+          <Route path="/pengingat" element={<PengingatList />} />
+          <Route path="/impor" element={<ImporData />} />
           <Route path="/pengaturan" element={<Pengaturan />} />
         </Routes>
       </PageWrapper>
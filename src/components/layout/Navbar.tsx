@@ -1,8 +1,12 @@
 import { useState, useEffect, useRef } from "react"; // Added hooks
 import { useNavigate } from "react-router-dom"; // Added routing
-import { Bell, UserCircle, AlertCircle, Calendar, X } from "lucide-react"; // Added icons
+import { listen } from "@tauri-apps/api/event";
+import { Bell, UserCircle, AlertCircle, Calendar, X, Inbox } from "lucide-react"; // Added icons
 import { TransaksiService, PenyewaService } from "../../services/penyewa.service";
 import { getMotor } from "../../services/motor.service";
+import { TransaksiTerlambat } from "../../types/transaksi.type";
+import { PengingatService } from "../../services/pengingat.service";
+import { EVENT_PENGINGAT_BERUBAH } from "../pages/Pengingat/PengingatList";
 import Breadcrumbs from "./Breadcrumb"
 
 export default function Navbar() {
@@ -10,6 +14,9 @@ export default function Navbar() {
   const [notifications, setNotifications] = useState<{ id: number; message: string; subtext: string; type: "overdue" | "due_today" }[]>([]);
   const [showDropdown, setShowDropdown] = useState(false);
   const dropdownRef = useRef<HTMLDivElement>(null);
+  // Transaksi yang baru saja berubah menjadi terlambat (dari event scheduler)
+  const [baruTerlambat, setBaruTerlambat] = useState<TransaksiTerlambat[]>([]);
+  const [pengingatBelumDibaca, setPengingatBelumDibaca] = useState(0);
 
   useEffect(() => {
     fetchNotifications();
@@ -24,6 +31,43 @@ export default function Navbar() {
     return () => document.removeEventListener("mousedown", handleClickOutside);
   }, []);
 
+  // Scheduler backend mengirim event ini setiap kali memeriksa keterlambatan
+  useEffect(() => {
+    const tampilkanTerlambat = (list: TransaksiTerlambat[]) => {
+      const baru = list.filter((t) => t.baru_terlambat);
+      if (baru.length > 0) {
+        setBaruTerlambat(baru);
+      }
+    };
+    const unlisten = listen<TransaksiTerlambat[]>("transaksi-terlambat", (event) => {
+      fetchNotifications();
+      tampilkanTerlambat(event.payload);
+    });
+    // Pemeriksaan pertama bisa selesai sebelum listener di atas terpasang
+    TransaksiService.getTerlambatTerakhir()
+      .then(tampilkanTerlambat)
+      .catch((err) => console.error("Failed to get overdue transaksi:", err));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Jumlah pengingat belum dibaca di inbox
+  useEffect(() => {
+    const hitung = () => {
+      PengingatService.countBelumDibaca()
+        .then(setPengingatBelumDibaca)
+        .catch((err) => console.error("Failed to count pengingat:", err));
+    };
+    hitung();
+    const unlisten = listen("pengingat-baru", hitung);
+    window.addEventListener(EVENT_PENGINGAT_BERUBAH, hitung);
+    return () => {
+      unlisten.then((fn) => fn());
+      window.removeEventListener(EVENT_PENGINGAT_BERUBAH, hitung);
+    };
+  }, []);
+
   const fetchNotifications = async () => {
     try {
       const [transaksiList, motors, penyewas] = await Promise.all([
@@ -36,7 +80,7 @@ export default function Navbar() {
       const alerts: typeof notifications = [];
 
       transaksiList.forEach((t) => {
-        if (t.status === "dipinjam") {
+        if (t.status === "dipinjam" || t.status === "terlambat") {
           const motor = motors.find((m) => m.motor_id === t.motor_id);
           const penyewa = penyewas.find((p) => p.penyewa_id === t.penyewa_id);
           const motorName = motor ? `${motor.nama} (${motor.plat})` : `Motor #${t.motor_id}`;
@@ -71,102 +115,161 @@ export default function Navbar() {
     navigate(`/transaksi/edit/${id}`);
   };
 
+  const handleTerlambatClick = (id: number) => {
+    setBaruTerlambat([]);
+    navigate(`/transaksi/edit/${id}`);
+  };
+
   return (
-    <header
-      className="
-        fixed
-        top-4
-        left-[17rem]
-        right-4
-        h-16
-        bg-slate-800/90
-        backdrop-blur-md
-        border
-        border-slate-700/50
-        rounded-xl
-        shadow-lg
-        flex
-        items-center
-        justify-between
-        px-6
-        z-40
-      "
-    >
-
-      {/* Left */}
-      <Breadcrumbs />
-
-      {/* Right */}
-      <div className="flex items-center gap-4" ref={dropdownRef}>
-
-        {/* Notification Bell */}
-        <div className="relative">
-          <button
-            onClick={() => setShowDropdown(!showDropdown)}
-            className="relative p-2 rounded-full hover:bg-slate-700 transition"
-          >
-            <Bell size={20} className="text-slate-200" />
-            {notifications.length > 0 && (
-              <span className="absolute top-1 right-1 w-2.5 h-2.5 bg-red-500 border-2 border-slate-800 rounded-full animate-pulse" />
-            )}
-          </button>
+    <>
+      <header
+        className="
+          fixed
+          top-4
+          left-[17rem]
+          right-4
+          h-16
+          bg-slate-800/90
+          backdrop-blur-md
+          border
+          border-slate-700/50
+          rounded-xl
+          shadow-lg
+          flex
+          items-center
+          justify-between
+          px-6
+          z-40
+        "
+      >
+
+        {/* Left */}
+        <Breadcrumbs />
+
+        {/* Right */}
+        <div className="flex items-center gap-4" ref={dropdownRef}>
+
+          {/* Notification Bell */}
+          <div className="relative">
+            <button
+              onClick={() => setShowDropdown(!showDropdown)}
+              className="relative p-2 rounded-full hover:bg-slate-700 transition"
+            >
+              <Bell size={20} className="text-slate-200" />
+              {(notifications.length > 0 || pengingatBelumDibaca > 0) && (
+                <span className="absolute top-1 right-1 w-2.5 h-2.5 bg-red-500 border-2 border-slate-800 rounded-full animate-pulse" />
+              )}
+            </button>
+
+            {/* Dropdown */}
+            {showDropdown && (
+              <div className="absolute right-0 mt-3 w-80 bg-slate-800 border border-slate-700 rounded-xl shadow-2xl overflow-hidden animate-in fade-in slide-in-from-top-2">
+                <div className="p-3 border-b border-slate-700 flex justify-between items-center bg-slate-900/50">
+                  <h3 className="text-sm font-semibold text-slate-200">Notifikasi ({notifications.length})</h3>
+                  <button
+                    onClick={() => setShowDropdown(false)}
+                    className="text-slate-400 hover:text-slate-200"
+                  >
+                    <X size={14} />
+                  </button>
+                </div>
+
+                <div className="max-h-[300px] overflow-y-auto scrollbar-thin scrollbar-thumb-slate-700 scrollbar-track-transparent">
+                  {notifications.length === 0 ? (
+                    <div className="p-8 text-center text-slate-500">
+                      <Bell size={24} className="mx-auto mb-2 opacity-50" />
+                      <p className="text-xs">Tidak ada notifikasi baru</p>
+                    </div>
+                  ) : (
+                    <div className="divide-y divide-slate-700/50">
+                      {notifications.map((notif) => (
+                        <button
+                          key={notif.id}
+                          onClick={() => handleNotificationClick(notif.id)}
+                          className="w-full text-left p-3 hover:bg-slate-700/50 transition active:bg-slate-700 flex gap-3 group"
+                        >
+                          <div className={`mt-1 shrink-0 ${notif.type === "overdue" ? "text-red-400" : "text-amber-400"}`}>
+                            {notif.type === "overdue" ? <AlertCircle size={16} /> : <Calendar size={16} />}
+                          </div>
+                          <div>
+                            <p className={`text-sm font-medium ${notif.type === "overdue" ? "text-red-300 group-hover:text-red-200" : "text-amber-300 group-hover:text-amber-200"}`}>
+                              {notif.message}
+                            </p>
+                            <p className="text-xs text-slate-400 mt-0.5 group-hover:text-slate-300">
+                              {notif.subtext}
+                            </p>
+                          </div>
+                        </button>
+                      ))}
+                    </div>
+                  )}
+                </div>
 
-          {/* Dropdown */}
-          {showDropdown && (
-            <div className="absolute right-0 mt-3 w-80 bg-slate-800 border border-slate-700 rounded-xl shadow-2xl overflow-hidden animate-in fade-in slide-in-from-top-2">
-              <div className="p-3 border-b border-slate-700 flex justify-between items-center bg-slate-900/50">
-                <h3 className="text-sm font-semibold text-slate-200">Notifikasi ({notifications.length})</h3>
                 <button
-                  onClick={() => setShowDropdown(false)}
-                  className="text-slate-400 hover:text-slate-200"
+                  onClick={() => {
+                    setShowDropdown(false);
+                    navigate("/pengingat");
+                  }}
+                  className="w-full p-3 border-t border-slate-700 bg-slate-900/50 hover:bg-slate-700/50 transition flex items-center justify-between text-sm"
                 >
-                  <X size={14} />
+                  <span className="flex items-center gap-2 text-slate-300">
+                    <Inbox size={14} />
+                    Lihat semua pengingat
+                  </span>
+                  {pengingatBelumDibaca > 0 && (
+                    <span className="px-2 py-0.5 rounded-full bg-red-500 text-white text-xs font-semibold">
+                      {pengingatBelumDibaca} belum dibaca
+                    </span>
+                  )}
                 </button>
               </div>
+            )}
+          </div>
 
-              <div className="max-h-[300px] overflow-y-auto scrollbar-thin scrollbar-thumb-slate-700 scrollbar-track-transparent">
-                {notifications.length === 0 ? (
-                  <div className="p-8 text-center text-slate-500">
-                    <Bell size={24} className="mx-auto mb-2 opacity-50" />
-                    <p className="text-xs">Tidak ada notifikasi baru</p>
-                  </div>
-                ) : (
-                  <div className="divide-y divide-slate-700/50">
-                    {notifications.map((notif) => (
-                      <button
-                        key={notif.id}
-                        onClick={() => handleNotificationClick(notif.id)}
-                        className="w-full text-left p-3 hover:bg-slate-700/50 transition active:bg-slate-700 flex gap-3 group"
-                      >
-                        <div className={`mt-1 shrink-0 ${notif.type === "overdue" ? "text-red-400" : "text-amber-400"}`}>
-                          {notif.type === "overdue" ? <AlertCircle size={16} /> : <Calendar size={16} />}
-                        </div>
-                        <div>
-                          <p className={`text-sm font-medium ${notif.type === "overdue" ? "text-red-300 group-hover:text-red-200" : "text-amber-300 group-hover:text-amber-200"}`}>
-                            {notif.message}
-                          </p>
-                          <p className="text-xs text-slate-400 mt-0.5 group-hover:text-slate-300">
-                            {notif.subtext}
-                          </p>
-                        </div>
-                      </button>
-                    ))}
-                  </div>
-                )}
-              </div>
+          <div className="flex items-center gap-2 cursor-pointer hover:bg-slate-700 px-3 py-1 rounded-lg transition">
+            <UserCircle size={28} className="text-slate-200" />
+            <div className="text-sm leading-tight">
+              <p className="font-medium text-slate-100">Admin</p>
+              <p className="text-xs text-slate-400">Administrator</p>
             </div>
-          )}
+          </div>
+
         </div>
+      </header>
 
-        <div className="flex items-center gap-2 cursor-pointer hover:bg-slate-700 px-3 py-1 rounded-lg transition">
-          <UserCircle size={28} className="text-slate-200" />
-          <div className="text-sm leading-tight">
-            <p className="font-medium text-slate-100">Admin</p>
-            <p className="text-xs text-slate-400">Administrator</p>
+      {/* Peringatan transaksi yang baru terlambat (di luar header karena backdrop-blur
+          membuat posisi fixed relatif ke header) */}
+      {baruTerlambat.length > 0 && (
+        <div className="fixed bottom-6 right-6 z-50">
+          <div className="bg-slate-800 border border-red-600/50 rounded-xl shadow-2xl shadow-red-900/20 p-4 flex items-start gap-3 max-w-sm">
+            <div className="p-1.5 bg-red-500/20 rounded-lg shrink-0 mt-0.5">
+              <AlertCircle size={18} className="text-red-400" />
+            </div>
+            <div className="flex-1 min-w-0">
+              <p className="text-sm font-semibold text-slate-200">
+                {baruTerlambat.length} transaksi baru terlambat
+              </p>
+              <div className="mt-1 space-y-1">
+                {baruTerlambat.map((t) => (
+                  <button
+                    key={t.transaksi_id}
+                    onClick={() => handleTerlambatClick(t.transaksi_id)}
+                    className="block w-full text-left text-xs text-slate-400 hover:text-red-300 transition truncate"
+                  >
+                    {t.nomor ?? `#${t.transaksi_id}`} — {t.motor} ({t.plat}), {t.penyewa}, {t.hari_terlambat} hari
+                  </button>
+                ))}
+              </div>
+            </div>
+            <button
+              onClick={() => setBaruTerlambat([])}
+              className="text-slate-500 hover:text-slate-300 transition shrink-0"
+            >
+              <X size={16} />
+            </button>
           </div>
         </div>
-
-      </div>
-    </header>
+      )}
+    </>
   )
 }
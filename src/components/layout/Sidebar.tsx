@@ -1,4 +1,6 @@
+import { useEffect, useState } from "react"
 import { NavLink } from "react-router-dom"
+import { listen } from "@tauri-apps/api/event"
 import {
   LayoutDashboard,
   Bike,
@@ -8,7 +10,11 @@ import {
   BarChart3,
   Settings,
   Banknote,
+  Inbox,
+  Upload,
 } from "lucide-react"
+import { PengingatService } from "../../services/pengingat.service"
+import { EVENT_PENGINGAT_BERUBAH } from "../pages/Pengingat/PengingatList"
 
 interface MenuItem {
   name: string
@@ -35,6 +41,11 @@ const menuGroups: MenuGroup[] = [
         path: "/penyewa",
         icon: <Users size={18} />,
       },
+      {
+        name: "Impor Data",
+        path: "/impor",
+        icon: <Upload size={18} />,
+      },
     ],
   },
   {
@@ -50,6 +61,11 @@ const menuGroups: MenuGroup[] = [
         path: "/bukti_Pelunasan",
         icon: <Wallet size={18} />,
       },
+      {
+        name: "Pengingat",
+        path: "/pengingat",
+        icon: <Inbox size={18} />,
+      },
     ],
   },
   {
@@ -85,6 +101,25 @@ const menuGroups: MenuGroup[] = [
 ]
 
 export default function Sidebar() {
+  const [belumDibaca, setBelumDibaca] = useState(0)
+
+  // Badge pengingat: dihitung ulang saat scheduler membuat pengingat baru
+  // atau saat halaman pengingat mengubah status dibaca
+  useEffect(() => {
+    const hitung = () => {
+      PengingatService.countBelumDibaca()
+        .then(setBelumDibaca)
+        .catch((err) => console.error("Gagal menghitung pengingat:", err))
+    }
+    hitung()
+    const unlisten = listen("pengingat-baru", hitung)
+    window.addEventListener(EVENT_PENGINGAT_BERUBAH, hitung)
+    return () => {
+      unlisten.then((fn) => fn())
+      window.removeEventListener(EVENT_PENGINGAT_BERUBAH, hitung)
+    }
+  }, [])
+
   return (
     <aside className="fixed left-0 top-0 h-screen w-64 border-r-2 border-zinc-600 text-slate-100 shadow-xl flex flex-col">
       {/* Logo */}
@@ -132,6 +167,11 @@ export default function Sidebar() {
                     {item.icon}
                   </div>
                   {item.name}
+                  {item.path === "/pengingat" && belumDibaca > 0 && (
+                    <span className="ml-auto min-w-[1.25rem] px-1.5 py-0.5 rounded-full bg-red-500 text-white text-xs font-semibold text-center">
+                      {belumDibaca > 99 ? "99+" : belumDibaca}
+                    </span>
+                  )}
                 </NavLink>
               ))}
             </div>
@@ -0,0 +1,78 @@
+import { useState } from "react";
+import { Lock, AlertCircle, RefreshCw } from "lucide-react";
+import { KeamananService } from "../../../services/keamanan.service";
+
+export default function BukaKunci({ onTerbuka }: { onTerbuka: () => void }) {
+    const [kataSandi, setKataSandi] = useState("");
+    const [memproses, setMemproses] = useState(false);
+    const [pesan, setPesan] = useState("");
+
+    const handleBukaKunci = async (e: React.FormEvent) => {
+        e.preventDefault();
+        if (!kataSandi) return;
+
+        setMemproses(true);
+        setPesan("");
+
+        try {
+            await KeamananService.bukaKunci(kataSandi);
+            onTerbuka();
+        } catch (err) {
+            console.error("Unlock failed:", err);
+            setPesan(`${err}`);
+            setKataSandi("");
+        } finally {
+            setMemproses(false);
+        }
+    };
+
+    return (
+        <div className="min-h-screen bg-slate-900 flex items-center justify-center p-4">
+            <div className="max-w-md w-full bg-slate-800 rounded-2xl border border-slate-700 shadow-2xl overflow-hidden">
+                {/* Header */}
+                <div className="bg-slate-900/50 p-6 text-center border-b border-slate-700">
+                    <div className="mx-auto w-16 h-16 bg-cyan-600/20 rounded-full flex items-center justify-center mb-4">
+                        <Lock size={32} className="text-cyan-500" />
+                    </div>
+                    <h1 className="text-2xl font-bold text-white mb-2">Database Terkunci</h1>
+                    <p className="text-slate-400 text-sm">
+                        Data rental dienkripsi. Masukkan kata sandi database untuk melanjutkan.
+                    </p>
+                </div>
+
+                <form onSubmit={handleBukaKunci} className="p-6 space-y-4">
+                    <input
+                        type="password"
+                        value={kataSandi}
+                        onChange={(e) => setKataSandi(e.target.value)}
+                        className="w-full bg-slate-900 border border-slate-600 rounded-lg py-2.5 px-4 text-white placeholder:text-slate-600 focus:outline-none focus:ring-2 focus:ring-cyan-500 focus:border-transparent transition"
+                        placeholder="Kata sandi database"
+                        disabled={memproses}
+                        autoFocus
+                    />
+
+                    {pesan && (
+                        <div className="flex items-center gap-2 text-red-400 text-sm bg-red-900/20 p-3 rounded-lg border border-red-900/50">
+                            <AlertCircle size={16} />
+                            {pesan}
+                        </div>
+                    )}
+
+                    <button
+                        type="submit"
+                        disabled={memproses || !kataSandi}
+                        className="w-full bg-cyan-600 hover:bg-cyan-500 disabled:opacity-50 disabled:cursor-not-allowed text-white font-semibold py-3 rounded-lg transition-all shadow-lg shadow-cyan-600/20 flex items-center justify-center gap-2"
+                    >
+                        {memproses ? (
+                            <>
+                                <RefreshCw size={18} className="animate-spin" /> Membuka...
+                            </>
+                        ) : (
+                            "Buka Database"
+                        )}
+                    </button>
+                </form>
+            </div>
+        </div>
+    );
+}
@@ -4,17 +4,19 @@ import { useNavigate } from "react-router-dom";
 import { ArrowLeft, Receipt, User, Bike, Calendar, DollarSign, AlertTriangle } from "lucide-react";
 import { Link } from "react-router-dom";
 
-import { Transaksi } from "../../../types/transaksi.type";
+import { Tagihan, Transaksi } from "../../../types/transaksi.type";
 import { Motor } from "../../../types/motor.type";
 import { Penyewa } from "../../../types/penyewa.type";
 import { BuktiPelunasan } from "../../../types/bukti_pelunasan.type";
 import { getMotor } from "../../../services/motor.service";
 import { PenyewaService, TransaksiService } from "../../../services/penyewa.service";
+import { LampiranService } from "../../../services/lampiran.service";
 
 import Button from "@/components/ui/Button";
 import Input from "@/components/ui/Input";
 import Select from "../../ui/Select";
 import FileInput from "../../ui/FileInput";
+import RincianTagihan from "../Transaksi/RincianTagihan";
 
 export default function BuktiPelunasanAdd() {
     const navigate = useNavigate();
@@ -22,6 +24,7 @@ export default function BuktiPelunasanAdd() {
     const [motors, setMotors] = useState<Motor[]>([]);
     const [penyewas, setPenyewas] = useState<Penyewa[]>([]);
     const [existingBukti, setExistingBukti] = useState<BuktiPelunasan[]>([]);
+    const [tagihanList, setTagihanList] = useState<Tagihan[]>([]);
     const [loadingData, setLoadingData] = useState(true);
     const [submitting, setSubmitting] = useState(false);
     const [error, setError] = useState("");
@@ -44,16 +47,18 @@ export default function BuktiPelunasanAdd() {
     const fetchReferenceData = async () => {
         try {
             setLoadingData(true);
-            const [t, m, p, b] = await Promise.all([
+            const [t, m, p, b, tg] = await Promise.all([
                 TransaksiService.getAll(),
                 getMotor(),
                 PenyewaService.getAll(),
                 invoke<BuktiPelunasan[]>("get_all_bukti_pelunasan"),
+                invoke<Tagihan[]>("get_all_tagihan"),
             ]);
             setTransaksiList(t);
             setMotors(m);
             setPenyewas(p);
             setExistingBukti(b);
+            setTagihanList(tg);
         } catch (err) {
             console.error("Failed to fetch reference data:", err);
             setError("Gagal memuat data referensi");
@@ -65,9 +70,12 @@ export default function BuktiPelunasanAdd() {
     // Get transaksi_id yang sudah lunas (sudah punya bukti pelunasan)
     const paidTransaksiIds = new Set(existingBukti.map((b) => b.transaksi_id));
 
-    // Filter: hanya tampilkan transaksi yang BELUM ada bukti pelunasannya
+    // Filter: tampilkan transaksi yang belum ada bukti pelunasannya, atau yang
+    // tagihannya bertambah (denda, km, BBM, kerusakan) sehingga masih ada sisa
     const availableTransaksi = transaksiList.filter(
-        (t) => !paidTransaksiIds.has(t.transaksi_id)
+        (t) =>
+            !paidTransaksiIds.has(t.transaksi_id) ||
+            (tagihanList.find((tg) => tg.transaksi_id === t.transaksi_id)?.sisa_bayar ?? 0) > 0
     );
 
     const getMotorName = (id: number) => {
@@ -93,12 +101,17 @@ export default function BuktiPelunasanAdd() {
         (t) => t.transaksi_id === bukti.transaksi_id
     );
 
+    const getTagihan = (transaksiId: number) =>
+        tagihanList.find((t) => t.transaksi_id === transaksiId) ?? null;
+
+    const selectedTagihan = getTagihan(bukti.transaksi_id);
+
+    // Default jumlah bayar = sisa tagihan (sewa + denda + km + BBM + kerusakan - yang sudah dibayar)
     const handleTransaksiChange = (transaksiId: number) => {
-        const transaksi = transaksiList.find((t) => t.transaksi_id === transaksiId);
         setBukti({
             ...bukti,
             transaksi_id: transaksiId,
-            jumlah_bayar: (transaksi?.total_bayar ?? 0) + (transaksi?.denda ?? 0),
+            jumlah_bayar: getTagihan(transaksiId)?.sisa_bayar ?? 0,
         });
     };
 
@@ -107,15 +120,6 @@ export default function BuktiPelunasanAdd() {
         label: `#${t.transaksi_id} — ${getPenyewaName(t.penyewa_id)} — ${getMotorName(t.motor_id)}`,
     }));
 
-    const fileToBase64 = (file: File): Promise<string> => {
-        return new Promise((resolve, reject) => {
-            const reader = new FileReader();
-            reader.readAsDataURL(file);
-            reader.onload = () => resolve(reader.result as string);
-            reader.onerror = (error) => reject(error);
-        });
-    };
-
     const handleFormSubmit = (e: React.FormEvent) => {
         e.preventDefault();
         setError("");
@@ -138,8 +142,7 @@ export default function BuktiPelunasanAdd() {
             let fotoPath = bukti.foto_bukti;
 
             if (selectedFile) {
-                const base64 = await fileToBase64(selectedFile);
-                fotoPath = await invoke<string>("save_bukti_pelunasan_image", { base64 });
+                fotoPath = await LampiranService.unggah(selectedFile);
             }
 
             await invoke("create_bukti_pelunasan", {
@@ -208,7 +211,7 @@ export default function BuktiPelunasanAdd() {
                             )}
 
                             <p className="text-xs text-amber-400/80">
-                                Setelah dicatat, transaksi ini akan ditandai sebagai lunas dan tidak bisa dipilih lagi.
+                                Setelah dicatat, transaksi yang sisa tagihannya sudah nol tidak bisa dipilih lagi.
                             </p>
                         </div>
 
@@ -389,25 +392,17 @@ export default function BuktiPelunasanAdd() {
 
                                 <div className="flex items-start gap-3">
                                     <DollarSign size={16} className="text-emerald-400 mt-0.5 shrink-0" />
-                                    <div>
-                                        <p className="text-xs text-slate-500">Total Bayar</p>
-                                        <p className="text-lg text-emerald-400 font-bold">
-                                            {formatCurrency(selectedTransaksi.total_bayar)}
-                                        </p>
-                                    </div>
-                                </div>
-
-                                {selectedTransaksi.denda != null && selectedTransaksi.denda > 0 && (
-                                    <div className="flex items-start gap-3">
-                                        <DollarSign size={16} className="text-red-400 mt-0.5 shrink-0" />
-                                        <div>
-                                            <p className="text-xs text-slate-500">Denda</p>
-                                            <p className="text-sm text-red-400 font-semibold">
-                                                {formatCurrency(selectedTransaksi.denda)}
+                                    <div className="flex-1">
+                                        <p className="text-xs text-slate-500 mb-1">Tagihan</p>
+                                        {selectedTagihan ? (
+                                            <RincianTagihan tagihan={selectedTagihan} />
+                                        ) : (
+                                            <p className="text-lg text-emerald-400 font-bold">
+                                                {formatCurrency(selectedTransaksi.total_bayar)}
                                             </p>
-                                        </div>
+                                        )}
                                     </div>
-                                )}
+                                </div>
 
                                 <div className="mt-2">
                                     <span
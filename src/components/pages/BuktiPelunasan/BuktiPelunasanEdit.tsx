@@ -3,17 +3,19 @@ import { invoke } from "@tauri-apps/api/core";
 import { useNavigate, useParams, Link } from "react-router-dom";
 import { ArrowLeft, Receipt, User, Bike, Calendar, DollarSign } from "lucide-react";
 
-import { Transaksi } from "../../../types/transaksi.type";
+import { Tagihan, Transaksi } from "../../../types/transaksi.type";
 import { Motor } from "../../../types/motor.type";
 import { Penyewa } from "../../../types/penyewa.type";
 import { BuktiPelunasan } from "../../../types/bukti_pelunasan.type";
 import { getMotor } from "../../../services/motor.service";
 import { PenyewaService, TransaksiService } from "../../../services/penyewa.service";
+import { LampiranService } from "../../../services/lampiran.service";
 
 import Button from "../../ui/Button";
 import Input from "../../ui/Input";
 import Select from "../../ui/Select";
 import FileInput from "../../ui/FileInput";
+import RincianTagihan from "../Transaksi/RincianTagihan";
 
 export default function BuktiPelunasanEdit() {
     const { id } = useParams<{ id: string }>();
@@ -23,6 +25,7 @@ export default function BuktiPelunasanEdit() {
     const [transaksiList, setTransaksiList] = useState<Transaksi[]>([]);
     const [motors, setMotors] = useState<Motor[]>([]);
     const [penyewas, setPenyewas] = useState<Penyewa[]>([]);
+    const [tagihanList, setTagihanList] = useState<Tagihan[]>([]);
     const [loading, setLoading] = useState(true);
     const [submitting, setSubmitting] = useState(false);
     const [error, setError] = useState("");
@@ -35,16 +38,18 @@ export default function BuktiPelunasanEdit() {
     const fetchData = async () => {
         try {
             setLoading(true);
-            const [buktiResult, t, m, p] = await Promise.all([
+            const [buktiResult, t, m, p, tg] = await Promise.all([
                 invoke<BuktiPelunasan>("get_bukti_pelunasan_by_id", { id: parseInt(id!) }),
                 TransaksiService.getAll(),
                 getMotor(),
                 PenyewaService.getAll(),
+                invoke<Tagihan[]>("get_all_tagihan"),
             ]);
             setBukti(buktiResult);
             setTransaksiList(t);
             setMotors(m);
             setPenyewas(p);
+            setTagihanList(tg);
         } catch (err) {
             console.error("Failed to fetch data:", err);
             setError("Gagal memuat data");
@@ -76,13 +81,18 @@ export default function BuktiPelunasanEdit() {
         (t) => t.transaksi_id === bukti?.transaksi_id
     );
 
+    const getTagihan = (transaksiId?: number) =>
+        tagihanList.find((t) => t.transaksi_id === transaksiId) ?? null;
+
+    const selectedTagihan = getTagihan(bukti?.transaksi_id);
+
+    // Default jumlah bayar = sisa tagihan transaksi yang baru dipilih
     const handleTransaksiChange = (transaksiId: number) => {
         if (!bukti) return;
-        const transaksi = transaksiList.find((t) => t.transaksi_id === transaksiId);
         setBukti({
             ...bukti,
             transaksi_id: transaksiId,
-            jumlah_bayar: (transaksi?.total_bayar ?? 0) + (transaksi?.denda ?? 0),
+            jumlah_bayar: getTagihan(transaksiId)?.sisa_bayar ?? 0,
         });
     };
 
@@ -91,15 +101,6 @@ export default function BuktiPelunasanEdit() {
         label: `#${t.transaksi_id} — ${getPenyewaName(t.penyewa_id)} — ${getMotorName(t.motor_id)}`,
     }));
 
-    const fileToBase64 = (file: File): Promise<string> => {
-        return new Promise((resolve, reject) => {
-            const reader = new FileReader();
-            reader.readAsDataURL(file);
-            reader.onload = () => resolve(reader.result as string);
-            reader.onerror = (error) => reject(error);
-        });
-    };
-
     const handleSubmit = async (e: React.FormEvent) => {
         e.preventDefault();
         if (!bukti) return;
@@ -111,8 +112,7 @@ export default function BuktiPelunasanEdit() {
             let fotoPath = bukti.foto_bukti;
 
             if (selectedFile) {
-                const base64 = await fileToBase64(selectedFile);
-                fotoPath = await invoke<string>("save_bukti_pelunasan_image", { base64 });
+                fotoPath = await LampiranService.unggah(selectedFile);
             }
 
             await invoke("update_bukti_pelunasan", {
@@ -279,22 +279,15 @@ export default function BuktiPelunasanEdit() {
                                 <hr className="border-slate-700" />
                                 <div className="flex items-start gap-3">
                                     <DollarSign size={16} className="text-emerald-400 mt-0.5 shrink-0" />
-                                    <div>
-                                        <p className="text-xs text-slate-500">Total Bayar</p>
-                                        <p className="text-lg text-emerald-400 font-bold">{formatCurrency(selectedTransaksi.total_bayar)}</p>
+                                    <div className="flex-1">
+                                        <p className="text-xs text-slate-500 mb-1">Tagihan</p>
+                                        {selectedTagihan ? (
+                                            <RincianTagihan tagihan={selectedTagihan} />
+                                        ) : (
+                                            <p className="text-lg text-emerald-400 font-bold">{formatCurrency(selectedTransaksi.total_bayar)}</p>
+                                        )}
                                     </div>
                                 </div>
-                                {selectedTransaksi.denda != null && selectedTransaksi.denda > 0 && (
-                                    <div className="flex items-start gap-3">
-                                        <DollarSign size={16} className="text-red-400 mt-0.5 shrink-0" />
-                                        <div>
-                                            <p className="text-xs text-slate-500">Denda</p>
-                                            <p className="text-sm text-red-400 font-semibold">
-                                                {formatCurrency(selectedTransaksi.denda)}
-                                            </p>
-                                        </div>
-                                    </div>
-                                )}
                             </div>
                         ) : (
                             <div className="text-center py-6">
@@ -162,7 +162,7 @@ export default function BuktiPelunasanLihat() {
                                 <p className="text-xs text-slate-500">Foto Bukti</p>
                                 {bukti.foto_bukti ? (
                                     <img
-                                        src={convertFileSrc(bukti.foto_bukti)}
+                                        src={convertFileSrc(bukti.foto_bukti_thumbnail ?? bukti.foto_bukti)}
                                         alt="Bukti Pelunasan"
                                         className="w-full h-auto rounded-lg mt-2 cursor-pointer hover:opacity-90 transition"
                                         onClick={() => setIsImageOpen(true)}
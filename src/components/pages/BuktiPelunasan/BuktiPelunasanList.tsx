@@ -1,5 +1,5 @@
 import { useEffect, useState } from "react";
-import { invoke } from "@tauri-apps/api/core";
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
 import { Link } from "react-router-dom";
 import { Plus, Search, Calendar, User, Receipt, FileSpreadsheet, FileText, CheckCircle, FolderOpen, X } from "lucide-react";
 import * as XLSX from "xlsx";
@@ -7,7 +7,7 @@ import jsPDF from "jspdf";
 import autoTable from "jspdf-autotable";
 
 import { BuktiPelunasan } from "../../../types/bukti_pelunasan.type";
-import { Transaksi } from "../../../types/transaksi.type";
+import { Tagihan, Transaksi } from "../../../types/transaksi.type";
 import { Penyewa } from "../../../types/penyewa.type";
 import { PenyewaService, TransaksiService } from "../../../services/penyewa.service";
 
@@ -25,6 +25,7 @@ export default function BuktiPelunasanList() {
     const [buktiList, setBuktiList] = useState<BuktiPelunasan[]>([]);
     const [transaksiList, setTransaksiList] = useState<Transaksi[]>([]);
     const [penyewaList, setPenyewaList] = useState<Penyewa[]>([]);
+    const [tagihanList, setTagihanList] = useState<Tagihan[]>([]);
 
     const [searchTerm, setSearchTerm] = useState("");
     const [metodeFilter, setMetodeFilter] = useState("semua");
@@ -51,14 +52,16 @@ export default function BuktiPelunasanList() {
     const fetchData = async () => {
         try {
             setLoading(true);
-            const [b, t, p] = await Promise.all([
+            const [b, t, p, tg] = await Promise.all([
                 invoke<BuktiPelunasan[]>("get_all_bukti_pelunasan"),
                 TransaksiService.getAll(),
                 PenyewaService.getAll(),
+                invoke<Tagihan[]>("get_all_tagihan"),
             ]);
             setBuktiList(b.sort((a, b) => b.bukti_id - a.bukti_id));
             setTransaksiList(t);
             setPenyewaList(p);
+            setTagihanList(tg);
         } catch (err) {
             console.error("Failed to fetch data:", err);
         } finally {
@@ -215,14 +218,18 @@ export default function BuktiPelunasanList() {
     };
 
 
+    const getTagihan = (transaksiId: number) => {
+        return tagihanList.find((t) => t.transaksi_id === transaksiId) || null;
+    };
+
+    // Total tagihan dari backend: sewa + denda + kelebihan km + BBM + kerusakan
     const getTotalBayarWajib = (transaksiId: number) => {
-        const trx = getTransaksi(transaksiId);
-        return (trx?.total_bayar ?? 0) + (trx?.denda ?? 0);
+        return getTagihan(transaksiId)?.total_tagihan ?? 0;
     };
 
+    // Sisa dihitung dari semua pembayaran transaksi, bukan hanya bukti ini
     const getKurang = (bukti: BuktiPelunasan) => {
-        const totalWajib = getTotalBayarWajib(bukti.transaksi_id);
-        return totalWajib - bukti.jumlah_bayar;
+        return getTagihan(bukti.transaksi_id)?.sisa_bayar ?? 0;
     };
 
     const statusLunasBadge = (bukti: BuktiPelunasan) => {
@@ -317,6 +324,24 @@ export default function BuktiPelunasanList() {
                 );
             }
         },
+        {
+            header: "Foto",
+            accessor: "foto_bukti" as const,
+            width: "60px",
+            render: (_: any, row: BuktiPelunasan) =>
+                row.foto_bukti ? (
+                    <Link to={`/bukti_pelunasan/lihat/${row.bukti_id}`}>
+                        <img
+                            src={convertFileSrc(row.foto_bukti_thumbnail ?? row.foto_bukti)}
+                            alt="Bukti"
+                            className="w-10 h-10 rounded object-cover border border-slate-700"
+                            loading="lazy"
+                        />
+                    </Link>
+                ) : (
+                    <span className="text-xs text-slate-500">-</span>
+                ),
+        },
         {
             header: "Tanggal Bayar",
             accessor: "tanggal_bayar" as const,
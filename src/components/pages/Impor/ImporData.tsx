@@ -0,0 +1,261 @@
+import { useState } from "react";
+import { open } from "@tauri-apps/plugin-dialog";
+import { AlertCircle, CheckCircle2, Copy, FileSpreadsheet, Search, Upload } from "lucide-react";
+import { ImporService } from "../../../services/impor.service";
+import { ImporLaporan, JenisImpor, PemetaanKolom, StatusBarisImpor } from "../../../types/impor.type";
+
+// Field yang dikenali backend untuk tiap jenis impor (sama dengan KOLOM_MOTOR / KOLOM_PENYEWA)
+const kolomImpor: Record<JenisImpor, { field: string; label: string; wajib: boolean }[]> = {
+    motor: [
+        { field: "nama", label: "Nama", wajib: true },
+        { field: "plat", label: "Plat", wajib: true },
+        { field: "tipe_motor", label: "Tipe Motor", wajib: false },
+        { field: "tahun", label: "Tahun", wajib: false },
+        { field: "harga_harian", label: "Harga Harian", wajib: true },
+        { field: "odometer", label: "Odometer", wajib: false },
+    ],
+    penyewa: [
+        { field: "nama", label: "Nama", wajib: true },
+        { field: "no_hp", label: "No. HP", wajib: false },
+        { field: "no_ktp", label: "No. KTP", wajib: true },
+        { field: "alamat", label: "Alamat", wajib: false },
+    ],
+};
+
+const statusInfo: Record<StatusBarisImpor, { label: string; icon: React.ReactNode; warna: string }> = {
+    valid: { label: "Valid", icon: <CheckCircle2 size={14} />, warna: "text-emerald-400" },
+    duplikat: { label: "Duplikat", icon: <Copy size={14} />, warna: "text-amber-400" },
+    gagal: { label: "Gagal", icon: <AlertCircle size={14} />, warna: "text-red-400" },
+};
+
+const normalisasiHeader = (h: string) => h.trim().toLowerCase().replace(/[ -]/g, "_");
+
+export default function ImporData() {
+    const [jenis, setJenis] = useState<JenisImpor>("motor");
+    const [filePath, setFilePath] = useState("");
+    const [header, setHeader] = useState<string[]>([]);
+    const [mapping, setMapping] = useState<PemetaanKolom>({});
+    const [laporan, setLaporan] = useState<ImporLaporan | null>(null);
+    const [loading, setLoading] = useState(false);
+    const [error, setError] = useState("");
+
+    // Kolom yang namanya sama dengan field langsung dipetakan, sisanya dipilih manual
+    const petakanOtomatis = (j: JenisImpor, kolomFile: string[]): PemetaanKolom => {
+        const hasil: PemetaanKolom = {};
+        for (const { field } of kolomImpor[j]) {
+            const cocok = kolomFile.find((h) => normalisasiHeader(h) === field);
+            if (cocok) hasil[field] = cocok;
+        }
+        return hasil;
+    };
+
+    const gantiJenis = (j: JenisImpor) => {
+        setJenis(j);
+        setMapping(petakanOtomatis(j, header));
+        setLaporan(null);
+    };
+
+    const pilihFile = async () => {
+        const selected = await open({
+            title: "Pilih File Impor",
+            filters: [{ name: "CSV / Excel", extensions: ["csv", "txt", "xlsx", "xlsm", "xls", "ods"] }],
+            multiple: false,
+            directory: false,
+        });
+        if (!selected) return;
+
+        try {
+            setLoading(true);
+            setError("");
+            setLaporan(null);
+            const kolomFile = await ImporService.getHeader(selected);
+            setFilePath(selected);
+            setHeader(kolomFile);
+            setMapping(petakanOtomatis(jenis, kolomFile));
+        } catch (err) {
+            console.error("Gagal membaca file impor:", err);
+            setError(String(err));
+            setFilePath("");
+            setHeader([]);
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const handlePreview = async () => {
+        try {
+            setLoading(true);
+            setError("");
+            setLaporan(await ImporService.preview(jenis, filePath, mapping));
+        } catch (err) {
+            console.error("Gagal preview impor:", err);
+            setError(String(err));
+            setLaporan(null);
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const handleImpor = async () => {
+        if (!laporan || laporan.valid === 0) return;
+        if (!confirm(`Impor ${laporan.valid} data ${jenis}? Baris duplikat dan gagal akan dilewati.`)) return;
+        try {
+            setLoading(true);
+            setError("");
+            setLaporan(await ImporService.jalankan(jenis, filePath, mapping));
+        } catch (err) {
+            console.error("Gagal impor:", err);
+            setError(String(err));
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const namaFile = filePath.split(/[\\/]/).pop();
+    const wajibBelumDipetakan = kolomImpor[jenis].filter((k) => k.wajib && !mapping[k.field]);
+
+    return (
+        <div className="space-y-6">
+            <div className="bg-slate-800 rounded-xl border border-slate-700 overflow-hidden">
+                {/* Header */}
+                <div className="p-5 flex flex-col md:flex-row md:items-center justify-between gap-4">
+                    <div>
+                        <h2 className="text-xl font-bold text-slate-100">Impor Data</h2>
+                        <p className="text-sm text-slate-400">
+                            Tambah data motor atau penyewa sekaligus dari file CSV / Excel
+                        </p>
+                    </div>
+
+                    <div className="flex bg-slate-900/50 border border-slate-700 rounded-lg p-1">
+                        {(["motor", "penyewa"] as JenisImpor[]).map((j) => (
+                            <button
+                                key={j}
+                                onClick={() => gantiJenis(j)}
+                                disabled={loading}
+                                className={`px-3 py-1.5 rounded-md text-sm capitalize transition ${jenis === j ? "bg-blue-600 text-white" : "text-slate-400 hover:text-slate-200"}`}
+                            >
+                                {j}
+                            </button>
+                        ))}
+                    </div>
+                </div>
+
+                <div className="px-5 pb-5 space-y-4">
+                    <div className="flex items-center gap-3">
+                        <button
+                            onClick={pilihFile}
+                            disabled={loading}
+                            className="flex items-center gap-1.5 px-3 py-2 bg-slate-700 hover:bg-slate-600 text-slate-200 rounded-lg text-sm transition disabled:opacity-50"
+                        >
+                            <FileSpreadsheet size={16} />
+                            <span>Pilih File</span>
+                        </button>
+                        <span className="text-sm text-slate-400 truncate">
+                            {namaFile || "Belum ada file dipilih"}
+                        </span>
+                    </div>
+
+                    {error && (
+                        <div className="p-3 bg-red-500/10 border border-red-700/50 rounded-lg text-sm text-red-300">
+                            {error}
+                        </div>
+                    )}
+
+                    {/* Pemetaan kolom */}
+                    {header.length > 0 && (
+                        <div>
+                            <p className="text-sm font-medium text-slate-300 mb-2">Pemetaan Kolom</p>
+                            <div className="grid grid-cols-1 md:grid-cols-2 gap-3">
+                                {kolomImpor[jenis].map(({ field, label, wajib }) => (
+                                    <label key={field} className="flex items-center gap-3">
+                                        <span className="w-32 shrink-0 text-sm text-slate-400">
+                                            {label}
+                                            {wajib && <span className="text-red-400"> *</span>}
+                                        </span>
+                                        <select
+                                            value={mapping[field] ?? ""}
+                                            onChange={(e) => {
+                                                const baru = { ...mapping };
+                                                if (e.target.value) baru[field] = e.target.value;
+                                                else delete baru[field];
+                                                setMapping(baru);
+                                                setLaporan(null);
+                                            }}
+                                            className="flex-1 px-3 py-2 bg-slate-900/50 border border-slate-700 rounded-lg text-sm text-slate-200 focus:outline-none focus:border-blue-500"
+                                        >
+                                            <option value="">— Tidak dipetakan —</option>
+                                            {header.map((h, i) => (
+                                                <option key={`${h}-${i}`} value={h}>
+                                                    {h}
+                                                </option>
+                                            ))}
+                                        </select>
+                                    </label>
+                                ))}
+                            </div>
+
+                            <div className="flex items-center justify-end gap-2 mt-4">
+                                {wajibBelumDipetakan.length > 0 && (
+                                    <span className="mr-auto text-xs text-amber-400">
+                                        Kolom wajib belum dipetakan: {wajibBelumDipetakan.map((k) => k.label).join(", ")}
+                                    </span>
+                                )}
+                                <button
+                                    onClick={handlePreview}
+                                    disabled={loading}
+                                    className="flex items-center gap-1.5 px-3 py-2 bg-blue-600/20 hover:bg-blue-600/30 text-blue-400 border border-blue-700/50 rounded-lg text-sm transition disabled:opacity-50 disabled:cursor-not-allowed"
+                                >
+                                    <Search size={16} />
+                                    <span>Periksa</span>
+                                </button>
+                                <button
+                                    onClick={handleImpor}
+                                    disabled={loading || !laporan?.dry_run || laporan.valid === 0}
+                                    className="flex items-center gap-1.5 px-3 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-lg text-sm transition disabled:opacity-50 disabled:cursor-not-allowed"
+                                >
+                                    <Upload size={16} />
+                                    <span>Impor</span>
+                                </button>
+                            </div>
+                        </div>
+                    )}
+                </div>
+            </div>
+
+            {/* Laporan hasil periksa / impor */}
+            {laporan && (
+                <div className="bg-slate-800 rounded-xl border border-slate-700 overflow-hidden">
+                    <div className="p-5">
+                        <h3 className="text-lg font-semibold text-slate-100">
+                            {laporan.dry_run ? "Hasil Pemeriksaan" : "Impor Selesai"}
+                        </h3>
+                        <p className="text-sm text-slate-400">
+                            {laporan.total_baris} baris · {laporan.valid} valid · {laporan.duplikat} duplikat · {laporan.gagal} gagal
+                            {!laporan.dry_run && ` · ${laporan.diimpor} data ${laporan.jenis} ditambahkan`}
+                        </p>
+                    </div>
+
+                    {laporan.baris.some((b) => b.status !== "valid") && (
+                        <div className="divide-y divide-slate-700/50 border-t border-slate-700 max-h-96 overflow-y-auto">
+                            {laporan.baris
+                                .filter((b) => b.status !== "valid")
+                                .map((b) => {
+                                    const info = statusInfo[b.status] ?? statusInfo.gagal;
+                                    return (
+                                        <div key={b.baris} className="flex items-start gap-3 px-5 py-3 text-sm">
+                                            <span className="w-20 shrink-0 text-slate-500">Baris {b.baris}</span>
+                                            <span className={`w-24 shrink-0 flex items-center gap-1 ${info.warna}`}>
+                                                {info.icon}
+                                                {info.label}
+                                            </span>
+                                            <span className="text-slate-300">{b.pesan}</span>
+                                        </div>
+                                    );
+                                })}
+                        </div>
+                    )}
+                </div>
+            )}
+        </div>
+    );
+}
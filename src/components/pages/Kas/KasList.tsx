@@ -147,7 +147,7 @@ export default function KasList() {
             entries.push({
                 id: b.bukti_id,
                 tanggal: b.tanggal_bayar,
-                noRef: `TRX-${String(b.transaksi_id).padStart(4, "0")}`,
+                noRef: b.nomor || `TRX-${String(b.transaksi_id).padStart(4, "0")}`,
                 customer: trx ? getPenyewaName(trx.penyewa_id) : "-",
                 motor: trx ? getMotorName(trx.motor_id) : "-",
                 metode: b.metode_bayar || "Kas",
@@ -163,7 +163,7 @@ export default function KasList() {
                 entries.push({
                     id: b.bukti_id + 500000, // Safe offset
                     tanggal: b.tanggal_bayar,
-                    noRef: `DDA-${String(b.transaksi_id).padStart(4, "0")}`,
+                    noRef: trx.nomor ? `DDA-${trx.nomor}` : `DDA-${String(b.transaksi_id).padStart(4, "0")}`,
                     customer: getPenyewaName(trx.penyewa_id),
                     motor: getMotorName(trx.motor_id),
                     metode: b.metode_bayar || "Kas",
@@ -178,7 +178,7 @@ export default function KasList() {
         ...pengeluaranList.map((p) => ({
             id: p.pengeluaran_id + 100000,
             tanggal: p.tanggal,
-            noRef: `PGL-${String(p.pengeluaran_id).padStart(4, "0")}`,
+            noRef: p.nomor || `PGL-${String(p.pengeluaran_id).padStart(4, "0")}`,
             customer: "-",
             motor: "-",
             metode: p.sumber_dana || "Kas",
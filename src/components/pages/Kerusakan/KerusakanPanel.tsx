@@ -0,0 +1,206 @@
+import { useEffect, useState } from "react";
+import { convertFileSrc } from "@tauri-apps/api/core";
+import { Link } from "react-router-dom";
+import { AlertOctagon, Plus, Trash2, Upload, X } from "lucide-react";
+import { KerusakanService } from "@/services/kerusakan.service";
+import { LampiranService } from "@/services/lampiran.service";
+import { Kerusakan } from "@/types/kerusakan.type";
+import { FormGroup, Input, Label } from "@/components/ui/Form";
+
+interface KerusakanPanelProps {
+    // Dengan transaksiId kerusakan bisa ditambah/dihapus, dengan motorId hanya riwayat
+    transaksiId?: number;
+    motorId?: number;
+    // Dipanggil setelah data berubah, misalnya untuk memuat ulang tagihan
+    onChange?: () => void;
+}
+
+const formatCurrency = (amount: number) =>
+    new Intl.NumberFormat("id-ID", { style: "currency", currency: "IDR", maximumFractionDigits: 0 }).format(amount);
+
+export default function KerusakanPanel({ transaksiId, motorId, onChange }: KerusakanPanelProps) {
+    const bisaUbah = transaksiId != null;
+    const formKosong = (): Kerusakan => ({
+        kerusakan_id: 0,
+        transaksi_id: transaksiId ?? 0,
+        tanggal: new Date().toISOString().slice(0, 10),
+        deskripsi: "",
+        foto: [],
+        estimasi_biaya: 0,
+        biaya_dibebankan: 0,
+    });
+
+    const [riwayat, setRiwayat] = useState<Kerusakan[]>([]);
+    const [showForm, setShowForm] = useState(false);
+    const [form, setForm] = useState<Kerusakan>(formKosong());
+    const [saving, setSaving] = useState(false);
+
+    const fetchRiwayat = async () => {
+        try {
+            setRiwayat(await KerusakanService.getAll({ transaksiId, motorId }));
+        } catch (err) {
+            console.error("Gagal ambil riwayat kerusakan", err);
+        }
+    };
+
+    useEffect(() => {
+        fetchRiwayat();
+    }, [transaksiId, motorId]);
+
+    const handleFoto = async (e: React.ChangeEvent<HTMLInputElement>) => {
+        const file = e.target.files?.[0];
+        if (!file) return;
+        try {
+            const path = await LampiranService.unggah(file);
+            setForm((f) => ({ ...f, foto: [...f.foto, path] }));
+        } catch (err) {
+            alert("Gagal mengunggah foto: " + err);
+        }
+    };
+
+    const handleSimpan = async () => {
+        if (!form.deskripsi.trim()) {
+            alert("Deskripsi kerusakan wajib diisi");
+            return;
+        }
+        if (form.estimasi_biaya < 0 || form.biaya_dibebankan < 0) {
+            alert("Biaya kerusakan tidak boleh negatif");
+            return;
+        }
+        setSaving(true);
+        try {
+            await KerusakanService.create(form);
+            setForm(formKosong());
+            setShowForm(false);
+            await fetchRiwayat();
+            onChange?.();
+        } catch (err) {
+            alert("Gagal menyimpan kerusakan: " + err);
+        } finally {
+            setSaving(false);
+        }
+    };
+
+    const handleHapus = async (k: Kerusakan) => {
+        if (!confirm(`Hapus catatan kerusakan "${k.deskripsi}"?`)) return;
+        try {
+            await KerusakanService.delete(k.kerusakan_id);
+            await fetchRiwayat();
+            onChange?.();
+        } catch (err) {
+            alert("Gagal menghapus kerusakan: " + err);
+        }
+    };
+
+    const totalDibebankan = riwayat.reduce((sum, k) => sum + k.biaya_dibebankan, 0);
+
+    return (
+        <div className="bg-slate-800 rounded-xl border border-slate-700 p-5">
+            <div className="flex items-center justify-between mb-3">
+                <h3 className="text-slate-200 font-semibold flex items-center gap-2">
+                    <AlertOctagon size={16} className="text-red-400" />
+                    Riwayat Kerusakan
+                </h3>
+                {bisaUbah && (
+                    <button
+                        type="button"
+                        onClick={() => setShowForm(!showForm)}
+                        className="flex items-center gap-1 px-2.5 py-1.5 bg-red-600/20 hover:bg-red-600/30 text-red-400 border border-red-700/50 rounded-lg text-xs transition"
+                    >
+                        {showForm ? <X size={14} /> : <Plus size={14} />}
+                        <span>{showForm ? "Batal" : "Catat Kerusakan"}</span>
+                    </button>
+                )}
+            </div>
+
+            {showForm && (
+                <div className="space-y-3 mb-4 p-3 bg-slate-900/50 border border-slate-700 rounded-lg">
+                    <div className="grid grid-cols-1 md:grid-cols-3 gap-3">
+                        <FormGroup>
+                            <Label required>Tanggal</Label>
+                            <Input type="date" value={form.tanggal} onChange={(e) => setForm({ ...form, tanggal: e.target.value })} />
+                        </FormGroup>
+                        <FormGroup>
+                            <Label>Estimasi Biaya</Label>
+                            <Input type="number" min={0} value={form.estimasi_biaya} onChange={(e) => setForm({ ...form, estimasi_biaya: Number(e.target.value) || 0 })} />
+                        </FormGroup>
+                        <FormGroup>
+                            <Label>Dibebankan ke Penyewa</Label>
+                            <Input type="number" min={0} value={form.biaya_dibebankan} onChange={(e) => setForm({ ...form, biaya_dibebankan: Number(e.target.value) || 0 })} />
+                        </FormGroup>
+                    </div>
+                    <FormGroup>
+                        <Label required>Deskripsi</Label>
+                        <Input placeholder="Spion kiri patah" value={form.deskripsi} onChange={(e) => setForm({ ...form, deskripsi: e.target.value })} />
+                    </FormGroup>
+                    <div className="flex items-center gap-3">
+                        <label className="flex items-center gap-2 px-3 py-1.5 bg-slate-700 hover:bg-slate-600 rounded-lg cursor-pointer transition text-xs">
+                            <Upload size={14} />
+                            Tambah Foto
+                            <input type="file" accept="image/*" className="hidden" onChange={handleFoto} />
+                        </label>
+                        {form.foto.length > 0 && <span className="text-xs text-slate-400">{form.foto.length} foto</span>}
+                    </div>
+                    <button
+                        type="button"
+                        onClick={handleSimpan}
+                        disabled={saving}
+                        className="w-full px-3 py-2 bg-red-600 hover:bg-red-700 text-white rounded-lg text-sm transition disabled:opacity-50"
+                    >
+                        {saving ? "Menyimpan..." : "Simpan Kerusakan"}
+                    </button>
+                </div>
+            )}
+
+            {riwayat.length === 0 ? (
+                <p className="text-sm text-slate-500">Belum ada catatan kerusakan</p>
+            ) : (
+                <>
+                    <div className="space-y-2 max-h-96 overflow-y-auto">
+                        {riwayat.map((k) => (
+                            <div key={k.kerusakan_id} className="flex items-start justify-between gap-2 p-3 bg-slate-900/40 border border-slate-700/50 rounded-lg">
+                                <div className="min-w-0 text-sm space-y-1">
+                                    <p className="text-slate-200 font-medium">{k.deskripsi}</p>
+                                    <p className="text-xs text-slate-400">
+                                        {k.tanggal} · estimasi {formatCurrency(k.estimasi_biaya)} · dibebankan {formatCurrency(k.biaya_dibebankan)}
+                                    </p>
+                                    {!bisaUbah && (
+                                        <Link to={`/transaksi/edit/${k.transaksi_id}`} className="text-xs text-blue-400 hover:underline">
+                                            Transaksi #{k.transaksi_id}
+                                        </Link>
+                                    )}
+                                    {k.foto.length > 0 && (
+                                        <div className="flex gap-1.5 pt-1">
+                                            {k.foto.map((f, i) => (
+                                                <img
+                                                    key={f}
+                                                    src={convertFileSrc(k.foto_thumbnail?.[i] ?? f)}
+                                                    alt={k.deskripsi}
+                                                    className="w-12 h-12 object-cover rounded border border-slate-700"
+                                                />
+                                            ))}
+                                        </div>
+                                    )}
+                                </div>
+                                {bisaUbah && (
+                                    <button
+                                        type="button"
+                                        onClick={() => handleHapus(k)}
+                                        className="p-1.5 text-slate-500 hover:text-red-400 transition shrink-0"
+                                        title="Hapus"
+                                    >
+                                        <Trash2 size={14} />
+                                    </button>
+                                )}
+                            </div>
+                        ))}
+                    </div>
+                    <div className="flex justify-between text-sm pt-3 mt-3 border-t border-slate-700">
+                        <span className="text-slate-400">Total dibebankan</span>
+                        <span className="text-red-400 font-semibold">{formatCurrency(totalDibebankan)}</span>
+                    </div>
+                </>
+            )}
+        </div>
+    );
+}
@@ -0,0 +1,190 @@
+import { useEffect, useState } from "react";
+import { convertFileSrc } from "@tauri-apps/api/core";
+import { FileText, ImagePlus, Plus, Trash2, X } from "lucide-react";
+import { DokumenMotorService } from "@/services/dokumen_motor.service";
+import { LampiranService } from "@/services/lampiran.service";
+import { DokumenMotor, JENIS_DOKUMEN_MOTOR, JenisDokumenMotor } from "@/types/dokumen_motor.type";
+import { FormGroup, Input, Label, Select } from "@/components/ui/Form";
+
+interface DokumenMotorPanelProps {
+    motorId: number;
+}
+
+// Dokumen motor (STNK, pajak, dst.) beserta masa berlakunya
+export default function DokumenMotorPanel({ motorId }: DokumenMotorPanelProps) {
+    const formKosong = (): DokumenMotor => ({
+        dokumen_id: 0,
+        motor_id: motorId,
+        jenis: "STNK",
+        nomor: "",
+        tanggal_kadaluarsa: "",
+        foto: null,
+    });
+
+    const [dokumen, setDokumen] = useState<DokumenMotor[]>([]);
+    const [showForm, setShowForm] = useState(false);
+    const [form, setForm] = useState<DokumenMotor>(formKosong());
+    const [saving, setSaving] = useState(false);
+    const [fotoBesar, setFotoBesar] = useState<string | null>(null);
+
+    const fetchDokumen = async () => {
+        try {
+            setDokumen(await DokumenMotorService.getAll(motorId));
+        } catch (err) {
+            console.error("Gagal ambil dokumen motor", err);
+        }
+    };
+
+    useEffect(() => {
+        fetchDokumen();
+    }, [motorId]);
+
+    const handlePilihFoto = async () => {
+        try {
+            const path = await LampiranService.impor();
+            if (path) setForm((f) => ({ ...f, foto: path }));
+        } catch (err) {
+            alert("Gagal mengunggah foto: " + err);
+        }
+    };
+
+    const handleSimpan = async () => {
+        if (!form.nomor.trim() || !form.tanggal_kadaluarsa) {
+            alert("Nomor dan tanggal kadaluarsa wajib diisi");
+            return;
+        }
+        setSaving(true);
+        try {
+            await DokumenMotorService.create(form);
+            setForm(formKosong());
+            setShowForm(false);
+            fetchDokumen();
+        } catch (err) {
+            alert("Gagal menyimpan dokumen: " + err);
+        } finally {
+            setSaving(false);
+        }
+    };
+
+    const handleHapus = async (d: DokumenMotor) => {
+        if (!confirm(`Hapus dokumen ${d.jenis} ${d.nomor}?`)) return;
+        try {
+            await DokumenMotorService.delete(d.dokumen_id);
+            fetchDokumen();
+        } catch (err) {
+            alert("Gagal menghapus dokumen: " + err);
+        }
+    };
+
+    const hariIni = new Date().toISOString().slice(0, 10);
+
+    return (
+        <div className="bg-slate-800 rounded-xl border border-slate-700 p-5">
+            <div className="flex items-center justify-between mb-3">
+                <h3 className="text-slate-200 font-semibold flex items-center gap-2">
+                    <FileText size={16} className="text-purple-400" />
+                    Dokumen Motor
+                </h3>
+                <button
+                    type="button"
+                    onClick={() => setShowForm(!showForm)}
+                    className="flex items-center gap-1 px-2.5 py-1.5 bg-purple-600/20 hover:bg-purple-600/30 text-purple-400 border border-purple-700/50 rounded-lg text-xs transition"
+                >
+                    {showForm ? <X size={14} /> : <Plus size={14} />}
+                    <span>{showForm ? "Batal" : "Tambah"}</span>
+                </button>
+            </div>
+
+            {showForm && (
+                <div className="space-y-3 mb-4 p-3 bg-slate-900/50 border border-slate-700 rounded-lg">
+                    <FormGroup>
+                        <Label required>Jenis</Label>
+                        <Select value={form.jenis} onChange={(e) => setForm({ ...form, jenis: e.target.value as JenisDokumenMotor })}>
+                            {JENIS_DOKUMEN_MOTOR.map((j) => (
+                                <option key={j} value={j}>{j}</option>
+                            ))}
+                        </Select>
+                    </FormGroup>
+                    <FormGroup>
+                        <Label required>Nomor</Label>
+                        <Input value={form.nomor} onChange={(e) => setForm({ ...form, nomor: e.target.value })} />
+                    </FormGroup>
+                    <FormGroup>
+                        <Label required>Berlaku Sampai</Label>
+                        <Input type="date" value={form.tanggal_kadaluarsa} onChange={(e) => setForm({ ...form, tanggal_kadaluarsa: e.target.value })} />
+                    </FormGroup>
+                    <FormGroup>
+                        <Label>Foto / Scan</Label>
+                        <div className="flex items-center gap-3">
+                            {form.foto && (
+                                <img src={convertFileSrc(form.foto)} alt="Foto dokumen" className="w-12 h-12 rounded object-cover border border-slate-700" />
+                            )}
+                            <button
+                                type="button"
+                                onClick={handlePilihFoto}
+                                className="flex items-center gap-1 px-2.5 py-1.5 bg-slate-700 hover:bg-slate-600 text-slate-200 rounded-lg text-xs transition"
+                            >
+                                <ImagePlus size={14} />
+                                <span>{form.foto ? "Ganti File" : "Pilih File"}</span>
+                            </button>
+                        </div>
+                    </FormGroup>
+                    <button
+                        type="button"
+                        onClick={handleSimpan}
+                        disabled={saving}
+                        className="w-full px-3 py-2 bg-purple-600 hover:bg-purple-700 text-white rounded-lg text-sm transition disabled:opacity-50"
+                    >
+                        {saving ? "Menyimpan..." : "Simpan Dokumen"}
+                    </button>
+                </div>
+            )}
+
+            {dokumen.length === 0 ? (
+                <p className="text-sm text-slate-500">Belum ada dokumen</p>
+            ) : (
+                <div className="space-y-2">
+                    {dokumen.map((d) => {
+                        const kadaluarsa = d.tanggal_kadaluarsa < hariIni;
+                        return (
+                            <div key={d.dokumen_id} className="flex items-start justify-between gap-2 p-3 bg-slate-900/40 border border-slate-700/50 rounded-lg">
+                                {d.foto && (
+                                    <img
+                                        src={convertFileSrc(d.foto_thumbnail ?? d.foto)}
+                                        alt={d.jenis}
+                                        className="w-10 h-10 rounded object-cover border border-slate-700 cursor-pointer shrink-0"
+                                        loading="lazy"
+                                        onClick={() => setFotoBesar(convertFileSrc(d.foto!))}
+                                    />
+                                )}
+                                <div className="min-w-0 flex-1 text-sm">
+                                    <p className="text-slate-200 font-medium">{d.jenis} · {d.nomor}</p>
+                                    <p className={`text-xs ${kadaluarsa ? "text-red-400" : "text-slate-400"}`}>
+                                        {kadaluarsa ? "Kadaluarsa sejak" : "Berlaku sampai"} {d.tanggal_kadaluarsa}
+                                    </p>
+                                </div>
+                                <button
+                                    type="button"
+                                    onClick={() => handleHapus(d)}
+                                    className="p-1.5 text-slate-500 hover:text-red-400 transition shrink-0"
+                                    title="Hapus"
+                                >
+                                    <Trash2 size={14} />
+                                </button>
+                            </div>
+                        );
+                    })}
+                </div>
+            )}
+
+            {fotoBesar && (
+                <div
+                    className="fixed inset-0 z-50 flex items-center justify-center bg-black/80 p-4 backdrop-blur-sm"
+                    onClick={() => setFotoBesar(null)}
+                >
+                    <img src={fotoBesar} alt="Dokumen" className="max-w-full max-h-[85vh] rounded-lg shadow-2xl object-contain" />
+                </div>
+            )}
+        </div>
+    );
+}
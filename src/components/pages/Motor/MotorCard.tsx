@@ -1,14 +1,15 @@
-import { Pencil, Trash2, Bike } from "lucide-react"
+import { Pencil, Trash2, Bike, Eye } from "lucide-react"
 import { convertFileSrc } from "@tauri-apps/api/core"
 import { Motor } from "@/types/motor.type"
 
 interface MotorCardProps {
   motor: Motor
   onEdit?: (motor: Motor) => void
+  onDetail?: (motor: Motor) => void
   onDelete?: () => void
 }
 
-export default function MotorCard({ motor, onEdit, onDelete }: MotorCardProps) {
+export default function MotorCard({ motor, onEdit, onDetail, onDelete }: MotorCardProps) {
   const isTersedia = motor.status === "tersedia";
 
   return (
@@ -18,7 +19,7 @@ export default function MotorCard({ motor, onEdit, onDelete }: MotorCardProps) {
       <div className="relative h-48 overflow-hidden bg-slate-900">
         {motor.foto ? (
           <img
-            src={convertFileSrc(motor.foto)}
+            src={convertFileSrc(motor.foto_thumbnail ?? motor.foto)}
             alt={motor.nama}
             className="w-full h-full object-cover group-hover:scale-110 transition-transform duration-500"
           />
@@ -40,6 +41,13 @@ export default function MotorCard({ motor, onEdit, onDelete }: MotorCardProps) {
 
         {/* Action Overlay */}
         <div className="absolute inset-x-0 bottom-0 p-4 bg-gradient-to-t from-slate-900 to-transparent translate-y-full group-hover:translate-y-0 transition-transform duration-300 flex justify-end gap-2">
+          <button
+            onClick={(e) => { e.stopPropagation(); onDetail?.(motor); }}
+            className="p-2 bg-slate-600 hover:bg-slate-500 text-white rounded-lg shadow-lg transition"
+            title="Detail"
+          >
+            <Eye size={16} />
+          </button>
           <button
             onClick={(e) => { e.stopPropagation(); onEdit?.(motor); }}
             className="p-2 bg-blue-600 hover:bg-blue-500 text-white rounded-lg shadow-lg transition"
@@ -0,0 +1,105 @@
+import { useEffect, useState } from "react";
+import { Link, useParams } from "react-router-dom";
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
+import { ArrowLeft, Bike, Pencil } from "lucide-react";
+import { Motor } from "@/types/motor.type";
+import ServisMotorPanel from "./ServisMotorPanel";
+import DokumenMotorPanel from "./DokumenMotorPanel";
+import KerusakanPanel from "../Kerusakan/KerusakanPanel";
+
+// Detail motor beserta riwayat servis dan kerusakannya
+export default function MotorDetail() {
+    const { id } = useParams<{ id: string }>();
+    const motorId = parseInt(id!);
+    const [motor, setMotor] = useState<Motor | null>(null);
+    const [fetching, setFetching] = useState(true);
+
+    useEffect(() => {
+        const fetchMotor = async () => {
+            try {
+                setMotor(await invoke<Motor>("get_motor_by_id", { id: motorId }));
+            } catch (error) {
+                console.error("Failed to fetch motor:", error);
+            } finally {
+                setFetching(false);
+            }
+        };
+        fetchMotor();
+    }, [motorId]);
+
+    if (fetching) {
+        return (
+            <div className="flex items-center justify-center p-12">
+                <div className="animate-spin rounded-full h-8 w-8 border-b-2 border-blue-500"></div>
+                <p className="ml-3 text-slate-400">Memuat data motor...</p>
+            </div>
+        );
+    }
+
+    if (!motor) return <div className="p-8 text-center text-rose-500 font-bold">Data motor tidak ditemukan</div>;
+
+    return (
+        <div className="space-y-6">
+            {/* Header */}
+            <div className="flex items-center justify-between gap-4">
+                <div className="flex items-center gap-4">
+                    <Link
+                        to="/motor"
+                        className="p-2 hover:bg-slate-700 rounded-lg transition text-slate-400 hover:text-slate-100"
+                    >
+                        <ArrowLeft size={20} />
+                    </Link>
+                    <div>
+                        <h1 className="text-2xl font-bold text-slate-100">{motor.nama}</h1>
+                        <p className="text-slate-500 text-sm font-mono uppercase">{motor.plat}</p>
+                    </div>
+                </div>
+                <Link
+                    to={`/motor/edit/${motor.motor_id}`}
+                    className="flex items-center gap-1.5 px-3 py-2 bg-blue-600/20 hover:bg-blue-600/30 text-blue-400 border border-blue-700/50 rounded-lg text-sm transition"
+                >
+                    <Pencil size={16} />
+                    <span>Edit</span>
+                </Link>
+            </div>
+
+            <div className="max-w-5xl mx-auto grid grid-cols-1 lg:grid-cols-3 gap-6">
+                {/* Info Motor */}
+                <div className="lg:col-span-1 space-y-4">
+                    <div className="bg-slate-800 rounded-xl border border-slate-700 overflow-hidden">
+                        <div className="h-48 bg-slate-900">
+                            {motor.foto ? (
+                                <img src={convertFileSrc(motor.foto_thumbnail ?? motor.foto)} alt={motor.nama} className="w-full h-full object-cover" />
+                            ) : (
+                                <div className="w-full h-full flex items-center justify-center text-slate-700">
+                                    <Bike size={64} />
+                                </div>
+                            )}
+                        </div>
+                        <div className="p-5 space-y-2 text-sm">
+                            {[
+                                ["Tipe", motor.tipe_motor],
+                                ["Tahun", motor.tahun],
+                                ["Harga Harian", `Rp ${Number(motor.harga_harian).toLocaleString("id-ID")}`],
+                                ["Odometer", motor.odometer != null ? `${motor.odometer.toLocaleString("id-ID")} km` : "-"],
+                                ["Status", motor.status],
+                            ].map(([label, value]) => (
+                                <div key={label} className="flex justify-between">
+                                    <span className="text-slate-400">{label}</span>
+                                    <span className="text-slate-200 capitalize">{value}</span>
+                                </div>
+                            ))}
+                        </div>
+                    </div>
+                    <DokumenMotorPanel motorId={motorId} />
+                    <ServisMotorPanel motorId={motorId} />
+                </div>
+
+                {/* Riwayat Kerusakan */}
+                <div className="lg:col-span-2">
+                    <KerusakanPanel motorId={motorId} />
+                </div>
+            </div>
+        </div>
+    );
+}
@@ -3,6 +3,7 @@ import { useNavigate, useParams, Link } from "react-router-dom";
 import { invoke } from "@tauri-apps/api/core";
 import { ArrowLeft, Bike, Info, Camera } from "lucide-react";
 import MotorEntryForm from "./MotorEntryForm";
+import ServisMotorPanel from "./ServisMotorPanel";
 import { MotorFormData, Motor } from "@/types/motor.type";
 import { updateMotor } from "@/services/motor.service";
 
@@ -110,10 +111,16 @@ export default function MotorEdit() {
                             </div>
                             <div className="flex items-center gap-2">
                                 <span className="w-2 h-2 rounded-full bg-red-400"></span>
-                                <span className="text-slate-400"><span className="text-slate-300">Perbaikan</span> — dalam perawatan</span>
+                                <span className="text-slate-400"><span className="text-slate-300">Perbaikan</span> — dalam perawatan, tidak bisa disewa</span>
+                            </div>
+                            <div className="flex items-center gap-2">
+                                <span className="w-2 h-2 rounded-full bg-blue-400"></span>
+                                <span className="text-slate-400"><span className="text-slate-300">Servis</span> — sedang diservis, tidak bisa disewa</span>
                             </div>
                         </div>
                     </div>
+
+                    <ServisMotorPanel motorId={parseInt(id!)} />
                 </div>
             </div>
         </div>
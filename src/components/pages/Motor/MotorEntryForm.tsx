@@ -1,6 +1,6 @@
 import React, { useEffect, useState } from 'react';
 import { useForm } from 'react-hook-form';
-import { invoke } from '@tauri-apps/api/core';
+import { LampiranService } from '@/services/lampiran.service';
 import { MotorFormData } from '@/types/motor.type';
 import { FormCard, FormGroup, Label, Input } from '@/components/ui/Form';
 import Button from '@/components/ui/Button';
@@ -44,24 +44,14 @@ const MotorEntryForm: React.FC<MotorFormProps> = ({ initialData, onSubmit, isLoa
     const file = e.target.files?.[0];
     if (!file) return;
 
+    setPreview(URL.createObjectURL(file));
     setUploadingImage(true);
     try {
-      const reader = new FileReader();
-      reader.onload = async () => {
-        const base64 = reader.result as string;
-        setPreview(base64);
-
-        try {
-          const path = await invoke<string>("save_motor_image", { base64 });
-          setValue('foto', path);
-        } catch (err) {
-          console.error("Failed to save image:", err);
-          alert("Gagal mengunggah gambar");
-        }
-      };
-      reader.readAsDataURL(file);
+      const path = await LampiranService.unggah(file);
+      setValue('foto', path);
     } catch (err) {
-      console.error("File reading error:", err);
+      console.error("Failed to save image:", err);
+      alert("Gagal mengunggah gambar: " + err);
     } finally {
       setUploadingImage(false);
     }
@@ -134,6 +124,7 @@ const MotorEntryForm: React.FC<MotorFormProps> = ({ initialData, onSubmit, isLoa
               <option value="tersedia">Tersedia</option>
               <option value="disewa">Disewa</option>
               <option value="perbaikan">Perbaikan</option>
+              <option value="servis">Servis</option>
             </select>
             {errors.status && <p className="text-red-500 text-xs mt-1">{errors.status.message}</p>}
           </FormGroup>
@@ -3,7 +3,7 @@ import { useForm } from 'react-hook-form';
 import { FormCard, FormGroup, Label, Input } from '@/components/ui/Form';
 import Button from '@/components/ui/Button';
 import { Save, Upload } from 'lucide-react';
-import { invoke } from '@tauri-apps/api/core';
+import { LampiranService } from '@/services/lampiran.service';
 import { MotorFormData } from '@/types/motor.type';
 
 interface MotorFormProps {
@@ -44,24 +44,14 @@ const MotorForm: React.FC<MotorFormProps> = ({ initialData, onSubmit, isLoading
     const file = e.target.files?.[0];
     if (!file) return;
 
+    setPreview(URL.createObjectURL(file));
     setUploadingImage(true);
     try {
-      const reader = new FileReader();
-      reader.onload = async () => {
-        const base64 = reader.result as string;
-        setPreview(base64);
-
-        try {
-          const path = await invoke<string>("save_motor_image", { base64 });
-          setValue('foto', path);
-        } catch (err) {
-          console.error("Failed to save image:", err);
-          alert("Gagal mengunggah gambar");
-        }
-      };
-      reader.readAsDataURL(file);
+      const path = await LampiranService.unggah(file);
+      setValue('foto', path);
     } catch (err) {
-      console.error("File reading error:", err);
+      console.error("Failed to save image:", err);
+      alert("Gagal mengunggah gambar: " + err);
     } finally {
       setUploadingImage(false);
     }
@@ -134,6 +124,7 @@ const MotorForm: React.FC<MotorFormProps> = ({ initialData, onSubmit, isLoading
               <option value="tersedia">Tersedia</option>
               <option value="disewa">Disewa</option>
               <option value="perbaikan">Perbaikan</option>
+              <option value="servis">Servis</option>
             </select>
             {errors.status && <p className="text-red-500 text-xs mt-1">{errors.status.message}</p>}
           </FormGroup>
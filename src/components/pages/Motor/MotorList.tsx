@@ -3,8 +3,10 @@ import { useNavigate } from "react-router-dom"
 import MotorCard from "./MotorCard"
 import { getMotor, deleteMotor } from "@/services/motor.service"
 import { Motor } from "@/types/motor.type"
+import { ServisMotorService } from "@/services/servis_motor.service"
+import { MotorPerluServis } from "@/types/servis_motor.type"
 import Button from "@/components/ui/Button"
-import { Plus, FileSpreadsheet, FileText, CheckCircle, FolderOpen, X } from "lucide-react"
+import { Plus, FileSpreadsheet, FileText, CheckCircle, FolderOpen, X, Wrench } from "lucide-react"
 import { invoke } from "@tauri-apps/api/core"
 import * as XLSX from "xlsx"
 import jsPDF from "jspdf"
@@ -14,6 +16,7 @@ export default function MotorList() {
   const navigate = useNavigate()
   const [motors, setMotors] = useState<Motor[]>([])
   const [loading, setLoading] = useState(true)
+  const [perluServis, setPerluServis] = useState<MotorPerluServis[]>([])
   const [toast, setToast] = useState<{ show: boolean; message: string; filePath: string; folderPath: string }>({
     show: false,
     message: "",
@@ -35,8 +38,9 @@ export default function MotorList() {
   const fetchMotor = async () => {
     try {
       setLoading(true)
-      const data = await getMotor()
+      const [data, servis] = await Promise.all([getMotor(), ServisMotorService.getPerluServis()])
       setMotors(data)
+      setPerluServis(servis)
     } catch (err) {
       console.error("Gagal ambil data motor", err)
     } finally {
@@ -48,6 +52,10 @@ export default function MotorList() {
     navigate(`/motor/edit/${motor.motor_id}`)
   }
 
+  const handleDetail = (motor: Motor) => {
+    navigate(`/motor/detail/${motor.motor_id}`)
+  }
+
   const handleDelete = async (motor: Motor) => {
     if (confirm(`Apakah Anda yakin ingin menghapus motor ${motor.nama}?`)) {
       try {
@@ -186,6 +194,28 @@ export default function MotorList() {
         </div>
       </div>
 
+      {/* Motor yang mendekati / melewati jadwal servis */}
+      {perluServis.length > 0 && (
+        <div className="bg-amber-500/10 border border-amber-700/50 rounded-xl p-4">
+          <h3 className="flex items-center gap-2 text-sm font-semibold text-amber-300 mb-2">
+            <Wrench size={16} />
+            {perluServis.length} motor perlu servis
+          </h3>
+          <div className="space-y-1">
+            {perluServis.map((m) => (
+              <button
+                key={m.motor_id}
+                onClick={() => navigate(`/motor/detail/${m.motor_id}`)}
+                className="block w-full text-left text-sm text-slate-300 hover:text-amber-200 transition"
+              >
+                <span className="font-medium">{m.nama} ({m.plat})</span>
+                <span className="text-slate-400"> — {m.alasan}</span>
+              </button>
+            ))}
+          </div>
+        </div>
+      )}
+
       {/* List */}
       <div className="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-6">
         {motors.length === 0 ? (
@@ -198,6 +228,7 @@ export default function MotorList() {
               key={motor.motor_id}
               motor={motor}
               onEdit={handleEdit}
+              onDetail={handleDetail}
               onDelete={() => handleDelete(motor)}
             />
           ))
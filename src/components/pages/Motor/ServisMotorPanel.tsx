@@ -0,0 +1,193 @@
+import { useEffect, useState } from "react";
+import { Plus, Trash2, Wrench, X } from "lucide-react";
+import { ServisMotorService } from "@/services/servis_motor.service";
+import { ServisMotor } from "@/types/servis_motor.type";
+import { FormGroup, Input, Label } from "@/components/ui/Form";
+
+interface ServisMotorPanelProps {
+    motorId: number;
+}
+
+const formKosong = (motorId: number): ServisMotor => ({
+    servis_id: 0,
+    motor_id: motorId,
+    tanggal: new Date().toISOString().slice(0, 10),
+    jenis: "",
+    odometer: null,
+    biaya: 0,
+    suku_cadang: "",
+    keterangan: "",
+    servis_berikut_tanggal: "",
+    servis_berikut_km: null,
+    catat_pengeluaran: true,
+});
+
+const formatCurrency = (amount: number) =>
+    new Intl.NumberFormat("id-ID", { style: "currency", currency: "IDR", minimumFractionDigits: 0 }).format(amount);
+
+// Riwayat servis satu motor beserta form pencatatan servis baru
+export default function ServisMotorPanel({ motorId }: ServisMotorPanelProps) {
+    const [riwayat, setRiwayat] = useState<ServisMotor[]>([]);
+    const [showForm, setShowForm] = useState(false);
+    const [form, setForm] = useState<ServisMotor>(formKosong(motorId));
+    const [saving, setSaving] = useState(false);
+
+    const fetchRiwayat = async () => {
+        try {
+            setRiwayat(await ServisMotorService.getAll(motorId));
+        } catch (err) {
+            console.error("Gagal ambil riwayat servis", err);
+        }
+    };
+
+    useEffect(() => {
+        fetchRiwayat();
+    }, [motorId]);
+
+    const angkaAtauNull = (v: string) => (v === "" ? null : Number(v));
+
+    const handleSimpan = async () => {
+        if (!form.jenis.trim()) {
+            alert("Jenis servis wajib diisi");
+            return;
+        }
+        if (form.biaya < 0) {
+            alert("Biaya servis tidak boleh negatif");
+            return;
+        }
+        setSaving(true);
+        try {
+            await ServisMotorService.create({
+                ...form,
+                servis_berikut_tanggal: form.servis_berikut_tanggal || null,
+                suku_cadang: form.suku_cadang || null,
+                keterangan: form.keterangan || null,
+            });
+            setForm(formKosong(motorId));
+            setShowForm(false);
+            fetchRiwayat();
+        } catch (err) {
+            console.error("Gagal simpan servis", err);
+            alert("Gagal menyimpan servis: " + err);
+        } finally {
+            setSaving(false);
+        }
+    };
+
+    const handleHapus = async (s: ServisMotor) => {
+        if (!confirm(`Hapus catatan servis ${s.jenis} tanggal ${s.tanggal}?`)) return;
+        try {
+            await ServisMotorService.delete(s.servis_id);
+            fetchRiwayat();
+        } catch (err) {
+            console.error("Gagal hapus servis", err);
+            alert("Gagal menghapus servis: " + err);
+        }
+    };
+
+    return (
+        <div className="bg-slate-800 rounded-xl border border-slate-700 p-5">
+            <div className="flex items-center justify-between mb-3">
+                <h3 className="text-slate-200 font-semibold flex items-center gap-2">
+                    <Wrench size={16} className="text-blue-400" />
+                    Riwayat Servis
+                </h3>
+                <button
+                    type="button"
+                    onClick={() => setShowForm(!showForm)}
+                    className="flex items-center gap-1 px-2.5 py-1.5 bg-blue-600/20 hover:bg-blue-600/30 text-blue-400 border border-blue-700/50 rounded-lg text-xs transition"
+                >
+                    {showForm ? <X size={14} /> : <Plus size={14} />}
+                    <span>{showForm ? "Batal" : "Catat Servis"}</span>
+                </button>
+            </div>
+
+            {showForm && (
+                <div className="space-y-3 mb-4 p-3 bg-slate-900/50 border border-slate-700 rounded-lg">
+                    <div className="grid grid-cols-2 gap-3">
+                        <FormGroup>
+                            <Label required>Tanggal</Label>
+                            <Input type="date" value={form.tanggal} onChange={(e) => setForm({ ...form, tanggal: e.target.value })} />
+                        </FormGroup>
+                        <FormGroup>
+                            <Label required>Jenis</Label>
+                            <Input placeholder="Ganti oli" value={form.jenis} onChange={(e) => setForm({ ...form, jenis: e.target.value })} />
+                        </FormGroup>
+                        <FormGroup>
+                            <Label>Odometer (km)</Label>
+                            <Input type="number" min={0} value={form.odometer ?? ""} onChange={(e) => setForm({ ...form, odometer: angkaAtauNull(e.target.value) })} />
+                        </FormGroup>
+                        <FormGroup>
+                            <Label>Biaya</Label>
+                            <Input type="number" min={0} value={form.biaya} onChange={(e) => setForm({ ...form, biaya: Number(e.target.value) || 0 })} />
+                        </FormGroup>
+                        <FormGroup>
+                            <Label>Servis Berikut (tanggal)</Label>
+                            <Input type="date" value={form.servis_berikut_tanggal ?? ""} onChange={(e) => setForm({ ...form, servis_berikut_tanggal: e.target.value })} />
+                        </FormGroup>
+                        <FormGroup>
+                            <Label>Servis Berikut (km)</Label>
+                            <Input type="number" min={0} value={form.servis_berikut_km ?? ""} onChange={(e) => setForm({ ...form, servis_berikut_km: angkaAtauNull(e.target.value) })} />
+                        </FormGroup>
+                    </div>
+                    <FormGroup>
+                        <Label>Suku Cadang</Label>
+                        <Input value={form.suku_cadang ?? ""} onChange={(e) => setForm({ ...form, suku_cadang: e.target.value })} />
+                    </FormGroup>
+                    <FormGroup>
+                        <Label>Keterangan</Label>
+                        <Input value={form.keterangan ?? ""} onChange={(e) => setForm({ ...form, keterangan: e.target.value })} />
+                    </FormGroup>
+                    <label className="flex items-center gap-2 text-sm text-slate-400">
+                        <input
+                            type="checkbox"
+                            checked={form.catat_pengeluaran ?? false}
+                            onChange={(e) => setForm({ ...form, catat_pengeluaran: e.target.checked })}
+                        />
+                        Catat biaya sebagai pengeluaran rental
+                    </label>
+                    <button
+                        type="button"
+                        onClick={handleSimpan}
+                        disabled={saving}
+                        className="w-full px-3 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-lg text-sm transition disabled:opacity-50"
+                    >
+                        {saving ? "Menyimpan..." : "Simpan Servis"}
+                    </button>
+                </div>
+            )}
+
+            {riwayat.length === 0 ? (
+                <p className="text-sm text-slate-500">Belum ada catatan servis</p>
+            ) : (
+                <div className="space-y-2 max-h-80 overflow-y-auto">
+                    {riwayat.map((s) => (
+                        <div key={s.servis_id} className="flex items-start justify-between gap-2 p-3 bg-slate-900/40 border border-slate-700/50 rounded-lg">
+                            <div className="min-w-0 text-sm">
+                                <p className="text-slate-200 font-medium">{s.jenis}</p>
+                                <p className="text-xs text-slate-400">
+                                    {s.tanggal}
+                                    {s.odometer != null && ` · ${s.odometer} km`}
+                                    {` · ${formatCurrency(s.biaya)}`}
+                                </p>
+                                {(s.servis_berikut_tanggal || s.servis_berikut_km != null) && (
+                                    <p className="text-xs text-amber-400 mt-0.5">
+                                        Berikutnya: {[s.servis_berikut_tanggal, s.servis_berikut_km != null ? `${s.servis_berikut_km} km` : null].filter(Boolean).join(" / ")}
+                                    </p>
+                                )}
+                            </div>
+                            <button
+                                type="button"
+                                onClick={() => handleHapus(s)}
+                                className="p-1.5 text-slate-500 hover:text-red-400 transition shrink-0"
+                                title="Hapus"
+                            >
+                                <Trash2 size={14} />
+                            </button>
+                        </div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+}
@@ -2,6 +2,12 @@ import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { save, open } from "@tauri-apps/plugin-dialog";
 import { relaunch } from "@tauri-apps/plugin-process";
+import { BackupService } from "../../../services/backup.service";
+import { KeamananService } from "../../../services/keamanan.service";
+import { StatusEnkripsiDatabase } from "../../../types/keamanan.type";
+import { BackupHistory, InfoRestore } from "../../../types/backup.type";
+import PengaturanPenomoran from "./PengaturanPenomoran";
+import PengaturanWhatsapp from "./PengaturanWhatsapp";
 import {
     Settings,
     Save,
@@ -16,6 +22,10 @@ import {
     Tag,
     Percent,
     Calendar,
+    FolderOpen,
+    History,
+    RotateCcw,
+    Lock,
 } from "lucide-react";
 
 export default function Pengaturan() {
@@ -37,9 +47,39 @@ export default function Pengaturan() {
     const [backingUp, setBackingUp] = useState(false);
     const [importing, setImporting] = useState(false);
     const [showImportConfirm, setShowImportConfirm] = useState(false);
+    const [backupFolder, setBackupFolder] = useState<string>("");
+    const [backupHarian, setBackupHarian] = useState(false);
+    const [backupSaatKeluar, setBackupSaatKeluar] = useState(false);
+    const [simpanHarian, setSimpanHarian] = useState<string>("7");
+    const [simpanMingguan, setSimpanMingguan] = useState<string>("4");
+    const [simpanBulanan, setSimpanBulanan] = useState<string>("12");
+    const [enkripsiOtomatis, setEnkripsiOtomatis] = useState(false);
+    const [adaSandiOtomatis, setAdaSandiOtomatis] = useState(false);
+    const [sandiOtomatis, setSandiOtomatis] = useState("");
+    const [konfirmasiSandiOtomatis, setKonfirmasiSandiOtomatis] = useState("");
+    const [riwayatBackup, setRiwayatBackup] = useState<BackupHistory[]>([]);
+    const [backupOtomatisBerjalan, setBackupOtomatisBerjalan] = useState(false);
+    const [infoRestore, setInfoRestore] = useState<InfoRestore | null>(null);
+    const [sandiBackup, setSandiBackup] = useState("");
+    const [konfirmasiSandiBackup, setKonfirmasiSandiBackup] = useState("");
+    // File import terenkripsi yang menunggu kata sandi
+    const [importTerenkripsi, setImportTerenkripsi] = useState<string | null>(null);
+    const [sandiImport, setSandiImport] = useState("");
+    const [statusEnkripsi, setStatusEnkripsi] = useState<StatusEnkripsiDatabase | null>(null);
+    const [sandiDatabase, setSandiDatabase] = useState("");
+    const [konfirmasiSandiDatabase, setKonfirmasiSandiDatabase] = useState("");
+    const [mengenkripsi, setMengenkripsi] = useState(false);
+    const [membatalkanRestore, setMembatalkanRestore] = useState(false);
 
     useEffect(() => {
         fetchSettings();
+        fetchRiwayatBackup();
+        KeamananService.getStatusEnkripsi()
+            .then(setStatusEnkripsi)
+            .catch((err) => console.error("Failed to fetch encryption status:", err));
+        BackupService.getInfoRestoreTerakhir()
+            .then(setInfoRestore)
+            .catch((err) => console.error("Failed to fetch restore info:", err));
     }, []);
 
     useEffect(() => {
@@ -65,6 +105,14 @@ export default function Pengaturan() {
                 invoke<string>("get_pengaturan", { key: "diskon_tanggal_berakhir" }),
                 invoke<string>("get_pengaturan", { key: "diskon_aktif" }),
             ]);
+            const [bFolder, bHarian, bKeluar, sHarian, sMingguan, sBulanan] = await Promise.all([
+                invoke<string>("get_pengaturan", { key: "backup_folder" }),
+                invoke<string>("get_pengaturan", { key: "backup_harian" }),
+                invoke<string>("get_pengaturan", { key: "backup_saat_keluar" }),
+                invoke<string>("get_pengaturan", { key: "backup_simpan_harian" }),
+                invoke<string>("get_pengaturan", { key: "backup_simpan_mingguan" }),
+                invoke<string>("get_pengaturan", { key: "backup_simpan_bulanan" }),
+            ]);
             setDendaPerHari(denda || "0");
             setSaldoAwalKas(kas || "0");
             setSaldoAwalBank(bank || "0");
@@ -73,6 +121,15 @@ export default function Pengaturan() {
             setDiskonMulai(dMulai || "");
             setDiskonBerakhir(dBerakhir || "");
             setDiskonAktif(dAktif === "1");
+            setBackupFolder(bFolder || "");
+            setBackupHarian(bHarian === "1");
+            setBackupSaatKeluar(bKeluar === "1");
+            setSimpanHarian(sHarian || "7");
+            setSimpanMingguan(sMingguan || "4");
+            setSimpanBulanan(sBulanan || "12");
+            const adaSandi = await BackupService.adaSandiOtomatis();
+            setAdaSandiOtomatis(adaSandi);
+            setEnkripsiOtomatis(adaSandi);
         } catch (err) {
             console.error("Failed to fetch settings:", err);
         } finally {
@@ -80,6 +137,179 @@ export default function Pengaturan() {
         }
     };
 
+    const fetchRiwayatBackup = async () => {
+        try {
+            setRiwayatBackup(await BackupService.getHistory(10));
+        } catch (err) {
+            console.error("Failed to fetch backup history:", err);
+        }
+    };
+
+    const pilihFolderBackup = async () => {
+        const folder = await open({
+            title: "Pilih Folder Backup Otomatis",
+            directory: true,
+            multiple: false,
+        });
+        if (folder) {
+            setBackupFolder(folder);
+        }
+    };
+
+    const saveBackupOtomatis = async () => {
+        if (enkripsiOtomatis && !sandiOtomatis && !adaSandiOtomatis) {
+            setToast({ show: true, message: "Isi kata sandi backup otomatis", type: "error" });
+            return;
+        }
+        if (enkripsiOtomatis && sandiOtomatis !== konfirmasiSandiOtomatis) {
+            setToast({ show: true, message: "Konfirmasi kata sandi backup otomatis tidak sama", type: "error" });
+            return;
+        }
+        try {
+            setSaving("backup");
+            // Kata sandi lama dipertahankan jika kolomnya dibiarkan kosong
+            if (!enkripsiOtomatis && adaSandiOtomatis) {
+                await BackupService.aturSandiOtomatis(null);
+            } else if (enkripsiOtomatis && sandiOtomatis) {
+                await BackupService.aturSandiOtomatis(sandiOtomatis);
+            }
+            setAdaSandiOtomatis(enkripsiOtomatis);
+            setSandiOtomatis("");
+            setKonfirmasiSandiOtomatis("");
+            const angka = (val: string) => (parseInt(val.replace(/\D/g, "")) || 0).toString();
+            await Promise.all([
+                invoke("set_pengaturan", {
+                    key: "backup_folder",
+                    value: backupFolder,
+                    keterangan: "Folder Backup Otomatis",
+                }),
+                invoke("set_pengaturan", {
+                    key: "backup_harian",
+                    value: backupHarian ? "1" : "0",
+                    keterangan: "Backup Harian",
+                }),
+                invoke("set_pengaturan", {
+                    key: "backup_saat_keluar",
+                    value: backupSaatKeluar ? "1" : "0",
+                    keterangan: "Backup Saat Aplikasi Ditutup",
+                }),
+                invoke("set_pengaturan", {
+                    key: "backup_simpan_harian",
+                    value: angka(simpanHarian),
+                    keterangan: "Jumlah Backup Harian Disimpan",
+                }),
+                invoke("set_pengaturan", {
+                    key: "backup_simpan_mingguan",
+                    value: angka(simpanMingguan),
+                    keterangan: "Jumlah Backup Mingguan Disimpan",
+                }),
+                invoke("set_pengaturan", {
+                    key: "backup_simpan_bulanan",
+                    value: angka(simpanBulanan),
+                    keterangan: "Jumlah Backup Bulanan Disimpan",
+                }),
+            ]);
+            setToast({
+                show: true,
+                message: "Pengaturan backup otomatis berhasil disimpan",
+                type: "success",
+            });
+        } catch (err) {
+            console.error("Failed to save backup settings:", err);
+            setToast({
+                show: true,
+                message: `Gagal menyimpan pengaturan backup: ${err}`,
+                type: "error",
+            });
+        } finally {
+            setSaving(null);
+        }
+    };
+
+    const handleBackupSekarang = async () => {
+        try {
+            setBackupOtomatisBerjalan(true);
+            const riwayat = await BackupService.jalankanSekarang();
+            setToast({
+                show: true,
+                message: `Backup berhasil disimpan ke: ${riwayat.path}`,
+                type: "success",
+            });
+        } catch (err) {
+            console.error("Backup failed:", err);
+            setToast({
+                show: true,
+                message: `Gagal backup database: ${err}`,
+                type: "error",
+            });
+        } finally {
+            setBackupOtomatisBerjalan(false);
+            fetchRiwayatBackup();
+        }
+    };
+
+    const handleBatalkanRestore = async () => {
+        try {
+            setMembatalkanRestore(true);
+            await BackupService.batalkanRestoreTerakhir();
+            setToast({
+                show: true,
+                message: "Restore dibatalkan! Aplikasi akan restart...",
+                type: "success",
+            });
+            setTimeout(async () => {
+                await relaunch();
+            }, 1500);
+        } catch (err) {
+            console.error("Undo restore failed:", err);
+            setToast({
+                show: true,
+                message: `Gagal membatalkan restore: ${err}`,
+                type: "error",
+            });
+        } finally {
+            setMembatalkanRestore(false);
+        }
+    };
+
+    const handleEnkripsiDatabase = async () => {
+        if (sandiDatabase !== konfirmasiSandiDatabase) {
+            setToast({
+                show: true,
+                message: "Konfirmasi kata sandi database tidak sama",
+                type: "error",
+            });
+            return;
+        }
+        try {
+            setMengenkripsi(true);
+            await KeamananService.aktifkanEnkripsi(sandiDatabase);
+            setStatusEnkripsi(await KeamananService.getStatusEnkripsi());
+            setSandiDatabase("");
+            setKonfirmasiSandiDatabase("");
+            setToast({
+                show: true,
+                message: "Database berhasil dienkripsi",
+                type: "success",
+            });
+        } catch (err) {
+            console.error("Encrypt database failed:", err);
+            setToast({
+                show: true,
+                message: `Gagal mengenkripsi database: ${err}`,
+                type: "error",
+            });
+        } finally {
+            setMengenkripsi(false);
+        }
+    };
+
+    const formatUkuran = (bytes: number) => {
+        if (bytes >= 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+        if (bytes >= 1024) return `${(bytes / 1024).toFixed(0)} KB`;
+        return `${bytes} B`;
+    };
+
     const formatInputCurrency = (val: string) => {
         const num = parseInt(val.replace(/\D/g, "")) || 0;
         return new Intl.NumberFormat("id-ID").format(num);
@@ -158,6 +388,15 @@ export default function Pengaturan() {
     })();
 
     const handleBackup = async () => {
+        if (sandiBackup && sandiBackup !== konfirmasiSandiBackup) {
+            setToast({
+                show: true,
+                message: "Konfirmasi kata sandi backup tidak sama",
+                type: "error",
+            });
+            return;
+        }
+
         try {
             setBackingUp(true);
 
@@ -167,15 +406,18 @@ export default function Pengaturan() {
                 .toISOString()
                 .replace(/[:.]/g, "-")
                 .slice(0, 19);
-            const defaultName = `backup_rental_motor_${timestamp}.sqlite`;
+            // Database terenkripsi selalu menghasilkan backup terenkripsi
+            const terenkripsi = !!sandiBackup || !!statusEnkripsi?.terenkripsi;
+            const ekstensi = terenkripsi ? "enc" : "zip";
+            const defaultName = `backup_rental_motor_${timestamp}.${ekstensi}`;
 
             const destPath = await save({
                 title: "Simpan Backup Database",
                 defaultPath: defaultName,
                 filters: [
                     {
-                        name: "SQLite Database",
-                        extensions: ["sqlite"],
+                        name: terenkripsi ? "Arsip Backup Terenkripsi" : "Arsip Backup",
+                        extensions: [ekstensi],
                     },
                 ],
             });
@@ -187,6 +429,7 @@ export default function Pengaturan() {
 
             const result = await invoke<string>("backup_database", {
                 destPath,
+                kataSandi: sandiBackup || null,
             });
             setToast({
                 show: true,
@@ -202,6 +445,7 @@ export default function Pengaturan() {
             });
         } finally {
             setBackingUp(false);
+            fetchRiwayatBackup();
         }
     };
 
@@ -214,8 +458,8 @@ export default function Pengaturan() {
                 title: "Pilih File Database untuk Import",
                 filters: [
                     {
-                        name: "SQLite Database",
-                        extensions: ["sqlite", "db"],
+                        name: "Backup (Arsip / SQLite)",
+                        extensions: ["zip", "enc", "sqlite", "db"],
                     },
                 ],
                 multiple: false,
@@ -227,19 +471,32 @@ export default function Pengaturan() {
                 return;
             }
 
-            await invoke("import_database", { srcPath: selectedPath });
+            // Backup terenkripsi: minta kata sandi dulu
+            if (await invoke<boolean>("cek_backup_terenkripsi", { path: selectedPath })) {
+                setSandiImport("");
+                setImportTerenkripsi(selectedPath);
+                return;
+            }
 
+            await jalankanImport(selectedPath);
+        } catch (err) {
+            console.error("Import failed:", err);
             setToast({
                 show: true,
-                message:
-                    "Database berhasil diimpor! Aplikasi akan restart...",
-                type: "success",
+                message: `Gagal import database: ${err}`,
+                type: "error",
             });
+        } finally {
+            setImporting(false);
+        }
+    };
 
-            // Restart app after a short delay so user can see the toast
-            setTimeout(async () => {
-                await relaunch();
-            }, 1500);
+    const handleImportTerenkripsi = async () => {
+        if (!importTerenkripsi) return;
+        try {
+            setImporting(true);
+            await jalankanImport(importTerenkripsi, sandiImport);
+            setImportTerenkripsi(null);
         } catch (err) {
             console.error("Import failed:", err);
             setToast({
@@ -252,6 +509,21 @@ export default function Pengaturan() {
         }
     };
 
+    const jalankanImport = async (srcPath: string, kataSandi?: string) => {
+        await invoke("import_database", { srcPath, kataSandi: kataSandi || null });
+
+        setToast({
+            show: true,
+            message: "Database berhasil diimpor! Aplikasi akan restart...",
+            type: "success",
+        });
+
+        // Restart app after a short delay so user can see the toast
+        setTimeout(async () => {
+            await relaunch();
+        }, 1500);
+    };
+
     if (loading) {
         return (
             <div className="flex items-center justify-center p-12">
@@ -597,6 +869,16 @@ export default function Pengaturan() {
                 </div>
             </div>
 
+            {/* Penomoran Dokumen */}
+            <PengaturanPenomoran
+                onToast={(message, type) => setToast({ show: true, message, type })}
+            />
+
+            {/* Template WhatsApp */}
+            <PengaturanWhatsapp
+                onToast={(message, type) => setToast({ show: true, message, type })}
+            />
+
             {/* Backup & Restore */}
             <div className="bg-slate-800 rounded-xl border border-slate-700 overflow-hidden">
                 <div className="p-5 border-b border-slate-700">
@@ -631,6 +913,52 @@ export default function Pengaturan() {
                         </p>
                     </div>
 
+                    {/* Kata sandi backup (opsional) */}
+                    <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+                        <div>
+                            <label className="block text-sm font-medium text-slate-300 mb-2">
+                                <span className="flex items-center gap-1.5">
+                                    <Lock size={14} className="text-cyan-400" />
+                                    Kata Sandi Backup (opsional)
+                                </span>
+                            </label>
+                            <input
+                                type="password"
+                                value={sandiBackup}
+                                onChange={(e) => setSandiBackup(e.target.value)}
+                                placeholder={
+                                    statusEnkripsi?.terenkripsi
+                                        ? "Kosongkan untuk memakai kata sandi database"
+                                        : "Kosongkan jika tidak dienkripsi"
+                                }
+                                className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-cyan-500 focus:border-cyan-500 focus:outline-none transition"
+                            />
+                        </div>
+                        <div>
+                            <label className="block text-sm font-medium text-slate-300 mb-2">
+                                Ulangi Kata Sandi
+                            </label>
+                            <input
+                                type="password"
+                                value={konfirmasiSandiBackup}
+                                onChange={(e) => setKonfirmasiSandiBackup(e.target.value)}
+                                disabled={!sandiBackup}
+                                className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-cyan-500 focus:border-cyan-500 focus:outline-none transition disabled:opacity-50"
+                            />
+                        </div>
+                    </div>
+                    {sandiBackup ? (
+                        <p className="text-xs text-amber-300/80">
+                            Backup terenkripsi tidak bisa dipulihkan tanpa kata sandi ini. Simpan kata sandi di tempat yang aman.
+                        </p>
+                    ) : (
+                        statusEnkripsi?.terenkripsi && (
+                            <p className="text-xs text-amber-300/80">
+                                Database terenkripsi: backup tanpa kata sandi dienkripsi dengan kata sandi database.
+                            </p>
+                        )
+                    )}
+
                     {/* Buttons */}
                     <div className="flex flex-wrap gap-3">
                         {/* Backup Button */}
@@ -664,6 +992,269 @@ export default function Pengaturan() {
                                 ? "Mengimpor..."
                                 : "Import Database"}
                         </button>
+
+                        {/* Undo Restore Button */}
+                        {infoRestore && (
+                            <button
+                                onClick={handleBatalkanRestore}
+                                disabled={membatalkanRestore}
+                                title={`Restore ${infoRestore.waktu} dari ${infoRestore.sumber}`}
+                                className="flex items-center gap-2.5 px-5 py-3 bg-slate-700 hover:bg-slate-600 text-slate-200 rounded-lg text-sm font-medium transition disabled:opacity-50"
+                            >
+                                <RotateCcw size={16} />
+                                {membatalkanRestore
+                                    ? "Membatalkan..."
+                                    : "Batalkan Restore Terakhir"}
+                            </button>
+                        )}
+                    </div>
+                </div>
+            </div>
+
+            {/* Enkripsi Database */}
+            {statusEnkripsi && (
+                <div className="bg-slate-800 rounded-xl border border-slate-700 overflow-hidden">
+                    <div className="p-5 border-b border-slate-700">
+                        <div className="flex items-center gap-3">
+                            <div className="p-2 bg-cyan-500/10 rounded-lg">
+                                <Lock size={20} className="text-cyan-400" />
+                            </div>
+                            <div>
+                                <h2 className="text-lg font-bold text-slate-100">
+                                    Enkripsi Database
+                                </h2>
+                                <p className="text-sm text-slate-400">
+                                    Lindungi data penyewa (KTP, alamat, no. HP) di komputer ini
+                                </p>
+                            </div>
+                        </div>
+                    </div>
+                    <div className="p-5 space-y-5">
+                        {statusEnkripsi.terenkripsi ? (
+                            <div className="flex items-center gap-2 text-sm text-green-400">
+                                <CheckCircle size={16} />
+                                Database terenkripsi. Kata sandi diminta setiap aplikasi dibuka.
+                            </div>
+                        ) : !statusEnkripsi.didukung ? (
+                            <div className="flex items-start gap-3 p-3.5 bg-slate-900/60 rounded-lg border border-slate-700/50">
+                                <Info size={18} className="text-slate-400 mt-0.5 shrink-0" />
+                                <p className="text-xs text-slate-400 leading-relaxed">
+                                    Versi aplikasi ini dibangun tanpa dukungan enkripsi database.
+                                </p>
+                            </div>
+                        ) : (
+                            <>
+                                <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+                                    <div>
+                                        <label className="block text-sm font-medium text-slate-300 mb-2">
+                                            Kata Sandi Database
+                                        </label>
+                                        <input
+                                            type="password"
+                                            value={sandiDatabase}
+                                            onChange={(e) => setSandiDatabase(e.target.value)}
+                                            className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-cyan-500 focus:border-cyan-500 focus:outline-none transition"
+                                        />
+                                    </div>
+                                    <div>
+                                        <label className="block text-sm font-medium text-slate-300 mb-2">
+                                            Ulangi Kata Sandi
+                                        </label>
+                                        <input
+                                            type="password"
+                                            value={konfirmasiSandiDatabase}
+                                            onChange={(e) => setKonfirmasiSandiDatabase(e.target.value)}
+                                            className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-cyan-500 focus:border-cyan-500 focus:outline-none transition"
+                                        />
+                                    </div>
+                                </div>
+                                <p className="text-xs text-amber-300/80">
+                                    Kata sandi tidak bisa dipulihkan. Tanpa kata sandi ini database tidak bisa dibuka sama sekali — buat backup terlebih dahulu.
+                                </p>
+                                <button
+                                    onClick={handleEnkripsiDatabase}
+                                    disabled={mengenkripsi || !sandiDatabase}
+                                    className="flex items-center gap-2 px-5 py-2.5 bg-cyan-600 hover:bg-cyan-500 text-white rounded-lg text-sm font-medium transition disabled:opacity-50 shadow-lg shadow-cyan-500/10"
+                                >
+                                    <Lock size={16} />
+                                    {mengenkripsi ? "Mengenkripsi..." : "Enkripsi Database"}
+                                </button>
+                            </>
+                        )}
+                    </div>
+                </div>
+            )}
+
+            {/* Backup Otomatis */}
+            <div className="bg-slate-800 rounded-xl border border-slate-700 overflow-hidden">
+                <div className="p-5 border-b border-slate-700">
+                    <div className="flex items-center gap-3">
+                        <div className="p-2 bg-cyan-500/10 rounded-lg">
+                            <History size={20} className="text-cyan-400" />
+                        </div>
+                        <div>
+                            <h2 className="text-lg font-bold text-slate-100">
+                                Backup Otomatis
+                            </h2>
+                            <p className="text-sm text-slate-400">
+                                Backup harian atau saat aplikasi ditutup ke folder pilihan (flashdisk / folder sinkron)
+                            </p>
+                        </div>
+                    </div>
+                </div>
+                <div className="p-5 space-y-5">
+                    <div>
+                        <label className="block text-sm font-medium text-slate-300 mb-2">
+                            Folder Backup
+                        </label>
+                        <div className="flex gap-2">
+                            <input
+                                type="text"
+                                value={backupFolder}
+                                onChange={(e) => setBackupFolder(e.target.value)}
+                                placeholder="Belum diatur"
+                                className="flex-1 bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-cyan-500 focus:border-cyan-500 focus:outline-none transition"
+                            />
+                            <button
+                                onClick={pilihFolderBackup}
+                                className="flex items-center gap-2 px-4 py-2.5 bg-slate-700 hover:bg-slate-600 text-slate-200 rounded-lg text-sm font-medium transition"
+                            >
+                                <FolderOpen size={16} />
+                                Pilih
+                            </button>
+                        </div>
+                    </div>
+
+                    <div className="flex flex-wrap gap-6">
+                        <label className="flex items-center gap-2 text-sm text-slate-300 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                checked={backupHarian}
+                                onChange={(e) => setBackupHarian(e.target.checked)}
+                                className="w-4 h-4 accent-cyan-500"
+                            />
+                            Backup setiap hari
+                        </label>
+                        <label className="flex items-center gap-2 text-sm text-slate-300 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                checked={backupSaatKeluar}
+                                onChange={(e) => setBackupSaatKeluar(e.target.checked)}
+                                className="w-4 h-4 accent-cyan-500"
+                            />
+                            Backup saat aplikasi ditutup
+                        </label>
+                        <label className="flex items-center gap-2 text-sm text-slate-300 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                checked={enkripsiOtomatis}
+                                onChange={(e) => setEnkripsiOtomatis(e.target.checked)}
+                                className="w-4 h-4 accent-cyan-500"
+                            />
+                            Enkripsi backup otomatis
+                        </label>
+                    </div>
+
+                    {enkripsiOtomatis && (
+                        <div className="space-y-2">
+                            <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+                                <input
+                                    type="password"
+                                    value={sandiOtomatis}
+                                    onChange={(e) => setSandiOtomatis(e.target.value)}
+                                    placeholder={adaSandiOtomatis ? "Kosongkan jika tidak diganti" : "Kata sandi backup otomatis"}
+                                    className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-cyan-500 focus:border-cyan-500 focus:outline-none transition"
+                                />
+                                <input
+                                    type="password"
+                                    value={konfirmasiSandiOtomatis}
+                                    onChange={(e) => setKonfirmasiSandiOtomatis(e.target.value)}
+                                    disabled={!sandiOtomatis}
+                                    placeholder="Ulangi kata sandi"
+                                    className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-cyan-500 focus:border-cyan-500 focus:outline-none transition disabled:opacity-50"
+                                />
+                            </div>
+                            <p className="text-xs text-amber-300/80">
+                                Backup otomatis (.enc) hanya bisa dipulihkan dengan kata sandi ini. Simpan kata sandi di tempat yang aman.
+                            </p>
+                        </div>
+                    )}
+                    {!enkripsiOtomatis && statusEnkripsi?.terenkripsi && (
+                        <p className="text-xs text-amber-300/80">
+                            Database terenkripsi: backup otomatis dienkripsi dengan kata sandi database.
+                        </p>
+                    )}
+
+                    <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
+                        {[
+                            { label: "Simpan Harian", value: simpanHarian, set: setSimpanHarian, satuan: "hari" },
+                            { label: "Simpan Mingguan", value: simpanMingguan, set: setSimpanMingguan, satuan: "minggu" },
+                            { label: "Simpan Bulanan", value: simpanBulanan, set: setSimpanBulanan, satuan: "bulan" },
+                        ].map((item) => (
+                            <div key={item.label}>
+                                <label className="block text-sm font-medium text-slate-300 mb-2">
+                                    {item.label}
+                                </label>
+                                <div className="relative">
+                                    <input
+                                        type="text"
+                                        value={item.value}
+                                        onChange={(e) => item.set(e.target.value.replace(/\D/g, ""))}
+                                        className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 pr-16 text-sm text-slate-100 focus:ring-2 focus:ring-cyan-500 focus:border-cyan-500 focus:outline-none transition"
+                                    />
+                                    <span className="absolute right-4 top-1/2 -translate-y-1/2 text-xs text-slate-500">
+                                        {item.satuan}
+                                    </span>
+                                </div>
+                            </div>
+                        ))}
+                    </div>
+
+                    <div className="flex flex-wrap gap-3">
+                        <button
+                            onClick={saveBackupOtomatis}
+                            disabled={saving === "backup"}
+                            className="flex items-center gap-2 px-5 py-2.5 bg-cyan-600 hover:bg-cyan-500 text-white rounded-lg text-sm font-medium transition disabled:opacity-50 shadow-lg shadow-cyan-500/10"
+                        >
+                            <Save size={16} />
+                            {saving === "backup" ? "Menyimpan..." : "Simpan Pengaturan"}
+                        </button>
+                        <button
+                            onClick={handleBackupSekarang}
+                            disabled={backupOtomatisBerjalan || !backupFolder}
+                            className="flex items-center gap-2 px-5 py-2.5 bg-slate-700 hover:bg-slate-600 text-slate-200 rounded-lg text-sm font-medium transition disabled:opacity-50"
+                        >
+                            <Download size={16} />
+                            {backupOtomatisBerjalan ? "Membuat Backup..." : "Backup Sekarang"}
+                        </button>
+                    </div>
+
+                    {/* Riwayat */}
+                    <div className="space-y-2">
+                        <h3 className="text-sm font-semibold text-slate-300">Riwayat Backup</h3>
+                        {riwayatBackup.length === 0 ? (
+                            <p className="text-xs text-slate-500">Belum ada backup.</p>
+                        ) : (
+                            <div className="divide-y divide-slate-700/60 border border-slate-700/50 rounded-lg">
+                                {riwayatBackup.map((r) => (
+                                    <div key={r.backup_id} className="flex items-start gap-3 p-3 text-xs">
+                                        {r.status === "sukses" ? (
+                                            <CheckCircle size={16} className="text-green-400 shrink-0" />
+                                        ) : (
+                                            <XCircle size={16} className="text-red-400 shrink-0" />
+                                        )}
+                                        <div className="flex-1 min-w-0">
+                                            <p className="text-slate-200">
+                                                {r.waktu} · <span className="capitalize">{r.pemicu}</span>
+                                                {r.status === "sukses" && ` · ${formatUkuran(r.ukuran)} · ${r.jumlah_file} file`}
+                                            </p>
+                                            <p className="text-slate-500 truncate" title={r.path}>{r.path}</p>
+                                            {r.pesan && <p className="text-red-400">{r.pesan}</p>}
+                                        </div>
+                                    </div>
+                                ))}
+                            </div>
+                        )}
                     </div>
                 </div>
             </div>
@@ -697,8 +1288,9 @@ export default function Pengaturan() {
                                     <strong>Perhatian:</strong> Seluruh data
                                     yang ada saat ini akan{" "}
                                     <strong>digantikan</strong> dengan data dari
-                                    file yang diimpor. Proses ini tidak dapat
-                                    dibatalkan.
+                                    file yang diimpor. Data saat ini disimpan
+                                    otomatis dan bisa dikembalikan lewat
+                                    tombol "Batalkan Restore Terakhir".
                                 </p>
                             </div>
 
@@ -728,6 +1320,56 @@ export default function Pengaturan() {
                 </div>
             )}
 
+            {/* Kata Sandi Import Modal */}
+            {importTerenkripsi && (
+                <div className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50 flex items-center justify-center p-4">
+                    <div
+                        className="bg-slate-800 rounded-2xl border border-slate-700 shadow-2xl max-w-md w-full overflow-hidden"
+                        style={{ animation: "slideUp 0.25s ease-out" }}
+                    >
+                        <div className="p-6 space-y-4">
+                            <div className="flex justify-center">
+                                <div className="p-3 bg-cyan-500/10 rounded-full">
+                                    <Lock size={32} className="text-cyan-400" />
+                                </div>
+                            </div>
+                            <h3 className="text-xl font-bold text-slate-100 text-center">
+                                Backup Terenkripsi
+                            </h3>
+                            <p className="text-xs text-slate-400 text-center break-all">
+                                {importTerenkripsi}
+                            </p>
+                            <input
+                                type="password"
+                                value={sandiImport}
+                                onChange={(e) => setSandiImport(e.target.value)}
+                                onKeyDown={(e) => e.key === "Enter" && sandiImport && handleImportTerenkripsi()}
+                                placeholder="Kata sandi backup"
+                                autoFocus
+                                className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-cyan-500 focus:border-cyan-500 focus:outline-none transition"
+                            />
+                        </div>
+                        <div className="flex gap-3 p-5 pt-0">
+                            <button
+                                onClick={() => setImportTerenkripsi(null)}
+                                disabled={importing}
+                                className="flex-1 flex items-center justify-center gap-2 px-4 py-2.5 bg-slate-700 hover:bg-slate-600 text-slate-200 rounded-lg text-sm font-medium transition disabled:opacity-50"
+                            >
+                                Batal
+                            </button>
+                            <button
+                                onClick={handleImportTerenkripsi}
+                                disabled={importing || !sandiImport}
+                                className="flex-1 flex items-center justify-center gap-2 px-4 py-2.5 bg-amber-600 hover:bg-amber-500 text-white rounded-lg text-sm font-medium transition shadow-lg shadow-amber-500/20 disabled:opacity-50"
+                            >
+                                <Upload size={16} />
+                                {importing ? "Mengimpor..." : "Import"}
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            )}
+
             {/* Toast */}
             {toast.show && (
                 <div
@@ -0,0 +1,145 @@
+import { useEffect, useState } from "react";
+import { Hash, Save } from "lucide-react";
+import { PengaturanService } from "../../../services/pengaturan.service";
+import { JenisNomorDokumen, ResetNomor } from "../../../types/pengaturan.type";
+
+const JENIS: { jenis: JenisNomorDokumen; label: string; bawaan: string }[] = [
+    { jenis: "transaksi", label: "Transaksi / Invoice", bawaan: "INV/{YYYY}/{MM}/{NNNN}" },
+    { jenis: "bukti_pelunasan", label: "Bukti Pelunasan", bawaan: "BYR/{YYYY}/{MM}/{NNNN}" },
+    { jenis: "pengeluaran_rental", label: "Pengeluaran", bawaan: "PGL/{YYYY}/{MM}/{NNNN}" },
+];
+
+type Nomor = { format: string; reset: ResetNomor; pratinjau: string };
+
+type Props = {
+    onToast: (message: string, type: "success" | "error") => void;
+};
+
+export default function PengaturanPenomoran({ onToast }: Props) {
+    const [nomor, setNomor] = useState<Record<string, Nomor>>({});
+    const [saving, setSaving] = useState(false);
+
+    const pratinjau = (jenis: JenisNomorDokumen) =>
+        PengaturanService.previewNomor(jenis).catch((err) => `Error: ${err}`);
+
+    const fetchNomor = async () => {
+        try {
+            const hasil = await Promise.all(
+                JENIS.map(async ({ jenis }) => {
+                    const [format, reset, contoh] = await Promise.all([
+                        PengaturanService.get(`format_nomor_${jenis}`),
+                        PengaturanService.get(`reset_nomor_${jenis}`),
+                        pratinjau(jenis),
+                    ]);
+                    return [
+                        jenis,
+                        { format, reset: (reset || "bulanan") as ResetNomor, pratinjau: contoh },
+                    ] as const;
+                })
+            );
+            setNomor(Object.fromEntries(hasil));
+        } catch (err) {
+            console.error("Failed to fetch numbering settings:", err);
+        }
+    };
+
+    useEffect(() => {
+        fetchNomor();
+    }, []);
+
+    const ubah = (jenis: JenisNomorDokumen, data: Partial<Nomor>) =>
+        setNomor((lama) => ({ ...lama, [jenis]: { ...lama[jenis], ...data } }));
+
+    // Reset disimpan dulu supaya format dicek terhadap aturan reset yang baru
+    const saveNomor = async () => {
+        try {
+            setSaving(true);
+            for (const { jenis, label } of JENIS) {
+                const data = nomor[jenis];
+                if (!data) continue;
+                await PengaturanService.set(`reset_nomor_${jenis}`, data.reset, `Reset Nomor ${label}`);
+                await PengaturanService.set(`format_nomor_${jenis}`, data.format.trim(), `Format Nomor ${label}`);
+            }
+            onToast("Format nomor dokumen berhasil disimpan", "success");
+        } catch (err) {
+            console.error("Failed to save numbering settings:", err);
+            onToast(`Gagal menyimpan format nomor: ${err}`, "error");
+        } finally {
+            await fetchNomor();
+            setSaving(false);
+        }
+    };
+
+    return (
+        <div className="bg-slate-800 rounded-xl border border-slate-700 overflow-hidden">
+            <div className="p-5 border-b border-slate-700">
+                <div className="flex items-center gap-3">
+                    <div className="p-2 bg-sky-500/10 rounded-lg">
+                        <Hash size={20} className="text-sky-400" />
+                    </div>
+                    <div>
+                        <h2 className="text-lg font-bold text-slate-100">
+                            Penomoran Dokumen
+                        </h2>
+                        <p className="text-sm text-slate-400">
+                            Format nomor invoice, bukti pelunasan dan pengeluaran
+                        </p>
+                    </div>
+                </div>
+            </div>
+            <div className="p-5 space-y-5">
+                {JENIS.map(({ jenis, label, bawaan }) => {
+                    const data = nomor[jenis];
+                    return (
+                        <div key={jenis} className="grid grid-cols-1 md:grid-cols-3 gap-4">
+                            <div className="md:col-span-2">
+                                <label className="block text-sm font-medium text-slate-300 mb-2">
+                                    {label}
+                                </label>
+                                <input
+                                    type="text"
+                                    value={data?.format ?? ""}
+                                    onChange={(e) => ubah(jenis, { format: e.target.value })}
+                                    placeholder={bawaan}
+                                    className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm font-mono text-slate-100 focus:ring-2 focus:ring-sky-500 focus:border-sky-500 focus:outline-none transition"
+                                />
+                                <p className="text-xs text-slate-500 mt-1">
+                                    Nomor berikutnya: <span className="font-mono text-slate-300">{data?.pratinjau ?? "-"}</span>
+                                </p>
+                            </div>
+                            <div>
+                                <label className="block text-sm font-medium text-slate-300 mb-2">
+                                    Reset Nomor
+                                </label>
+                                <select
+                                    value={data?.reset ?? "bulanan"}
+                                    onChange={(e) => ubah(jenis, { reset: e.target.value as ResetNomor })}
+                                    className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-sky-500 focus:border-sky-500 focus:outline-none transition"
+                                >
+                                    <option value="bulanan">Setiap bulan</option>
+                                    <option value="tahunan">Setiap tahun</option>
+                                    <option value="tidak">Tidak pernah</option>
+                                </select>
+                            </div>
+                        </div>
+                    );
+                })}
+
+                <p className="text-xs text-slate-500">
+                    Token: {"{YYYY}"} tahun, {"{YY}"} tahun 2 digit, {"{MM}"} bulan, {"{DD}"} tanggal,
+                    {" {NNNN}"} nomor urut (jumlah N = panjang nomor). Kosongkan untuk memakai format bawaan.
+                    Reset bulanan wajib memuat {"{MM}"} dan tahun, reset tahunan wajib memuat tahun.
+                </p>
+
+                <button
+                    onClick={saveNomor}
+                    disabled={saving}
+                    className="flex items-center gap-2 px-5 py-2.5 bg-blue-600 hover:bg-blue-500 text-white rounded-lg text-sm font-medium transition disabled:opacity-50"
+                >
+                    <Save size={16} />
+                    {saving ? "Menyimpan..." : "Simpan Format Nomor"}
+                </button>
+            </div>
+        </div>
+    );
+}
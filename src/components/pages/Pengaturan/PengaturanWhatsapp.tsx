@@ -0,0 +1,106 @@
+import { useEffect, useState } from "react";
+import { MessageCircle, Save } from "lucide-react";
+import { PengaturanService } from "../../../services/pengaturan.service";
+import { JenisPesanWa } from "../../../types/transaksi.type";
+
+// Bawaan sama dengan template_default di handlers/whatsapp.rs
+const TEMPLATE: { jenis: JenisPesanWa; label: string; bawaan: string }[] = [
+    {
+        jenis: "pengingat",
+        label: "Pengingat Jatuh Tempo",
+        bawaan: "Halo {nama}, mengingatkan bahwa motor {motor} ({plat}) dijadwalkan kembali pada {tanggal_kembali}. Terima kasih.",
+    },
+    {
+        jenis: "terlambat",
+        label: "Keterlambatan",
+        bawaan: "Halo {nama}, motor {motor} ({plat}) seharusnya kembali pada {tanggal_kembali}. Saat ini ada denda keterlambatan {denda}. Mohon segera dikembalikan, terima kasih.",
+    },
+    {
+        jenis: "tagihan",
+        label: "Sisa Tagihan",
+        bawaan: "Halo {nama}, sisa pembayaran sewa motor {motor} ({plat}) adalah {sisa_bayar}. Terima kasih.",
+    },
+];
+
+type Props = {
+    onToast: (message: string, type: "success" | "error") => void;
+};
+
+export default function PengaturanWhatsapp({ onToast }: Props) {
+    const [template, setTemplate] = useState<Record<string, string>>({});
+    const [saving, setSaving] = useState(false);
+
+    useEffect(() => {
+        Promise.all(
+            TEMPLATE.map(async ({ jenis }) => [jenis, await PengaturanService.get(`template_wa_${jenis}`)] as const)
+        )
+            .then((hasil) => setTemplate(Object.fromEntries(hasil)))
+            .catch((err) => console.error("Failed to fetch WhatsApp templates:", err));
+    }, []);
+
+    // Template kosong disimpan apa adanya, backend lalu memakai template bawaan
+    const saveTemplate = async () => {
+        try {
+            setSaving(true);
+            for (const { jenis, label } of TEMPLATE) {
+                await PengaturanService.set(`template_wa_${jenis}`, (template[jenis] ?? "").trim(), `Template WhatsApp ${label}`);
+            }
+            onToast("Template WhatsApp berhasil disimpan", "success");
+        } catch (err) {
+            console.error("Failed to save WhatsApp templates:", err);
+            onToast(`Gagal menyimpan template WhatsApp: ${err}`, "error");
+        } finally {
+            setSaving(false);
+        }
+    };
+
+    return (
+        <div className="bg-slate-800 rounded-xl border border-slate-700 overflow-hidden">
+            <div className="p-5 border-b border-slate-700">
+                <div className="flex items-center gap-3">
+                    <div className="p-2 bg-green-500/10 rounded-lg">
+                        <MessageCircle size={20} className="text-green-400" />
+                    </div>
+                    <div>
+                        <h2 className="text-lg font-bold text-slate-100">
+                            Template WhatsApp
+                        </h2>
+                        <p className="text-sm text-slate-400">
+                            Isi pesan pengingat yang dikirim ke penyewa
+                        </p>
+                    </div>
+                </div>
+            </div>
+            <div className="p-5 space-y-5">
+                {TEMPLATE.map(({ jenis, label, bawaan }) => (
+                    <div key={jenis}>
+                        <label className="block text-sm font-medium text-slate-300 mb-2">
+                            {label}
+                        </label>
+                        <textarea
+                            rows={3}
+                            value={template[jenis] ?? ""}
+                            onChange={(e) => setTemplate((lama) => ({ ...lama, [jenis]: e.target.value }))}
+                            placeholder={bawaan}
+                            className="w-full bg-slate-900 border border-slate-600 rounded-lg px-4 py-3 text-sm text-slate-100 focus:ring-2 focus:ring-green-500 focus:border-green-500 focus:outline-none transition"
+                        />
+                    </div>
+                ))}
+
+                <p className="text-xs text-slate-500">
+                    Token: {"{nama}"} penyewa, {"{motor}"}, {"{plat}"}, {"{tanggal_kembali}"}, {"{denda}"},
+                    {" {sisa_bayar}"}, {"{nomor}"} nomor transaksi. Kosongkan untuk memakai template bawaan.
+                </p>
+
+                <button
+                    onClick={saveTemplate}
+                    disabled={saving}
+                    className="flex items-center gap-2 px-5 py-2.5 bg-blue-600 hover:bg-blue-500 text-white rounded-lg text-sm font-medium transition disabled:opacity-50"
+                >
+                    <Save size={16} />
+                    {saving ? "Menyimpan..." : "Simpan Template"}
+                </button>
+            </div>
+        </div>
+    );
+}
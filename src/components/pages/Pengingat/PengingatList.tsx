@@ -0,0 +1,211 @@
+import { useEffect, useState } from "react";
+import { useNavigate } from "react-router-dom";
+import { listen } from "@tauri-apps/api/event";
+import { AlertCircle, Bell, Calendar, CheckCheck, FileText, Mail, MailOpen, Trash2, Wrench } from "lucide-react";
+import { PengingatService } from "../../../services/pengingat.service";
+import { JenisPengingat, Pengingat } from "../../../types/pengingat.type";
+
+// Event untuk memberi tahu Sidebar/Navbar agar menghitung ulang badge
+export const EVENT_PENGINGAT_BERUBAH = "pengingat-berubah";
+
+const jenisInfo: Record<JenisPengingat, { label: string; icon: React.ReactNode; warna: string }> = {
+    jatuh_tempo: { label: "Jatuh Tempo", icon: <Calendar size={16} />, warna: "text-amber-400" },
+    terlambat: { label: "Terlambat", icon: <AlertCircle size={16} />, warna: "text-red-400" },
+    servis: { label: "Servis", icon: <Wrench size={16} />, warna: "text-blue-400" },
+    dokumen: { label: "Dokumen", icon: <FileText size={16} />, warna: "text-purple-400" },
+};
+
+export default function PengingatList() {
+    const navigate = useNavigate();
+    const [items, setItems] = useState<Pengingat[]>([]);
+    const [hanyaBelumDibaca, setHanyaBelumDibaca] = useState(false);
+    const [loading, setLoading] = useState(true);
+    const [error, setError] = useState("");
+
+    const fetchData = async () => {
+        try {
+            setLoading(true);
+            const data = await PengingatService.getAll(hanyaBelumDibaca);
+            setItems(data);
+            setError("");
+        } catch (err) {
+            console.error("Gagal memuat pengingat:", err);
+            setError(String(err));
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    useEffect(() => {
+        fetchData();
+    }, [hanyaBelumDibaca]);
+
+    // Pengingat baru dari scheduler langsung tampil tanpa reload
+    useEffect(() => {
+        const unlisten = listen("pengingat-baru", () => fetchData());
+        return () => {
+            unlisten.then((fn) => fn());
+        };
+    }, [hanyaBelumDibaca]);
+
+    const beritahuPerubahan = () => {
+        window.dispatchEvent(new Event(EVENT_PENGINGAT_BERUBAH));
+    };
+
+    const handleToggleDibaca = async (p: Pengingat) => {
+        try {
+            await PengingatService.tandaiDibaca(p.pengingat_id, !p.dibaca);
+            await fetchData();
+            beritahuPerubahan();
+        } catch (err) {
+            alert("Gagal memperbarui pengingat: " + err);
+        }
+    };
+
+    const handleTandaiSemua = async () => {
+        try {
+            await PengingatService.tandaiSemuaDibaca();
+            await fetchData();
+            beritahuPerubahan();
+        } catch (err) {
+            alert("Gagal memperbarui pengingat: " + err);
+        }
+    };
+
+    const handleHapus = async (id: number) => {
+        if (!confirm("Hapus pengingat ini?")) return;
+        try {
+            await PengingatService.hapus(id);
+            await fetchData();
+            beritahuPerubahan();
+        } catch (err) {
+            alert("Gagal menghapus pengingat: " + err);
+        }
+    };
+
+    // Buka data yang dirujuk pengingat, sekaligus tandai sudah dibaca
+    const handleBuka = async (p: Pengingat) => {
+        if (!p.dibaca) {
+            try {
+                await PengingatService.tandaiDibaca(p.pengingat_id, true);
+                beritahuPerubahan();
+            } catch (err) {
+                console.error("Gagal menandai pengingat:", err);
+            }
+        }
+        if (p.ref_id == null) return;
+        switch (p.jenis) {
+            case "jatuh_tempo":
+            case "terlambat":
+                navigate(`/transaksi/edit/${p.ref_id}`);
+                break;
+            case "servis":
+                navigate(`/motor/detail/${p.ref_id}`);
+                break;
+            case "dokumen":
+                navigate("/motor");
+                break;
+        }
+    };
+
+    const jumlahBelumDibaca = items.filter((p) => !p.dibaca).length;
+
+    return (
+        <div className="bg-slate-800 rounded-xl border border-slate-700 overflow-hidden">
+            {/* Header */}
+            <div className="p-5 flex flex-col md:flex-row md:items-center justify-between gap-4">
+                <div>
+                    <h2 className="text-xl font-bold text-slate-100">Pengingat</h2>
+                    <p className="text-sm text-slate-400">
+                        Jatuh tempo, keterlambatan, servis dan masa berlaku dokumen
+                    </p>
+                </div>
+
+                <div className="flex items-center gap-2">
+                    <div className="flex bg-slate-900/50 border border-slate-700 rounded-lg p-1">
+                        <button
+                            onClick={() => setHanyaBelumDibaca(false)}
+                            className={`px-3 py-1.5 rounded-md text-sm transition ${!hanyaBelumDibaca ? "bg-blue-600 text-white" : "text-slate-400 hover:text-slate-200"}`}
+                        >
+                            Semua
+                        </button>
+                        <button
+                            onClick={() => setHanyaBelumDibaca(true)}
+                            className={`px-3 py-1.5 rounded-md text-sm transition ${hanyaBelumDibaca ? "bg-blue-600 text-white" : "text-slate-400 hover:text-slate-200"}`}
+                        >
+                            Belum Dibaca
+                        </button>
+                    </div>
+                    <button
+                        onClick={handleTandaiSemua}
+                        disabled={jumlahBelumDibaca === 0}
+                        className="flex items-center gap-1.5 px-3 py-2 bg-blue-600/20 hover:bg-blue-600/30 text-blue-400 border border-blue-700/50 rounded-lg text-sm transition disabled:opacity-50 disabled:cursor-not-allowed"
+                    >
+                        <CheckCheck size={16} />
+                        <span>Tandai Semua Dibaca</span>
+                    </button>
+                </div>
+            </div>
+
+            {error && (
+                <div className="mx-5 mb-4 p-3 bg-red-500/10 border border-red-700/50 rounded-lg text-sm text-red-300">
+                    {error}
+                </div>
+            )}
+
+            {/* Daftar */}
+            {loading ? (
+                <div className="p-10 text-center text-slate-400 text-sm">Memuat...</div>
+            ) : items.length === 0 ? (
+                <div className="p-10 text-center text-slate-500">
+                    <Bell size={28} className="mx-auto mb-2 opacity-50" />
+                    <p className="text-sm">
+                        {hanyaBelumDibaca ? "Semua pengingat sudah dibaca" : "Belum ada pengingat"}
+                    </p>
+                </div>
+            ) : (
+                <div className="divide-y divide-slate-700/50 border-t border-slate-700">
+                    {items.map((p) => {
+                        const info = jenisInfo[p.jenis] ?? jenisInfo.jatuh_tempo;
+                        return (
+                            <div
+                                key={p.pengingat_id}
+                                className={`flex items-start gap-3 p-4 transition hover:bg-slate-700/30 ${p.dibaca ? "" : "bg-blue-500/5"}`}
+                            >
+                                <div className={`mt-0.5 shrink-0 ${info.warna}`}>{info.icon}</div>
+                                <button onClick={() => handleBuka(p)} className="flex-1 min-w-0 text-left">
+                                    <div className="flex items-center gap-2">
+                                        {!p.dibaca && <span className="w-2 h-2 rounded-full bg-blue-500 shrink-0" />}
+                                        <p className={`text-sm truncate ${p.dibaca ? "text-slate-300" : "font-semibold text-slate-100"}`}>
+                                            {p.judul}
+                                        </p>
+                                    </div>
+                                    <p className="text-xs text-slate-400 mt-0.5">{p.pesan}</p>
+                                    <p className="text-xs text-slate-500 mt-1">
+                                        {info.label} · {p.tanggal}
+                                    </p>
+                                </button>
+                                <div className="flex items-center gap-1 shrink-0">
+                                    <button
+                                        onClick={() => handleToggleDibaca(p)}
+                                        className="p-2 rounded-lg text-slate-400 hover:text-blue-400 hover:bg-slate-700 transition"
+                                        title={p.dibaca ? "Tandai belum dibaca" : "Tandai sudah dibaca"}
+                                    >
+                                        {p.dibaca ? <Mail size={16} /> : <MailOpen size={16} />}
+                                    </button>
+                                    <button
+                                        onClick={() => handleHapus(p.pengingat_id)}
+                                        className="p-2 rounded-lg text-slate-400 hover:text-red-400 hover:bg-slate-700 transition"
+                                        title="Hapus"
+                                    >
+                                        <Trash2 size={16} />
+                                    </button>
+                                </div>
+                            </div>
+                        );
+                    })}
+                </div>
+            )}
+        </div>
+    );
+}
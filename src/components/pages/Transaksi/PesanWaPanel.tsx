@@ -0,0 +1,93 @@
+import { useEffect, useState } from "react";
+import { MessageCircle, Send } from "lucide-react";
+import { TransaksiService } from "../../../services/transaksi.service";
+import { JenisPesanWa, PesanWa } from "../../../types/transaksi.type";
+
+const JENIS_PESAN_WA: { jenis: JenisPesanWa; label: string }[] = [
+    { jenis: "pengingat", label: "Pengingat Jatuh Tempo" },
+    { jenis: "terlambat", label: "Keterlambatan" },
+    { jenis: "tagihan", label: "Sisa Tagihan" },
+];
+
+interface PesanWaPanelProps {
+    transaksiId: number;
+    jenisAwal: JenisPesanWa;
+}
+
+export default function PesanWaPanel({ transaksiId, jenisAwal }: PesanWaPanelProps) {
+    const [jenis, setJenis] = useState<JenisPesanWa>(jenisAwal);
+    const [pesan, setPesan] = useState<PesanWa | null>(null);
+    const [error, setError] = useState("");
+    const [sending, setSending] = useState(false);
+
+    // Pratinjau dirender ulang setiap jenis berganti, isinya mengikuti template di Pengaturan
+    useEffect(() => {
+        TransaksiService.renderPesanWa(transaksiId, jenis)
+            .then((p) => {
+                setPesan(p);
+                setError("");
+            })
+            .catch((err) => {
+                console.error("Gagal membuat pesan WhatsApp:", err);
+                setPesan(null);
+                setError(String(err));
+            });
+    }, [transaksiId, jenis]);
+
+    const handleKirim = async () => {
+        setSending(true);
+        try {
+            await TransaksiService.kirimPesanWa(transaksiId, jenis);
+        } catch (err) {
+            alert("Gagal mengirim pesan WhatsApp: " + err);
+        } finally {
+            setSending(false);
+        }
+    };
+
+    return (
+        <div className="bg-slate-800 rounded-xl border border-slate-700 p-5">
+            <div className="flex items-center justify-between mb-3">
+                <h3 className="text-slate-200 font-semibold flex items-center gap-2">
+                    <MessageCircle size={16} className="text-green-400" />
+                    Pesan WhatsApp
+                </h3>
+                <select
+                    value={jenis}
+                    onChange={(e) => setJenis(e.target.value as JenisPesanWa)}
+                    className="bg-slate-900 border border-slate-600 rounded-lg px-3 py-1.5 text-xs text-slate-100 focus:outline-none focus:border-green-500"
+                >
+                    {JENIS_PESAN_WA.map(({ jenis, label }) => (
+                        <option key={jenis} value={jenis}>
+                            {label}
+                        </option>
+                    ))}
+                </select>
+            </div>
+
+            {error ? (
+                <p className="text-sm text-red-300">{error}</p>
+            ) : (
+                <div className="space-y-3">
+                    <p className="p-3 bg-slate-900/50 border border-slate-700 rounded-lg text-sm text-slate-300 whitespace-pre-wrap">
+                        {pesan?.pesan ?? "Memuat..."}
+                    </p>
+                    <div className="flex items-center justify-between gap-3">
+                        <span className="text-xs text-slate-500">
+                            {pesan?.no_hp ? `Ke +${pesan.no_hp}` : "Nomor HP penyewa belum diisi"}
+                        </span>
+                        <button
+                            type="button"
+                            onClick={handleKirim}
+                            disabled={sending || !pesan?.no_hp}
+                            className="flex items-center gap-1 px-2.5 py-1.5 bg-green-600/20 hover:bg-green-600/30 text-green-400 border border-green-700/50 rounded-lg text-xs transition disabled:opacity-50 disabled:cursor-not-allowed"
+                        >
+                            <Send size={14} />
+                            <span>{sending ? "Membuka..." : "Kirim via WhatsApp"}</span>
+                        </button>
+                    </div>
+                </div>
+            )}
+        </div>
+    );
+}
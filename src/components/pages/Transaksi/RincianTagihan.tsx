@@ -0,0 +1,47 @@
+import { Tagihan } from "../../../types/transaksi.type";
+
+interface RincianTagihanProps {
+    tagihan: Tagihan;
+}
+
+const formatCurrency = (amount: number) =>
+    new Intl.NumberFormat("id-ID", { style: "currency", currency: "IDR", maximumFractionDigits: 0 }).format(amount);
+
+// Rincian yang harus dibayar penyewa, sama dengan perhitungan get_tagihan_transaksi di backend
+export default function RincianTagihan({ tagihan }: RincianTagihanProps) {
+    const baris: [string, number][] = [
+        ["Sewa", tagihan.total_bayar],
+        ["Denda", tagihan.denda],
+        ["Kelebihan Km", tagihan.biaya_km_lebih],
+        ["BBM", tagihan.biaya_bbm],
+        ["Kerusakan", tagihan.biaya_kerusakan],
+    ];
+
+    return (
+        <div className="space-y-1.5 text-sm">
+            {baris
+                .filter(([label, nilai]) => label === "Sewa" || nilai > 0)
+                .map(([label, nilai]) => (
+                    <div key={label} className="flex justify-between">
+                        <span className="text-slate-400">{label}</span>
+                        <span className="text-slate-200">{formatCurrency(nilai)}</span>
+                    </div>
+                ))}
+            <hr className="border-slate-700" />
+            <div className="flex justify-between font-semibold">
+                <span className="text-slate-300">Total Tagihan</span>
+                <span className="text-slate-100">{formatCurrency(tagihan.total_tagihan)}</span>
+            </div>
+            <div className="flex justify-between">
+                <span className="text-slate-400">Sudah Dibayar</span>
+                <span className="text-emerald-400">{formatCurrency(tagihan.dibayar)}</span>
+            </div>
+            <div className="flex justify-between font-semibold">
+                <span className="text-slate-300">Sisa</span>
+                <span className={tagihan.sisa_bayar > 0 ? "text-red-400" : "text-emerald-400"}>
+                    {formatCurrency(tagihan.sisa_bayar)}
+                </span>
+            </div>
+        </div>
+    );
+}
@@ -53,6 +53,7 @@ export default function TransaksiAdd() {
             navigate("/transaksi");
         } catch (error) {
             console.error("Failed to save transaksi:", error);
+            alert("Gagal menyimpan transaksi: " + error);
         } finally {
             setLoading(false);
         }
@@ -3,11 +3,14 @@ import { useNavigate, useParams, Link } from "react-router-dom";
 import { invoke } from "@tauri-apps/api/core";
 import { ArrowLeft } from "lucide-react";
 import TransaksiForm, { TransaksiFormData } from "./TransaksiForm";
+import KerusakanPanel from "../Kerusakan/KerusakanPanel";
+import PesanWaPanel from "./PesanWaPanel";
 import { Motor } from "../../../types/motor.type";
 import { Penyewa } from "../../../types/penyewa.type";
-import { Transaksi } from "../../../types/transaksi.type";
+import { Tagihan, Transaksi } from "../../../types/transaksi.type";
 import { getMotor } from "../../../services/motor.service";
 import { PenyewaService } from "../../../services/penyewa.service";
+import { TransaksiService } from "../../../services/transaksi.service";
 
 export default function TransaksiEdit() {
     const { id } = useParams();
@@ -19,6 +22,7 @@ export default function TransaksiEdit() {
     const [penyewas, setPenyewas] = useState<Penyewa[]>([]);
     const [dendaPerHari, setDendaPerHari] = useState(0);
     const [diskonInfo, setDiskonInfo] = useState({ aktif: false, persen: 0, mulai: "", berakhir: "" });
+    const [tagihan, setTagihan] = useState<Tagihan | null>(null);
 
     useEffect(() => {
         fetchData();
@@ -27,10 +31,11 @@ export default function TransaksiEdit() {
     const fetchData = async () => {
         setFetching(true);
         try {
-            const [m, p, result, dendaStr, dPersen, dMulai, dBerakhir, dAktif] = await Promise.all([
+            const [m, p, result, t, dendaStr, dPersen, dMulai, dBerakhir, dAktif] = await Promise.all([
                 getMotor(),
                 PenyewaService.getAll(),
                 invoke<Transaksi>("get_transaksi_by_id", { id: Number(id) }),
+                TransaksiService.getTagihan(Number(id)),
                 invoke<string>("get_pengaturan", { key: "denda_per_hari" }).catch(() => "0"),
                 invoke<string>("get_pengaturan", { key: "diskon_persen" }).catch(() => "0"),
                 invoke<string>("get_pengaturan", { key: "diskon_tanggal_mulai" }).catch(() => ""),
@@ -40,6 +45,7 @@ export default function TransaksiEdit() {
 
             setMotors(m);
             setPenyewas(p);
+            setTagihan(t);
             setDendaPerHari(Number(dendaStr) || 0);
             setDiskonInfo({
                 aktif: dAktif === "1",
@@ -71,6 +77,7 @@ export default function TransaksiEdit() {
             navigate("/transaksi");
         } catch (error) {
             console.error("Failed to update transaksi:", error);
+            alert("Gagal memperbarui transaksi: " + error);
         } finally {
             setLoading(false);
         }
@@ -103,6 +110,24 @@ export default function TransaksiEdit() {
                 penyewas={penyewas}
                 dendaPerHari={dendaPerHari}
                 diskonInfo={diskonInfo}
+                tagihan={tagihan}
+            />
+
+            <PesanWaPanel
+                transaksiId={Number(id)}
+                jenisAwal={
+                    initialData?.status === "terlambat"
+                        ? "terlambat"
+                        : initialData?.status === "kembali" && (tagihan?.sisa_bayar ?? 0) > 0
+                            ? "tagihan"
+                            : "pengingat"
+                }
+            />
+
+            {/* Biaya kerusakan yang dibebankan ikut masuk tagihan */}
+            <KerusakanPanel
+                transaksiId={Number(id)}
+                onChange={() => TransaksiService.getTagihan(Number(id)).then(setTagihan)}
             />
         </div>
     );
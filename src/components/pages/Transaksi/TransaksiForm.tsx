@@ -2,12 +2,14 @@ import React, { useEffect, useState } from 'react';
 import { useForm } from 'react-hook-form';
 import { FormCard, FormGroup, Label, Input, Select } from '../../ui/Form';
 import Button from '../../ui/Button';
-import { Save, Upload, AlertTriangle, Camera, Tag } from 'lucide-react';
-import { Transaksi } from '../../../types/transaksi.type';
+import { Save, Upload, AlertTriangle, Camera, Tag, Gauge, Receipt, Fuel, ShieldAlert } from 'lucide-react';
+import { Tagihan, Transaksi } from '../../../types/transaksi.type';
 import { Motor } from '../../../types/motor.type';
-import { Penyewa } from '../../../types/penyewa.type';
-import { invoke } from '@tauri-apps/api/core';
+import { Penyewa, PenyewaFlag } from '../../../types/penyewa.type';
+import { PenyewaFlagService } from '../../../services/penyewa.service';
+import { LampiranService } from '../../../services/lampiran.service';
 import { convertFileSrc } from '@tauri-apps/api/core';
+import RincianTagihan from './RincianTagihan';
 
 export type TransaksiFormData = Omit<Transaksi, 'transaksi_id'>;
 
@@ -26,13 +28,19 @@ interface TransaksiFormProps {
     penyewas: Penyewa[];
     dendaPerHari: number;
     diskonInfo?: DiskonInfo;
+    // Hanya ada saat edit: rincian tagihan dari backend
+    tagihan?: Tagihan | null;
 }
 
-const TransaksiForm: React.FC<TransaksiFormProps> = ({ initialData, onSubmit, isLoading, motors, penyewas, dendaPerHari, diskonInfo }) => {
+// Input angka opsional: kosong dikirim sebagai null, bukan NaN
+const angkaOpsional = (v: unknown) => (v === '' || v == null ? null : Number(v));
+
+const TransaksiForm: React.FC<TransaksiFormProps> = ({ initialData, onSubmit, isLoading, motors, penyewas, dendaPerHari, diskonInfo, tagihan }) => {
     const [preview, setPreview] = useState<string | null>(null);
     const [uploadingImage, setUploadingImage] = useState(false);
     const [hargaNormal, setHargaNormal] = useState<number>(0);
     const [isDiskonApplied, setIsDiskonApplied] = useState(false);
+    const [flagPenyewa, setFlagPenyewa] = useState<PenyewaFlag | null>(null);
 
     const {
         register,
@@ -56,7 +64,7 @@ const TransaksiForm: React.FC<TransaksiFormProps> = ({ initialData, onSubmit, is
         if (initialData) {
             reset(initialData);
             if (initialData.foto_bukti) {
-                setPreview(convertFileSrc(initialData.foto_bukti));
+                setPreview(convertFileSrc(initialData.foto_bukti_thumbnail ?? initialData.foto_bukti));
             }
         }
     }, [initialData, reset]);
@@ -66,6 +74,48 @@ const TransaksiForm: React.FC<TransaksiFormProps> = ({ initialData, onSubmit, is
     const tanggalKembaliRencana = watch('tanggal_kembali_rencana');
     const tanggalKembaliAktual = watch('tanggal_kembali_aktual');
     const fotoBuktiPath = watch('foto_bukti');
+    const kmAwal = watch('km_awal');
+    const penyewaId = watch('penyewa_id');
+
+    const selectedMotor = motors.find(m => m.motor_id === motorId);
+
+    // Transaksi baru: km awal diisi dari odometer motor yang dipilih
+    useEffect(() => {
+        if (!initialData && selectedMotor?.odometer != null) {
+            setValue('km_awal', selectedMotor.odometer);
+        }
+    }, [initialData, selectedMotor?.motor_id, setValue]);
+
+    // Cek status blacklist / waspada penyewa yang dipilih
+    useEffect(() => {
+        if (!penyewaId) {
+            setFlagPenyewa(null);
+            return;
+        }
+        let batal = false;
+        PenyewaFlagService.cek(penyewaId)
+            .then((flag) => {
+                if (!batal) setFlagPenyewa(flag);
+            })
+            .catch((err) => {
+                console.error('Gagal cek flag penyewa:', err);
+                if (!batal) setFlagPenyewa(null);
+            });
+        return () => {
+            batal = true;
+        };
+    }, [penyewaId]);
+
+    const submitForm = (data: TransaksiFormData) => {
+        if (
+            flagPenyewa?.status === 'waspada' &&
+            !initialData &&
+            !confirm(`Penyewa berstatus WASPADA: ${flagPenyewa.alasan}\n\nTetap lanjutkan transaksi?`)
+        ) {
+            return;
+        }
+        onSubmit(data);
+    };
 
     // Auto-calculate total_bayar with discount
     useEffect(() => {
@@ -125,23 +175,14 @@ const TransaksiForm: React.FC<TransaksiFormProps> = ({ initialData, onSubmit, is
         const file = e.target.files?.[0];
         if (!file) return;
 
+        setPreview(URL.createObjectURL(file));
         setUploadingImage(true);
         try {
-            const reader = new FileReader();
-            reader.onload = async () => {
-                const base64 = reader.result as string;
-                setPreview(base64);
-                try {
-                    const path = await invoke<string>("save_transaksi_image", { base64 });
-                    setValue('foto_bukti', path);
-                } catch (err) {
-                    console.error("Failed to save image:", err);
-                    alert("Gagal mengunggah gambar");
-                }
-            };
-            reader.readAsDataURL(file);
+            const path = await LampiranService.unggah(file);
+            setValue('foto_bukti', path);
         } catch (err) {
-            console.error("File reading error:", err);
+            console.error("Failed to save image:", err);
+            alert("Gagal mengunggah gambar: " + err);
         } finally {
             setUploadingImage(false);
         }
@@ -155,7 +196,7 @@ const TransaksiForm: React.FC<TransaksiFormProps> = ({ initialData, onSubmit, is
             title={initialData ? 'Edit Transaksi' : 'Tambah Transaksi Baru'}
             description="Lengkapi data transaksi dengan benar."
         >
-            <form onSubmit={handleSubmit(onSubmit)} className="space-y-6">
+            <form onSubmit={handleSubmit(submitForm)} className="space-y-6">
                 <div className="grid grid-cols-1 md:grid-cols-2 gap-6">
                     <FormGroup>
                         <Label required>Motor</Label>
@@ -185,6 +226,26 @@ const TransaksiForm: React.FC<TransaksiFormProps> = ({ initialData, onSubmit, is
                                 </option>
                             ))}
                         </Select>
+                        {flagPenyewa && (
+                            <div
+                                className={`mt-2 flex items-start gap-2 rounded-lg border p-3 text-sm ${
+                                    flagPenyewa.status === 'blacklist'
+                                        ? 'border-red-500/40 bg-red-500/10 text-red-300'
+                                        : 'border-amber-500/40 bg-amber-500/10 text-amber-300'
+                                }`}
+                            >
+                                <ShieldAlert size={16} className="mt-0.5 shrink-0" />
+                                <div>
+                                    <p className="font-semibold">
+                                        {flagPenyewa.status === 'blacklist'
+                                            ? 'Penyewa di-blacklist, tidak bisa menyewa'
+                                            : 'Penyewa berstatus waspada'}
+                                    </p>
+                                    <p>{flagPenyewa.alasan}</p>
+                                    <p className="text-xs opacity-75">Sejak {flagPenyewa.tanggal}</p>
+                                </div>
+                            </div>
+                        )}
                     </FormGroup>
 
                     <FormGroup>
@@ -289,6 +350,121 @@ const TransaksiForm: React.FC<TransaksiFormProps> = ({ initialData, onSubmit, is
                     </div>
                 </div>
 
+                {/* Kilometer Section */}
+                <div className="border border-slate-700 rounded-xl p-5 space-y-4">
+                    <h3 className="text-sm font-semibold text-slate-300 uppercase tracking-wider flex items-center gap-2">
+                        <Gauge size={16} className="text-blue-400" />
+                        Kilometer
+                    </h3>
+                    <div className="grid grid-cols-1 md:grid-cols-3 gap-6">
+                        <FormGroup>
+                            <Label>Km Awal</Label>
+                            <Input
+                                type="number"
+                                min={0}
+                                {...register('km_awal', {
+                                    setValueAs: angkaOpsional,
+                                    validate: (v) =>
+                                        !!initialData || v == null || selectedMotor?.odometer == null || v >= selectedMotor.odometer ||
+                                        `Tidak boleh di bawah odometer motor (${selectedMotor.odometer} km)`,
+                                })}
+                                error={errors.km_awal?.message}
+                            />
+                            {selectedMotor?.odometer != null && (
+                                <p className="text-xs text-slate-500 mt-1">
+                                    Odometer motor: {selectedMotor.odometer.toLocaleString('id-ID')} km
+                                </p>
+                            )}
+                        </FormGroup>
+
+                        <FormGroup>
+                            <Label>Km Kembali</Label>
+                            <Input
+                                type="number"
+                                min={0}
+                                {...register('km_akhir', {
+                                    setValueAs: angkaOpsional,
+                                    validate: (v) =>
+                                        v == null || kmAwal == null || v >= kmAwal || 'Tidak boleh lebih kecil dari km awal',
+                                })}
+                                error={errors.km_akhir?.message}
+                            />
+                        </FormGroup>
+
+                        <FormGroup>
+                            <Label>Biaya Kelebihan Km</Label>
+                            <Input
+                                type="number"
+                                value={initialData?.biaya_km_lebih ?? 0}
+                                readOnly
+                                className="bg-slate-900/50 cursor-not-allowed"
+                            />
+                            <p className="text-xs text-slate-500 mt-1">Dihitung otomatis saat disimpan</p>
+                        </FormGroup>
+                    </div>
+                </div>
+
+                {/* BBM Section */}
+                <div className="border border-slate-700 rounded-xl p-5 space-y-4">
+                    <h3 className="text-sm font-semibold text-slate-300 uppercase tracking-wider flex items-center gap-2">
+                        <Fuel size={16} className="text-amber-400" />
+                        Bahan Bakar
+                    </h3>
+                    <div className="grid grid-cols-1 md:grid-cols-3 gap-6">
+                        <FormGroup>
+                            <Label>BBM Awal (%)</Label>
+                            <Input
+                                type="number"
+                                min={0}
+                                max={100}
+                                placeholder="0 - 100"
+                                {...register('bbm_awal', {
+                                    setValueAs: angkaOpsional,
+                                    validate: (v) => v == null || (v >= 0 && v <= 100) || 'Harus antara 0 dan 100',
+                                })}
+                                error={errors.bbm_awal?.message}
+                            />
+                        </FormGroup>
+
+                        <FormGroup>
+                            <Label>BBM Kembali (%)</Label>
+                            <Input
+                                type="number"
+                                min={0}
+                                max={100}
+                                placeholder="0 - 100"
+                                {...register('bbm_akhir', {
+                                    setValueAs: angkaOpsional,
+                                    validate: (v) => v == null || (v >= 0 && v <= 100) || 'Harus antara 0 dan 100',
+                                })}
+                                error={errors.bbm_akhir?.message}
+                            />
+                        </FormGroup>
+
+                        <FormGroup>
+                            <Label>Biaya BBM</Label>
+                            <Input
+                                type="number"
+                                value={initialData?.biaya_bbm ?? 0}
+                                readOnly
+                                className="bg-slate-900/50 cursor-not-allowed"
+                            />
+                            <p className="text-xs text-slate-500 mt-1">Dihitung otomatis jika BBM kembali lebih sedikit</p>
+                        </FormGroup>
+                    </div>
+                </div>
+
+                {/* Tagihan (hanya saat edit) */}
+                {tagihan && (
+                    <div className="border border-slate-700 rounded-xl p-5 space-y-4">
+                        <h3 className="text-sm font-semibold text-slate-300 uppercase tracking-wider flex items-center gap-2">
+                            <Receipt size={16} className="text-emerald-400" />
+                            Tagihan
+                        </h3>
+                        <RincianTagihan tagihan={tagihan} />
+                    </div>
+                )}
+
                 {/* Foto Bukti Section */}
                 <div className="border border-slate-700 rounded-xl p-5 space-y-4">
                     <h3 className="text-sm font-semibold text-slate-300 uppercase tracking-wider flex items-center gap-2">
@@ -1,7 +1,7 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { convertFileSrc } from "@tauri-apps/api/core";
-import { Plus, Search, Image, X, Tag, FileSpreadsheet, FileText, CheckCircle, FolderOpen } from "lucide-react";
+import { Plus, Search, Image, X, Tag, FileSpreadsheet, FileText, CheckCircle, FolderOpen, MessageCircle } from "lucide-react";
 import * as XLSX from "xlsx";
 import jsPDF from "jspdf";
 import autoTable from "jspdf-autotable";
@@ -11,6 +11,7 @@ import { Motor } from "@/types/motor.type";
 import { Penyewa } from "../../../types/penyewa.type";
 import { getMotor } from "../../../services/motor.service";
 import { PenyewaService } from "../../../services/penyewa.service";
+import { TransaksiService } from "../../../services/transaksi.service";
 import Button from "@/components/ui/Button";
 import Table from "@/components/ui/Table";
 
@@ -95,6 +96,15 @@ export default function TransaksiList() {
         }
     };
 
+    // Buka WhatsApp dengan template pengingat, atau template keterlambatan jika sudah lewat
+    const handleKirimWa = async (row: Transaksi) => {
+        try {
+            await TransaksiService.kirimPesanWa(row.transaksi_id, row.status === "terlambat" ? "terlambat" : "pengingat");
+        } catch (err) {
+            alert("Gagal mengirim pesan WhatsApp: " + err);
+        }
+    };
+
     const handleOpenFolder = async () => {
         try {
             await invoke("open_folder", { path: toast.folderPath });
@@ -316,7 +326,7 @@ export default function TransaksiList() {
             header: "Aksi",
             accessor: "transaksi_id" as const,
             align: "center" as const,
-            width: "180px",
+            width: "230px",
             render: (_: any, row: Transaksi) => (
                 <div className="flex justify-center gap-2">
                     {row.foto_bukti && (
@@ -325,7 +335,26 @@ export default function TransaksiList() {
                             className="px-2 py-1 text-xs rounded hover:bg-purple-900 text-purple-300 border border-purple-800 flex items-center gap-1"
                             title="Lihat Bukti"
                         >
-                            <Image size={12} /> Bukti
+                            {row.foto_bukti_thumbnail ? (
+                                <img
+                                    src={convertFileSrc(row.foto_bukti_thumbnail)}
+                                    alt="Bukti"
+                                    className="w-5 h-5 rounded object-cover"
+                                    loading="lazy"
+                                />
+                            ) : (
+                                <Image size={12} />
+                            )}{" "}
+                            Bukti
+                        </button>
+                    )}
+                    {row.status !== "kembali" && (
+                        <button
+                            onClick={() => handleKirimWa(row)}
+                            className="px-2 py-1 text-xs rounded hover:bg-green-900 text-green-300 border border-green-800 flex items-center gap-1"
+                            title="Kirim pengingat WhatsApp"
+                        >
+                            <MessageCircle size={12} /> WA
                         </button>
                     )}
                     <Button
@@ -0,0 +1,26 @@
+import { invoke } from "@tauri-apps/api/core";
+import { BackupHistory, InfoRestore } from "../types/backup.type";
+
+export const BackupService = {
+    getHistory: async (limit?: number): Promise<BackupHistory[]> => {
+        return await invoke("get_backup_history", { limit });
+    },
+    // Backup ke folder backup otomatis sekarang juga
+    jalankanSekarang: async (): Promise<BackupHistory> => {
+        return await invoke("jalankan_backup_sekarang");
+    },
+    // Kata sandi backup otomatis; null untuk mematikan enkripsi backup otomatis
+    aturSandiOtomatis: async (kataSandi: string | null): Promise<void> => {
+        return await invoke("atur_sandi_backup_otomatis", { kataSandi });
+    },
+    adaSandiOtomatis: async (): Promise<boolean> => {
+        return await invoke("cek_sandi_backup_otomatis");
+    },
+    getInfoRestoreTerakhir: async (): Promise<InfoRestore | null> => {
+        return await invoke("get_info_restore_terakhir");
+    },
+    // Kembalikan data ke kondisi sebelum import terakhir, aplikasi perlu restart sesudahnya
+    batalkanRestoreTerakhir: async (): Promise<InfoRestore> => {
+        return await invoke("batalkan_restore_terakhir");
+    },
+};
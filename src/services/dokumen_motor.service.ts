@@ -0,0 +1,23 @@
+import { invoke } from "@tauri-apps/api/core";
+import { DokumenMotor, DokumenMotorKadaluarsa } from "../types/dokumen_motor.type";
+
+export const DokumenMotorService = {
+    getAll: async (motorId?: number): Promise<DokumenMotor[]> => {
+        return await invoke("get_all_dokumen_motor", { motorId: motorId ?? null });
+    },
+    getById: async (id: number): Promise<DokumenMotor> => {
+        return await invoke("get_dokumen_motor_by_id", { id });
+    },
+    create: async (data: DokumenMotor): Promise<void> => {
+        await invoke("create_dokumen_motor", { data });
+    },
+    update: async (id: number, data: DokumenMotor): Promise<void> => {
+        await invoke("update_dokumen_motor", { id, data });
+    },
+    delete: async (id: number): Promise<void> => {
+        await invoke("delete_dokumen_motor", { id });
+    },
+    getAkanKadaluarsa: async (hari?: number): Promise<DokumenMotorKadaluarsa[]> => {
+        return await invoke("get_dokumen_motor_akan_kadaluarsa", { hari: hari ?? null });
+    },
+};
@@ -0,0 +1,14 @@
+import { invoke } from "@tauri-apps/api/core";
+import { ImporLaporan, JenisImpor, PemetaanKolom } from "../types/impor.type";
+
+export const ImporService = {
+    getHeader: async (filePath: string): Promise<string[]> => {
+        return await invoke("get_header_impor", { filePath });
+    },
+    preview: async (jenis: JenisImpor, filePath: string, mapping?: PemetaanKolom): Promise<ImporLaporan> => {
+        return await invoke("preview_impor", { jenis, filePath, mapping });
+    },
+    jalankan: async (jenis: JenisImpor, filePath: string, mapping?: PemetaanKolom): Promise<ImporLaporan> => {
+        return await invoke("jalankan_impor", { jenis, filePath, mapping });
+    },
+};
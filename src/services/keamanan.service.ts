@@ -0,0 +1,15 @@
+import { invoke } from "@tauri-apps/api/core";
+import { StatusEnkripsiDatabase } from "../types/keamanan.type";
+
+export const KeamananService = {
+    getStatusEnkripsi: async (): Promise<StatusEnkripsiDatabase> => {
+        return await invoke("get_status_enkripsi_database");
+    },
+    bukaKunci: async (kataSandi: string): Promise<void> => {
+        return await invoke("buka_kunci_database", { kataSandi });
+    },
+    // Enkripsi database yang sudah ada; kata sandi diminta setiap aplikasi dibuka
+    aktifkanEnkripsi: async (kataSandi: string): Promise<void> => {
+        return await invoke("aktifkan_enkripsi_database", { kataSandi });
+    },
+};
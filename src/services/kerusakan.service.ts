@@ -0,0 +1,26 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Kerusakan } from "../types/kerusakan.type";
+
+export const KerusakanService = {
+    getAll: async (filter?: { transaksiId?: number; motorId?: number }): Promise<Kerusakan[]> => {
+        return await invoke("get_all_kerusakan", {
+            transaksiId: filter?.transaksiId ?? null,
+            motorId: filter?.motorId ?? null,
+        });
+    },
+    getByMotor: async (motorId: number): Promise<Kerusakan[]> => {
+        return await invoke("get_all_kerusakan", { transaksiId: null, motorId });
+    },
+    getById: async (id: number): Promise<Kerusakan> => {
+        return await invoke("get_kerusakan_by_id", { id });
+    },
+    create: async (data: Kerusakan): Promise<void> => {
+        await invoke("create_kerusakan", { data });
+    },
+    update: async (id: number, data: Kerusakan): Promise<void> => {
+        await invoke("update_kerusakan", { id, data });
+    },
+    delete: async (id: number): Promise<void> => {
+        await invoke("delete_kerusakan", { id });
+    },
+};
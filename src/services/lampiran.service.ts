@@ -0,0 +1,17 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export const LampiranService = {
+    // Kirim file sebagai bytes mentah, tanpa konversi base64
+    unggah: async (file: Blob | ArrayBuffer | Uint8Array): Promise<string> => {
+        const bytes =
+            file instanceof Blob ? new Uint8Array(await file.arrayBuffer()) : new Uint8Array(file);
+        return await invoke("unggah_lampiran", bytes);
+    },
+    // Buka dialog pilih file di backend lalu salin file-nya langsung dari disk, null jika dibatalkan
+    impor: async (): Promise<string | null> => {
+        return await invoke("impor_lampiran");
+    },
+    bersihkan: async (): Promise<number> => {
+        return await invoke("bersihkan_lampiran");
+    },
+};
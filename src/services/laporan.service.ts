@@ -0,0 +1,14 @@
+import { invoke } from "@tauri-apps/api/core";
+import { ProfitMotor, UtilisasiArmada } from "../types/laporan.type";
+
+export const LaporanService = {
+    getProfitMotor: async (tanggalMulai: string, tanggalSelesai: string): Promise<ProfitMotor[]> => {
+        return await invoke("get_laporan_profit_motor", { tanggalMulai, tanggalSelesai });
+    },
+    eksporProfitMotor: async (tanggalMulai: string, tanggalSelesai: string): Promise<string> => {
+        return await invoke("ekspor_laporan_profit_motor", { tanggalMulai, tanggalSelesai });
+    },
+    getUtilisasi: async (tanggalMulai: string, tanggalSelesai: string): Promise<UtilisasiArmada> => {
+        return await invoke("get_laporan_utilisasi", { tanggalMulai, tanggalSelesai });
+    },
+};
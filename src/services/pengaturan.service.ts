@@ -0,0 +1,16 @@
+import { invoke } from "@tauri-apps/api/core";
+import { JenisNomorDokumen } from "../types/pengaturan.type";
+
+export const PengaturanService = {
+    // String kosong jika key belum pernah diatur
+    get: async (key: string): Promise<string> => {
+        return await invoke("get_pengaturan", { key });
+    },
+    set: async (key: string, value: string, keterangan?: string): Promise<void> => {
+        return await invoke("set_pengaturan", { key, value, keterangan: keterangan ?? null });
+    },
+    // Nomor dokumen berikutnya sesuai format tersimpan, counter tidak dinaikkan
+    previewNomor: async (jenis: JenisNomorDokumen, tanggal?: string): Promise<string> => {
+        return await invoke("preview_nomor_dokumen", { jenis, tanggal: tanggal ?? null });
+    },
+};
@@ -0,0 +1,20 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Pengingat } from "../types/pengingat.type";
+
+export const PengingatService = {
+    getAll: async (hanyaBelumDibaca?: boolean): Promise<Pengingat[]> => {
+        return await invoke("get_all_pengingat", { hanyaBelumDibaca });
+    },
+    countBelumDibaca: async (): Promise<number> => {
+        return await invoke("count_pengingat_belum_dibaca");
+    },
+    tandaiDibaca: async (id: number, dibaca: boolean = true): Promise<void> => {
+        return await invoke("tandai_pengingat_dibaca", { id, dibaca });
+    },
+    tandaiSemuaDibaca: async (): Promise<void> => {
+        return await invoke("tandai_semua_pengingat_dibaca");
+    },
+    hapus: async (id: number): Promise<void> => {
+        return await invoke("delete_pengingat", { id });
+    },
+};
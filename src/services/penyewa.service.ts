@@ -1,6 +1,13 @@
 import { invoke } from "@tauri-apps/api/core";
-import { Penyewa } from "../types/penyewa.type";
-import { Transaksi } from "../types/transaksi.type";
+import {
+  DokumenPenyewa,
+  JenisDokumenPenyewa,
+  NilaiPenyewa,
+  Penyewa,
+  PenyewaFlag,
+  RiwayatPenyewa,
+} from "../types/penyewa.type";
+import { Transaksi, TransaksiTerlambat } from "../types/transaksi.type";
 
 export const TransaksiService = {
   getAll: () => invoke<Transaksi[]>("get_all_transaksi"),
@@ -8,6 +15,8 @@ export const TransaksiService = {
   create: (data: Transaksi) => invoke("create_transaksi", { data }),
   update: (data: Transaksi) => invoke("update_transaksi", { data }),
   delete: (id: number) => invoke("delete_transaksi", { id }),
+  // Hasil pemeriksaan keterlambatan terakhir dari scheduler backend
+  getTerlambatTerakhir: () => invoke<TransaksiTerlambat[]>("get_transaksi_terlambat_terakhir"),
 };
 
 export const PenyewaService = {
@@ -15,5 +24,22 @@ export const PenyewaService = {
   create: (data: Penyewa) => invoke("create_penyewa", { data }),
   update: (data: Penyewa) => invoke("update_penyewa", { data }),
   delete: (id: number) => invoke("delete_penyewa", { id }),
+  getRiwayat: (id: number) => invoke<RiwayatPenyewa>("get_penyewa_riwayat", { id }),
+  getTop: (limit?: number) => invoke<NilaiPenyewa[]>("get_top_penyewa", { limit: limit ?? null }),
 };
 
+export const PenyewaFlagService = {
+  getAll: () => invoke<PenyewaFlag[]>("get_all_penyewa_flag"),
+  create: (data: PenyewaFlag) => invoke("create_penyewa_flag", { data }),
+  update: (id: number, data: PenyewaFlag) => invoke("update_penyewa_flag", { id, data }),
+  delete: (id: number) => invoke("delete_penyewa_flag", { id }),
+  cek: (penyewaId: number) => invoke<PenyewaFlag | null>("cek_penyewa_flag", { penyewaId }),
+};
+
+export const DokumenPenyewaService = {
+  getAll: (penyewaId: number) => invoke<DokumenPenyewa[]>("get_dokumen_penyewa", { penyewaId }),
+  // path adalah hasil LampiranService.unggah / impor
+  save: (penyewaId: number, jenis: JenisDokumenPenyewa, path: string) =>
+    invoke<DokumenPenyewa>("save_dokumen_penyewa", { penyewaId, jenis, path }),
+  delete: (id: number) => invoke("delete_dokumen_penyewa", { id }),
+};
@@ -0,0 +1,27 @@
+import { invoke } from "@tauri-apps/api/core";
+import { MotorPerluServis, ServisMotor } from "../types/servis_motor.type";
+
+export const ServisMotorService = {
+    getAll: async (motorId?: number): Promise<ServisMotor[]> => {
+        return await invoke("get_all_servis_motor", { motorId: motorId ?? null });
+    },
+    getById: async (id: number): Promise<ServisMotor> => {
+        return await invoke("get_servis_motor_by_id", { id });
+    },
+    create: async (data: ServisMotor): Promise<void> => {
+        await invoke("create_servis_motor", { data });
+    },
+    update: async (id: number, data: ServisMotor): Promise<void> => {
+        await invoke("update_servis_motor", { id, data });
+    },
+    delete: async (id: number): Promise<void> => {
+        await invoke("delete_servis_motor", { id });
+    },
+    // Ambang default diambil dari backend jika tidak diisi
+    getPerluServis: async (ambangHari?: number, ambangKm?: number): Promise<MotorPerluServis[]> => {
+        return await invoke("get_motor_perlu_servis", {
+            ambangHari: ambangHari ?? null,
+            ambangKm: ambangKm ?? null,
+        });
+    },
+};
@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import { Transaksi } from "../types/transaksi.type";
+import { JenisPesanWa, PesanWa, Tagihan, Transaksi } from "../types/transaksi.type";
 
 export const TransaksiService = {
     getAll: async (): Promise<Transaksi[]> => {
@@ -17,4 +17,19 @@ export const TransaksiService = {
     delete: async (id: number): Promise<void> => {
         await invoke("delete_transaksi", { id });
     },
+    // Sewa + denda + kelebihan km + BBM + kerusakan, dikurangi pembayaran yang sudah masuk
+    getTagihan: async (id: number): Promise<Tagihan> => {
+        return await invoke("get_tagihan_transaksi", { id });
+    },
+    getAllTagihan: async (): Promise<Tagihan[]> => {
+        return await invoke("get_all_tagihan");
+    },
+    // Pesan WhatsApp dari template pengaturan, tanpa membuka WhatsApp
+    renderPesanWa: async (id: number, jenis: JenisPesanWa): Promise<PesanWa> => {
+        return await invoke("render_pesan_wa", { transaksiId: id, jenis });
+    },
+    // Render lalu buka wa.me di browser / aplikasi WhatsApp
+    kirimPesanWa: async (id: number, jenis: JenisPesanWa): Promise<PesanWa> => {
+        return await invoke("kirim_pesan_wa", { transaksiId: id, jenis });
+    },
 };
\ No newline at end of file
@@ -0,0 +1,18 @@
+export type PemicuBackup = "harian" | "keluar" | "manual"
+
+export interface BackupHistory {
+  backup_id: number
+  waktu: string
+  pemicu: PemicuBackup
+  path: string
+  ukuran: number
+  jumlah_file: number
+  status: "sukses" | "gagal"
+  pesan?: string | null
+}
+
+export interface InfoRestore {
+  waktu: string
+  sumber: string
+  snapshot: string
+}
@@ -5,6 +5,8 @@ export interface BuktiPelunasan {
     jumlah_bayar: number;
     metode_bayar: string;
     foto_bukti: string;
+    foto_bukti_thumbnail?: string | null;
+    nomor?: string | null;
 }
 
 export interface BuktiPelunasanFormData {
@@ -0,0 +1,25 @@
+// Sama dengan db::JENIS_DOKUMEN_MOTOR di backend
+export const JENIS_DOKUMEN_MOTOR = ["STNK", "Pajak", "KIR", "Asuransi"] as const;
+export type JenisDokumenMotor = (typeof JENIS_DOKUMEN_MOTOR)[number];
+
+export interface DokumenMotor {
+    dokumen_id: number;
+    motor_id: number;
+    jenis: JenisDokumenMotor;
+    nomor: string;
+    tanggal_kadaluarsa: string;
+    foto?: string | null;
+    foto_thumbnail?: string | null;
+}
+
+export interface DokumenMotorKadaluarsa {
+    dokumen_id: number;
+    motor_id: number;
+    motor: string;
+    plat: string;
+    jenis: string;
+    nomor: string;
+    tanggal_kadaluarsa: string;
+    // Negatif berarti sudah lewat
+    sisa_hari: number;
+}
@@ -0,0 +1,23 @@
+export type JenisImpor = "motor" | "penyewa"
+
+export type StatusBarisImpor = "valid" | "duplikat" | "gagal"
+
+export interface ImporBaris {
+  baris: number
+  status: StatusBarisImpor
+  pesan: string
+}
+
+export interface ImporLaporan {
+  jenis: JenisImpor
+  dry_run: boolean
+  total_baris: number
+  valid: number
+  duplikat: number
+  gagal: number
+  diimpor: number
+  baris: ImporBaris[]
+}
+
+// field -> nama kolom di file; field yang tidak diisi dicocokkan otomatis
+export type PemetaanKolom = Record<string, string>
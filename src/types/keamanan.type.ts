@@ -0,0 +1,5 @@
+export interface StatusEnkripsiDatabase {
+  didukung: boolean
+  terenkripsi: boolean
+  terkunci: boolean
+}
@@ -0,0 +1,11 @@
+export interface Kerusakan {
+    kerusakan_id: number;
+    transaksi_id: number;
+    motor_id?: number;
+    tanggal: string;
+    deskripsi: string;
+    foto: string[];
+    foto_thumbnail?: (string | null)[];
+    estimasi_biaya: number;
+    biaya_dibebankan: number;
+}
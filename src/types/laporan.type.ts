@@ -0,0 +1,58 @@
+export interface ProfitMotor {
+    peringkat: number;
+    motor_id: number;
+    nama: string;
+    plat: string;
+    tipe_motor: string;
+    jumlah_transaksi: number;
+    pendapatan_sewa: number;
+    denda: number;
+    biaya_tambahan: number;
+    total_pendapatan: number;
+    diterima: number;
+    biaya_servis: number;
+    biaya_pengeluaran: number;
+    total_biaya: number;
+    hari_disewa: number;
+    hari_tersedia: number;
+    utilisasi: number;
+    laba_bersih: number;
+}
+
+export interface UtilisasiTitik {
+    tanggal: string;
+    hari: number;
+    motor_hari_tersedia: number;
+    motor_hari_disewa: number;
+    okupansi: number;
+    pendapatan: number;
+    revpar: number;
+}
+
+export interface UtilisasiTipe {
+    tipe_motor: string;
+    jumlah_motor: number;
+    motor_hari_tersedia: number;
+    motor_hari_disewa: number;
+    okupansi: number;
+    jumlah_transaksi: number;
+    rata_rata_lama_sewa: number;
+    pendapatan: number;
+    revpar: number;
+}
+
+export interface UtilisasiArmada {
+    tanggal_mulai: string;
+    tanggal_selesai: string;
+    jumlah_motor: number;
+    motor_hari_tersedia: number;
+    motor_hari_disewa: number;
+    okupansi: number;
+    jumlah_transaksi: number;
+    rata_rata_lama_sewa: number;
+    pendapatan: number;
+    revpar: number;
+    harian: UtilisasiTitik[];
+    mingguan: UtilisasiTitik[];
+    per_tipe: UtilisasiTipe[];
+}
@@ -6,7 +6,9 @@ export interface Motor {
   tahun: string
   harga_harian: number
   foto: string
+  foto_thumbnail?: string | null
   status: string
+  odometer?: number | null
 }
 
 export interface MotorFormData {
@@ -0,0 +1,3 @@
+export type JenisNomorDokumen = "transaksi" | "bukti_pelunasan" | "pengeluaran_rental";
+
+export type ResetNomor = "bulanan" | "tahunan" | "tidak";
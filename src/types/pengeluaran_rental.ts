@@ -5,4 +5,5 @@ export interface PengeluaranRental {
     nominal: number;
     keterangan: string;
     sumber_dana?: string;
+    nomor?: string | null;
 }
@@ -0,0 +1,12 @@
+export type JenisPengingat = "jatuh_tempo" | "terlambat" | "servis" | "dokumen"
+
+export interface Pengingat {
+  pengingat_id: number
+  jenis: JenisPengingat
+  ref_id?: number | null
+  judul: string
+  pesan: string
+  tanggal: string
+  dibaca: boolean
+  created_at?: string
+}
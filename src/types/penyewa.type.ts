@@ -1,7 +1,52 @@
+import { BuktiPelunasan } from "./bukti_pelunasan.type"
+import { Transaksi } from "./transaksi.type"
+
 export interface Penyewa {
   penyewa_id: number
   nama: string
   no_hp: string
   no_ktp: string
   alamat: string
-}
\ No newline at end of file
+}
+
+export interface NilaiPenyewa {
+  peringkat: number
+  penyewa_id: number
+  nama: string
+  no_hp: string
+  jumlah_transaksi: number
+  total_belanja: number
+  total_dibayar: number
+  jumlah_terlambat: number
+  total_denda: number
+  sisa_hutang: number
+  sewa_terakhir?: string | null
+}
+
+export interface RiwayatPenyewa {
+  penyewa: Penyewa
+  ringkasan: NilaiPenyewa
+  transaksi: Transaksi[]
+  pembayaran: BuktiPelunasan[]
+}
+
+export type StatusPenyewaFlag = "blacklist" | "waspada"
+
+export interface PenyewaFlag {
+  flag_id: number
+  no_ktp: string
+  status: StatusPenyewaFlag
+  alasan: string
+  tanggal: string
+}
+
+export type JenisDokumenPenyewa = "ktp" | "sim" | "selfie"
+
+export interface DokumenPenyewa {
+  dokumen_id: number
+  penyewa_id: number
+  jenis: JenisDokumenPenyewa
+  path: string
+  foto_thumbnail?: string | null
+  created_at: string
+}
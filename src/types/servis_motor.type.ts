@@ -0,0 +1,27 @@
+export interface ServisMotor {
+    servis_id: number;
+    motor_id: number;
+    tanggal: string;
+    jenis: string;
+    odometer?: number | null;
+    biaya: number;
+    suku_cadang?: string | null;
+    keterangan?: string | null;
+    servis_berikut_tanggal?: string | null;
+    servis_berikut_km?: number | null;
+    pengeluaran_id?: number | null;
+    // Jika true, biaya servis ikut dicatat sebagai pengeluaran rental
+    catat_pengeluaran?: boolean;
+}
+
+export interface MotorPerluServis {
+    motor_id: number;
+    nama: string;
+    plat: string;
+    status: string;
+    servis_terakhir?: string | null;
+    servis_berikut_tanggal?: string | null;
+    servis_berikut_km?: number | null;
+    odometer?: number | null;
+    alasan: string;
+}
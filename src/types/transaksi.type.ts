@@ -11,4 +11,46 @@ export interface Transaksi {
     denda?: number | null;
     diskon?: number | null;
     foto_bukti?: string | null;
-}
\ No newline at end of file
+    foto_bukti_thumbnail?: string | null;
+    nomor?: string | null;
+    km_awal?: number | null;
+    km_akhir?: number | null;
+    biaya_km_lebih?: number | null;
+    bbm_awal?: number | null;
+    bbm_akhir?: number | null;
+    biaya_bbm?: number | null;
+}
+// Rincian yang harus dibayar untuk satu transaksi (get_tagihan_transaksi)
+export interface Tagihan {
+    transaksi_id: number;
+    total_bayar: number;
+    denda: number;
+    biaya_km_lebih: number;
+    biaya_bbm: number;
+    biaya_kerusakan: number;
+    total_tagihan: number;
+    dibayar: number;
+    sisa_bayar: number;
+}
+// Payload event "transaksi-terlambat" dari scheduler backend
+export interface TransaksiTerlambat {
+    transaksi_id: number;
+    nomor?: string | null;
+    penyewa: string;
+    no_hp: string;
+    motor: string;
+    plat: string;
+    tanggal_kembali_rencana: string;
+    hari_terlambat: number;
+    denda: number;
+    baru_terlambat: boolean;
+}
+// Jenis template pesan WhatsApp, key pengaturan "template_wa_<jenis>"
+export type JenisPesanWa = "pengingat" | "terlambat" | "tagihan";
+// Hasil render_pesan_wa / kirim_pesan_wa
+export interface PesanWa {
+    transaksi_id: number;
+    no_hp: string;
+    pesan: string;
+    link: string;
+}